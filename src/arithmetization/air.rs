@@ -0,0 +1,523 @@
+use crate::fields::batch::batch_inverse;
+use crate::fields::element::FieldElement;
+use crate::polynomial::dense::Polynomial;
+use crate::polynomial::domain::EvaluationDomain;
+use crate::polynomial::multivariate::SparsePolynomial;
+use cryptography::{FftField, Field};
+
+// execution trace stored by columns, its length is a power of two
+pub struct TraceTable<F: Field> {
+    columns: Vec<Vec<FieldElement<F>>>,
+}
+
+impl<F> TraceTable<F>
+where
+    F: Field,
+{
+    pub fn new(columns: Vec<Vec<FieldElement<F>>>) -> Result<Self, &'static str> {
+        if columns.is_empty() {
+            return Err("trace needs at least one column");
+        }
+        let length = columns[0].len();
+        if !length.is_power_of_two() {
+            return Err("trace length must be a power of two");
+        }
+        if columns.iter().any(|column| column.len() != length) {
+            return Err("trace columns have different lengths");
+        }
+
+        Ok(Self { columns })
+    }
+
+    pub fn from_rows(rows: Vec<Vec<FieldElement<F>>>) -> Result<Self, &'static str> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return Err("trace rows have different lengths");
+        }
+
+        let columns = (0..width)
+            .map(|c| rows.iter().map(|row| row[c].clone()).collect())
+            .collect();
+        Self::new(columns)
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn length(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    pub fn column(&self, c: usize) -> &[FieldElement<F>] {
+        &self.columns[c]
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> &FieldElement<F> {
+        &self.columns[column][row]
+    }
+
+    pub fn row(&self, i: usize) -> Vec<FieldElement<F>> {
+        self.columns
+            .iter()
+            .map(|column| column[i].clone())
+            .collect()
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: FieldElement<F>) {
+        self.columns[column][row] = value;
+    }
+}
+
+// trace[row][column] = value
+pub struct BoundaryConstraint<F: Field> {
+    pub column: usize,
+    pub row: usize,
+    pub value: FieldElement<F>,
+}
+
+// trace polynomials interpolated over the trace domain <g> and evaluated on a coset
+// blowup times larger, point i of the coset has its next row at i + blowup
+pub struct TraceLde<F: FftField> {
+    blowup: usize,
+    domain: EvaluationDomain<F>,
+    polynomials: Vec<Vec<FieldElement<F>>>,
+    columns: Vec<Vec<FieldElement<F>>>,
+}
+
+impl<F> TraceLde<F>
+where
+    F: FftField,
+{
+    pub fn new(trace: &TraceTable<F>, blowup: usize) -> Result<Self, &'static str> {
+        if blowup < 2 || !blowup.is_power_of_two() {
+            return Err("blowup must be a power of two of at least 2");
+        }
+
+        let trace_domain = EvaluationDomain::<F>::with_offset(trace.length(), FieldElement::one())?;
+        let domain = EvaluationDomain::<F>::new(trace.length() * blowup)?;
+        let polynomials: Vec<Vec<FieldElement<F>>> = trace
+            .columns
+            .iter()
+            .map(|column| trace_domain.ifft(column))
+            .collect();
+        let columns = polynomials.iter().map(|p| domain.coset_fft(p)).collect();
+
+        Ok(Self {
+            blowup,
+            domain,
+            polynomials,
+            columns,
+        })
+    }
+
+    pub fn blowup(&self) -> usize {
+        self.blowup
+    }
+
+    pub fn domain(&self) -> &EvaluationDomain<F> {
+        &self.domain
+    }
+
+    // coefficients of the trace column polynomials
+    pub fn polynomials(&self) -> &[Vec<FieldElement<F>>] {
+        &self.polynomials
+    }
+
+    pub fn columns(&self) -> &[Vec<FieldElement<F>>] {
+        &self.columns
+    }
+
+    // values of all columns at point i
+    pub fn row(&self, i: usize) -> Vec<FieldElement<F>> {
+        self.columns
+            .iter()
+            .map(|column| column[i].clone())
+            .collect()
+    }
+
+    pub fn next_row(&self, i: usize) -> Vec<FieldElement<F>> {
+        self.row((i + self.blowup) % self.domain.size())
+    }
+}
+
+// algebraic intermediate representation: transition constraints are polynomials in
+// 2 * width variables, the current row followed by the next row, that vanish on
+// every pair of consecutive rows, boundary constraints fix single cells
+pub struct Air<F: FftField> {
+    width: usize,
+    trace_length: usize,
+    transitions: Vec<SparsePolynomial<F>>,
+    boundaries: Vec<BoundaryConstraint<F>>,
+}
+
+impl<F> Air<F>
+where
+    F: FftField,
+{
+    pub fn new(
+        width: usize,
+        trace_length: usize,
+        transitions: Vec<SparsePolynomial<F>>,
+        boundaries: Vec<BoundaryConstraint<F>>,
+    ) -> Result<Self, &'static str> {
+        if !trace_length.is_power_of_two() || trace_length < 2 {
+            return Err("trace length must be a power of two of at least 2");
+        }
+        if F::root_of_unity(trace_length as u64).is_none() {
+            return Err("no root of unity of the trace length in the field");
+        }
+        if transitions.iter().any(|t| t.num_variables() != 2 * width) {
+            return Err("transition constraints must have 2 * width variables");
+        }
+        if boundaries
+            .iter()
+            .any(|b| b.column >= width || b.row >= trace_length)
+        {
+            return Err("boundary constraint outside the trace");
+        }
+
+        Ok(Self {
+            width,
+            trace_length,
+            transitions,
+            boundaries,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    pub fn transitions(&self) -> &[SparsePolynomial<F>] {
+        &self.transitions
+    }
+
+    pub fn boundaries(&self) -> &[BoundaryConstraint<F>] {
+        &self.boundaries
+    }
+
+    // one composition coefficient per constraint
+    pub fn num_constraints(&self) -> usize {
+        self.transitions.len() + self.boundaries.len()
+    }
+
+    pub fn transition_degree(&self) -> usize {
+        self.transitions
+            .iter()
+            .filter_map(|t| t.total_degree())
+            .max()
+            .unwrap_or(1)
+    }
+
+    // a transition of degree d applied to trace polynomials of degree n - 1 and divided
+    // by a vanishing polynomial of degree n - 1 has degree below (d - 1) n, boundary
+    // quotients stay below n
+    pub fn composition_degree_bound(&self) -> usize {
+        self.trace_length * self.transition_degree().saturating_sub(1).max(1)
+    }
+
+    // generator g of the trace domain
+    pub fn trace_generator(&self) -> FieldElement<F> {
+        FieldElement::<F>::new(F::root_of_unity(self.trace_length as u64).unwrap())
+    }
+
+    fn frame(current: &[FieldElement<F>], next: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        current.iter().chain(next.iter()).cloned().collect()
+    }
+
+    // the first violated constraint on the trace itself
+    pub fn check(&self, trace: &TraceTable<F>) -> Result<(), &'static str> {
+        if trace.width() != self.width || trace.length() != self.trace_length {
+            return Err("trace has the wrong shape");
+        }
+        for b in self.boundaries.iter() {
+            if *trace.get(b.row, b.column) != b.value {
+                return Err("boundary constraint is violated");
+            }
+        }
+        for i in 0..self.trace_length - 1 {
+            let frame = Self::frame(&trace.row(i), &trace.row(i + 1));
+            for t in self.transitions.iter() {
+                if !t.evaluate(&frame)?.is_zero() {
+                    return Err("transition constraint is violated");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // sum of α_j C_j(x) (x - g^(n - 1)) / (x^n - 1) over the transitions and of
+    // α_b (T_c(x) - v) / (x - g^row) over the boundaries, at a point x outside the
+    // trace domain given the trace values at x and g x
+    pub fn evaluate_composition(
+        &self,
+        x: &FieldElement<F>,
+        current: &[FieldElement<F>],
+        next: &[FieldElement<F>],
+        coefficients: &[FieldElement<F>],
+    ) -> Result<FieldElement<F>, &'static str> {
+        if coefficients.len() != self.num_constraints() {
+            return Err("one coefficient per constraint is required");
+        }
+        if current.len() != self.width || next.len() != self.width {
+            return Err("frame has the wrong width");
+        }
+
+        let g = self.trace_generator();
+        let last = g.pow_u64(self.trace_length as u64 - 1);
+        let vanishing = &x.pow_u64(self.trace_length as u64) - &FieldElement::<F>::one();
+        let transition_factor = &(x - &last)
+            * &vanishing
+                .inverse()
+                .ok_or("point lies in the trace domain")?;
+
+        let frame = Self::frame(current, next);
+        let mut result = FieldElement::<F>::zero();
+        for (t, alpha) in self.transitions.iter().zip(coefficients.iter()) {
+            result += &(&(alpha * &t.evaluate(&frame)?) * &transition_factor);
+        }
+        let alphas = &coefficients[self.transitions.len()..];
+        for (b, alpha) in self.boundaries.iter().zip(alphas.iter()) {
+            let denominator = x - &g.pow_u64(b.row as u64);
+            let inverse = denominator
+                .inverse()
+                .ok_or("point lies in the trace domain")?;
+            result += &(&(alpha * &(&current[b.column] - &b.value)) * &inverse);
+        }
+        Ok(result)
+    }
+
+    // the composition over every point of the LDE domain, with the divisions batched
+    pub fn composition_evaluations(
+        &self,
+        lde: &TraceLde<F>,
+        coefficients: &[FieldElement<F>],
+    ) -> Result<Vec<FieldElement<F>>, &'static str> {
+        if coefficients.len() != self.num_constraints() {
+            return Err("one coefficient per constraint is required");
+        }
+        if lde.columns.len() != self.width || lde.domain.size() != self.trace_length * lde.blowup {
+            return Err("LDE has the wrong shape");
+        }
+        if self.composition_degree_bound() >= lde.domain.size() {
+            return Err("blowup is too small for the constraint degree");
+        }
+
+        let n = self.trace_length as u64;
+        let g = self.trace_generator();
+        let last = g.pow_u64(n - 1);
+        let xs = lde.domain.coset_elements();
+        let one = FieldElement::<F>::one();
+
+        let vanishing: Vec<FieldElement<F>> = xs.iter().map(|x| &x.pow_u64(n) - &one).collect();
+        let vanishing_inv = batch_inverse(&vanishing);
+
+        let mut result = Vec::with_capacity(xs.len());
+        for (i, x) in xs.iter().enumerate() {
+            let frame = Self::frame(&lde.row(i), &lde.next_row(i));
+            let mut sum = FieldElement::<F>::zero();
+            for (t, alpha) in self.transitions.iter().zip(coefficients.iter()) {
+                sum += &(alpha * &t.evaluate(&frame)?);
+            }
+            result.push(&(&sum * &(x - &last)) * &vanishing_inv[i]);
+        }
+
+        let alphas = &coefficients[self.transitions.len()..];
+        for (b, alpha) in self.boundaries.iter().zip(alphas.iter()) {
+            let point = g.pow_u64(b.row as u64);
+            let denominators: Vec<FieldElement<F>> = xs.iter().map(|x| x - &point).collect();
+            let column = &lde.columns[b.column];
+            for ((value, inverse), r) in column
+                .iter()
+                .zip(batch_inverse(&denominators).iter())
+                .zip(result.iter_mut())
+            {
+                *r += &(&(alpha * &(value - &b.value)) * inverse);
+            }
+        }
+        Ok(result)
+    }
+
+    // the composition in coefficient form, of degree below composition_degree_bound
+    // exactly when the trace satisfies the constraints (with high probability over the
+    // coefficients)
+    pub fn composition_polynomial(
+        &self,
+        lde: &TraceLde<F>,
+        coefficients: &[FieldElement<F>],
+    ) -> Result<Polynomial<F>, &'static str> {
+        let evaluations = self.composition_evaluations(lde, coefficients)?;
+        Ok(Polynomial::new(lde.domain.coset_ifft(&evaluations)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::u64_field::U64Field;
+    use crate::polynomial::multivariate::MonomialOrder;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+    type Poly = SparsePolynomial<Goldilocks>;
+
+    fn var(i: usize, width: usize) -> Poly {
+        Poly::variable(i, 2 * width, MonomialOrder::GradedLex)
+    }
+
+    // columns (a, b) with a' = b and b' = a + b starting from (1, 1)
+    fn fibonacci(n: usize) -> (Air<Goldilocks>, TraceTable<Goldilocks>) {
+        let mut rows = vec![vec![Element::one(), Element::one()]];
+        for i in 1..n {
+            let previous = &rows[i - 1];
+            rows.push(vec![previous[1].clone(), &previous[0] + &previous[1]]);
+        }
+        let result = rows[n - 1][1].clone();
+
+        let transitions = vec![
+            &var(2, 2) - &var(1, 2),
+            &(&var(3, 2) - &var(0, 2)) - &var(1, 2),
+        ];
+        let boundaries = vec![
+            BoundaryConstraint {
+                column: 0,
+                row: 0,
+                value: Element::one(),
+            },
+            BoundaryConstraint {
+                column: 1,
+                row: 0,
+                value: Element::one(),
+            },
+            BoundaryConstraint {
+                column: 1,
+                row: n - 1,
+                value: result,
+            },
+        ];
+
+        let air = Air::new(2, n, transitions, boundaries).unwrap();
+        (air, TraceTable::from_rows(rows).unwrap())
+    }
+
+    fn random_coefficients(air: &Air<Goldilocks>) -> Vec<Element> {
+        (0..air.num_constraints())
+            .map(|_| Element::random())
+            .collect()
+    }
+
+    #[test]
+    fn test_trace_table() {
+        let (_, trace) = fibonacci(8);
+        assert_eq!(trace.width(), 2);
+        assert_eq!(trace.length(), 8);
+        assert!(*trace.get(7, 1) == Element::from(34));
+        assert!(trace.row(2) == vec![Element::from(2), Element::from(3)]);
+
+        assert!(TraceTable::<Goldilocks>::new(vec![vec![Element::one(); 3]]).is_err());
+        assert!(TraceTable::<Goldilocks>::from_rows(vec![vec![Element::one()], vec![]]).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let (air, mut trace) = fibonacci(16);
+        assert!(air.check(&trace).is_ok());
+
+        trace.set(5, 0, Element::from(1000));
+        assert_eq!(air.check(&trace), Err("transition constraint is violated"));
+        trace.set(0, 0, Element::from(2));
+        assert_eq!(air.check(&trace), Err("boundary constraint is violated"));
+    }
+
+    #[test]
+    fn test_lde() {
+        let (_, trace) = fibonacci(8);
+        let lde = TraceLde::new(&trace, 4).unwrap();
+
+        assert_eq!(lde.domain().size(), 32);
+        for (column, polynomial) in lde.polynomials().iter().enumerate() {
+            let p = Polynomial::new(polynomial.clone());
+            // the interpolant passes through the trace and its LDE is its evaluation
+            let g = Element::new(Goldilocks::root_of_unity(8).unwrap());
+            for row in 0..8 {
+                assert!(p.evaluate(&g.pow_u64(row as u64)) == *trace.get(row, column));
+            }
+            for (x, y) in lde
+                .domain()
+                .coset_elements()
+                .iter()
+                .zip(lde.columns()[column].iter())
+            {
+                assert!(p.evaluate(x) == *y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_composition_degree() {
+        let (air, mut trace) = fibonacci(16);
+        let coefficients = random_coefficients(&air);
+        let bound = air.composition_degree_bound();
+        assert_eq!(bound, 16);
+
+        let lde = TraceLde::new(&trace, 4).unwrap();
+        let composition = air.composition_polynomial(&lde, &coefficients).unwrap();
+        assert!(composition.degree().unwrap() < bound);
+
+        // a single wrong cell makes the quotients non-polynomial
+        trace.set(9, 1, Element::from(7));
+        let lde = TraceLde::new(&trace, 4).unwrap();
+        let composition = air.composition_polynomial(&lde, &coefficients).unwrap();
+        assert!(composition.degree().unwrap() >= bound);
+    }
+
+    #[test]
+    fn test_out_of_domain_evaluation() {
+        // x' = x^2, a degree 2 transition
+        let n = 8;
+        let mut column = vec![Element::from(3)];
+        for i in 1..n {
+            column.push(column[i - 1].square());
+        }
+        let transitions = vec![&var(1, 1) - &(&var(0, 1) * &var(0, 1))];
+        let boundaries = vec![BoundaryConstraint {
+            column: 0,
+            row: 0,
+            value: Element::from(3),
+        }];
+        let air = Air::new(1, n, transitions, boundaries).unwrap();
+        let trace = TraceTable::new(vec![column]).unwrap();
+        assert!(air.check(&trace).is_ok());
+
+        let coefficients = random_coefficients(&air);
+        let lde = TraceLde::new(&trace, 4).unwrap();
+        let composition = air.composition_polynomial(&lde, &coefficients).unwrap();
+        assert!(composition.degree().unwrap() < air.composition_degree_bound());
+
+        let z = Element::random();
+        let g = air.trace_generator();
+        let t = Polynomial::new(lde.polynomials()[0].clone());
+        let value = air
+            .evaluate_composition(
+                &z,
+                &[t.evaluate(&z)],
+                &[t.evaluate(&(&g * &z))],
+                &coefficients,
+            )
+            .unwrap();
+        assert!(value == composition.evaluate(&z));
+
+        // a blowup of 2 cannot hold the quotient of a degree 3 constraint
+        let cubic = vec![&var(1, 1) - &(&(&var(0, 1) * &var(0, 1)) * &var(0, 1))];
+        let air = Air::new(1, n, cubic, Vec::new()).unwrap();
+        let lde = TraceLde::new(&trace, 2).unwrap();
+        assert!(air
+            .composition_evaluations(&lde, &[Element::one()])
+            .is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod air;
+pub mod plonk;
+pub mod r1cs;
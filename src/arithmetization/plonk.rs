@@ -0,0 +1,367 @@
+use crate::fields::element::FieldElement;
+use cryptography::Field;
+use std::collections::HashMap;
+
+// index into the values of a circuit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Variable(pub usize);
+
+// q_l a + q_r b + q_o c + q_m a b + q_c = 0
+#[derive(Debug)]
+pub struct Selectors<F: Field> {
+    pub q_l: FieldElement<F>,
+    pub q_r: FieldElement<F>,
+    pub q_o: FieldElement<F>,
+    pub q_m: FieldElement<F>,
+    pub q_c: FieldElement<F>,
+}
+
+impl<F> Clone for Selectors<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            q_l: self.q_l.clone(),
+            q_r: self.q_r.clone(),
+            q_o: self.q_o.clone(),
+            q_m: self.q_m.clone(),
+            q_c: self.q_c.clone(),
+        }
+    }
+}
+
+impl<F> Selectors<F>
+where
+    F: Field,
+{
+    pub fn zero() -> Self {
+        Self {
+            q_l: FieldElement::zero(),
+            q_r: FieldElement::zero(),
+            q_o: FieldElement::zero(),
+            q_m: FieldElement::zero(),
+            q_c: FieldElement::zero(),
+        }
+    }
+
+    pub fn evaluate(
+        &self,
+        a: &FieldElement<F>,
+        b: &FieldElement<F>,
+        c: &FieldElement<F>,
+    ) -> FieldElement<F> {
+        let linear = &(&(&self.q_l * a) + &(&self.q_r * b)) + &(&self.q_o * c);
+        &(&linear + &(&self.q_m * &(a * b))) + &self.q_c
+    }
+}
+
+// the variables on the a, b and c wires of a row
+#[derive(Debug)]
+pub struct Gate<F: Field> {
+    pub wires: [Variable; 3],
+    pub selectors: Selectors<F>,
+}
+
+impl<F> Clone for Gate<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            wires: self.wires,
+            selectors: self.selectors.clone(),
+        }
+    }
+}
+
+// PLONK arithmetization: a table of gates, and copy constraints that follow from
+// variables appearing on several wires. Public inputs occupy the first rows as
+// a - x = 0, with x supplied by the verifier.
+pub struct Circuit<F: Field> {
+    values: Vec<FieldElement<F>>,
+    inputs: Vec<Variable>,
+    gates: Vec<Gate<F>>,
+    zero: Variable,
+}
+
+impl<F> Default for Circuit<F>
+where
+    F: Field,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> Circuit<F>
+where
+    F: Field,
+{
+    // starts with a variable constrained to zero, used for unused wires and padding
+    pub fn new() -> Self {
+        let mut circuit = Self {
+            values: vec![FieldElement::zero()],
+            inputs: Vec::new(),
+            gates: Vec::new(),
+            zero: Variable(0),
+        };
+        let zero = circuit.zero;
+        let selectors = Selectors {
+            q_l: FieldElement::one(),
+            ..Selectors::zero()
+        };
+        circuit.gate([zero, zero, zero], selectors);
+        circuit
+    }
+
+    pub fn zero(&self) -> Variable {
+        self.zero
+    }
+
+    pub fn alloc(&mut self, value: FieldElement<F>) -> Variable {
+        self.values.push(value);
+        Variable(self.values.len() - 1)
+    }
+
+    pub fn alloc_input(&mut self, value: FieldElement<F>) -> Variable {
+        let variable = self.alloc(value);
+        self.inputs.push(variable);
+        variable
+    }
+
+    pub fn value(&self, variable: Variable) -> &FieldElement<F> {
+        &self.values[variable.0]
+    }
+
+    pub fn set_value(&mut self, variable: Variable, value: FieldElement<F>) {
+        self.values[variable.0] = value;
+    }
+
+    pub fn gate(&mut self, wires: [Variable; 3], selectors: Selectors<F>) {
+        self.gates.push(Gate { wires, selectors });
+    }
+
+    pub fn public_inputs(&self) -> Vec<FieldElement<F>> {
+        self.inputs.iter().map(|v| self.value(*v).clone()).collect()
+    }
+
+    pub fn num_public_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.inputs.len() + self.gates.len()
+    }
+
+    // the input rows followed by the gates
+    pub fn rows(&self) -> Vec<Gate<F>> {
+        let input_selectors = Selectors {
+            q_l: FieldElement::one(),
+            ..Selectors::zero()
+        };
+        self.inputs
+            .iter()
+            .map(|input| Gate {
+                wires: [*input, self.zero, self.zero],
+                selectors: input_selectors.clone(),
+            })
+            .chain(self.gates.iter().cloned())
+            .collect()
+    }
+
+    // wire values of the rows padded with zero rows to n, by column
+    pub fn wire_values(&self, n: usize) -> [Vec<FieldElement<F>>; 3] {
+        let rows = self.rows();
+        std::array::from_fn(|column| {
+            (0..n)
+                .map(|row| {
+                    let variable = rows.get(row).map_or(self.zero, |g| g.wires[column]);
+                    self.value(variable).clone()
+                })
+                .collect()
+        })
+    }
+
+    // permutation of the 3n wire positions column * n + row, every variable's
+    // positions form one cycle, padding rows are wired to zero
+    pub fn permutation(&self, n: usize) -> Result<Vec<usize>, &'static str> {
+        let rows = self.rows();
+        if rows.len() > n {
+            return Err("circuit does not fit in the domain");
+        }
+
+        let mut positions: HashMap<Variable, Vec<usize>> = HashMap::new();
+        for column in 0..3 {
+            for row in 0..n {
+                let variable = rows.get(row).map_or(self.zero, |g| g.wires[column]);
+                positions
+                    .entry(variable)
+                    .or_default()
+                    .push(column * n + row);
+            }
+        }
+
+        let mut sigma: Vec<usize> = (0..3 * n).collect();
+        for cycle in positions.values() {
+            for (i, position) in cycle.iter().enumerate() {
+                sigma[*position] = cycle[(i + 1) % cycle.len()];
+            }
+        }
+        Ok(sigma)
+    }
+
+    // index of the first row whose gate does not hold, input rows always hold
+    pub fn which_is_unsatisfied(&self) -> Option<usize> {
+        self.gates
+            .iter()
+            .position(|gate| {
+                let [a, b, c] = gate.wires.map(|w| self.value(w));
+                !gate.selectors.evaluate(a, b, c).is_zero()
+            })
+            .map(|i| i + self.inputs.len())
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.which_is_unsatisfied().is_none()
+    }
+
+    // gadgets
+
+    // new variable equal to a + b
+    pub fn add(&mut self, a: Variable, b: Variable) -> Variable {
+        let sum = self.value(a) + self.value(b);
+        let c = self.alloc(sum);
+        let selectors = Selectors {
+            q_l: FieldElement::one(),
+            q_r: FieldElement::one(),
+            q_o: -&FieldElement::one(),
+            ..Selectors::zero()
+        };
+        self.gate([a, b, c], selectors);
+        c
+    }
+
+    // new variable equal to a * b
+    pub fn mul(&mut self, a: Variable, b: Variable) -> Variable {
+        let product = self.value(a) * self.value(b);
+        let c = self.alloc(product);
+        let selectors = Selectors {
+            q_o: -&FieldElement::one(),
+            q_m: FieldElement::one(),
+            ..Selectors::zero()
+        };
+        self.gate([a, b, c], selectors);
+        c
+    }
+
+    // new variable equal to a + k
+    pub fn add_constant(&mut self, a: Variable, k: FieldElement<F>) -> Variable {
+        let sum = self.value(a) + &k;
+        let c = self.alloc(sum);
+        let selectors = Selectors {
+            q_l: FieldElement::one(),
+            q_o: -&FieldElement::one(),
+            q_c: k,
+            ..Selectors::zero()
+        };
+        self.gate([a, self.zero, c], selectors);
+        c
+    }
+
+    // new variable fixed to k
+    pub fn constant(&mut self, k: FieldElement<F>) -> Variable {
+        let c = self.alloc(k.clone());
+        let selectors = Selectors {
+            q_l: FieldElement::one(),
+            q_c: -&k,
+            ..Selectors::zero()
+        };
+        self.gate([c, self.zero, self.zero], selectors);
+        c
+    }
+
+    // a - b = 0
+    pub fn assert_equal(&mut self, a: Variable, b: Variable) {
+        let selectors = Selectors {
+            q_l: FieldElement::one(),
+            q_r: -&FieldElement::one(),
+            ..Selectors::zero()
+        };
+        self.gate([a, b, self.zero], selectors);
+    }
+
+    // a^2 - a = 0
+    pub fn assert_boolean(&mut self, a: Variable) {
+        let selectors = Selectors {
+            q_l: -&FieldElement::one(),
+            q_m: FieldElement::one(),
+            ..Selectors::zero()
+        };
+        self.gate([a, a, self.zero], selectors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    // x^3 + x + 5 = out with public out
+    fn cubic(x: u64, out: u64) -> Circuit<Goldilocks> {
+        let mut circuit = Circuit::new();
+        let out = circuit.alloc_input(Element::from(out));
+        let x = circuit.alloc(Element::from(x));
+
+        let x_squared = circuit.mul(x, x);
+        let x_cubed = circuit.mul(x_squared, x);
+        let sum = circuit.add(x_cubed, x);
+        let result = circuit.add_constant(sum, Element::from(5));
+        circuit.assert_equal(result, out);
+        circuit
+    }
+
+    #[test]
+    fn test_satisfiability() {
+        let circuit = cubic(3, 35);
+        assert!(circuit.is_satisfied());
+        assert_eq!(circuit.num_rows(), 7);
+        assert!(circuit.public_inputs() == vec![Element::from(35)]);
+
+        assert_eq!(cubic(3, 36).which_is_unsatisfied(), Some(6));
+
+        let mut circuit = cubic(3, 35);
+        circuit.set_value(Variable(3), Element::from(10));
+        assert_eq!(circuit.which_is_unsatisfied(), Some(2));
+    }
+
+    #[test]
+    fn test_permutation() {
+        let circuit = cubic(3, 35);
+        let n = 8;
+        let sigma = circuit.permutation(n).unwrap();
+        let values = circuit.wire_values(n).concat();
+
+        // a permutation that only moves positions between equal values
+        let mut sorted = sigma.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..3 * n).collect::<Vec<_>>());
+        for (i, j) in sigma.iter().enumerate() {
+            assert!(values[i] == values[*j]);
+        }
+
+        // x sits on both wires of the first multiplication and on the b wires of the
+        // second multiplication and the addition, after the input row and the zero row
+        let mut cycle = vec![2];
+        while sigma[*cycle.last().unwrap()] != cycle[0] {
+            cycle.push(sigma[*cycle.last().unwrap()]);
+        }
+        cycle.sort();
+        assert_eq!(cycle, vec![2, n + 2, n + 3, n + 4]);
+
+        assert!(circuit.permutation(4).is_err());
+    }
+}
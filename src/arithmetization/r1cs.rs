@@ -0,0 +1,496 @@
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use cryptography::Field;
+use std::ops::{Add, Neg, Sub};
+
+// public inputs and private witnesses are numbered separately, input 0 is the
+// constant one. In the full assignment the inputs come first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Variable {
+    Input(usize),
+    Witness(usize),
+}
+
+impl Variable {
+    pub const ONE: Variable = Variable::Input(0);
+}
+
+// sum of coefficient * variable
+#[derive(Debug)]
+pub struct LinearCombination<F: Field> {
+    terms: Vec<(Variable, FieldElement<F>)>,
+}
+
+impl<F> Clone for LinearCombination<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+impl<F> From<Variable> for LinearCombination<F>
+where
+    F: Field,
+{
+    fn from(variable: Variable) -> Self {
+        Self::zero().term(variable, FieldElement::<F>::one())
+    }
+}
+
+impl<F> LinearCombination<F>
+where
+    F: Field,
+{
+    pub fn zero() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    pub fn constant(c: FieldElement<F>) -> Self {
+        Self::zero().term(Variable::ONE, c)
+    }
+
+    pub fn term(mut self, variable: Variable, coefficient: FieldElement<F>) -> Self {
+        self.terms.push((variable, coefficient));
+        self
+    }
+
+    pub fn terms(&self) -> &[(Variable, FieldElement<F>)] {
+        &self.terms
+    }
+
+    pub fn scale(&self, factor: &FieldElement<F>) -> Self {
+        Self {
+            terms: self.terms.iter().map(|(v, c)| (*v, c * factor)).collect(),
+        }
+    }
+}
+
+impl<F> Add<&LinearCombination<F>> for &LinearCombination<F>
+where
+    F: Field,
+{
+    type Output = LinearCombination<F>;
+
+    fn add(self, other: &LinearCombination<F>) -> Self::Output {
+        let mut terms = self.terms.clone();
+        terms.extend(other.terms.iter().cloned());
+        LinearCombination { terms }
+    }
+}
+
+impl<F> Sub<&LinearCombination<F>> for &LinearCombination<F>
+where
+    F: Field,
+{
+    type Output = LinearCombination<F>;
+
+    fn sub(self, other: &LinearCombination<F>) -> Self::Output {
+        self + &(-other)
+    }
+}
+
+impl<F> Neg for &LinearCombination<F>
+where
+    F: Field,
+{
+    type Output = LinearCombination<F>;
+
+    fn neg(self) -> Self::Output {
+        LinearCombination {
+            terms: self.terms.iter().map(|(v, c)| (*v, -c)).collect(),
+        }
+    }
+}
+
+// rows of (column, coefficient)
+pub type SparseMatrix<F> = Vec<Vec<(usize, FieldElement<F>)>>;
+
+// <a, z> * <b, z> = <c, z> for the full assignment z
+pub struct Constraint<F: Field> {
+    pub a: LinearCombination<F>,
+    pub b: LinearCombination<F>,
+    pub c: LinearCombination<F>,
+    pub label: String,
+}
+
+// rank-1 constraint system together with the assignment built alongside it, gadgets
+// allocate their outputs with values computed from their inputs
+pub struct ConstraintSystem<F: Field> {
+    inputs: Vec<FieldElement<F>>,
+    witnesses: Vec<FieldElement<F>>,
+    constraints: Vec<Constraint<F>>,
+}
+
+impl<F> Default for ConstraintSystem<F>
+where
+    F: Field,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> ConstraintSystem<F>
+where
+    F: Field,
+{
+    pub fn new() -> Self {
+        Self {
+            inputs: vec![FieldElement::<F>::one()],
+            witnesses: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn alloc_input(&mut self, value: FieldElement<F>) -> Variable {
+        self.inputs.push(value);
+        Variable::Input(self.inputs.len() - 1)
+    }
+
+    pub fn alloc(&mut self, value: FieldElement<F>) -> Variable {
+        self.witnesses.push(value);
+        Variable::Witness(self.witnesses.len() - 1)
+    }
+
+    pub fn enforce(
+        &mut self,
+        a: LinearCombination<F>,
+        b: LinearCombination<F>,
+        c: LinearCombination<F>,
+        label: &str,
+    ) {
+        self.constraints.push(Constraint {
+            a,
+            b,
+            c,
+            label: label.to_string(),
+        });
+    }
+
+    pub fn value(&self, variable: Variable) -> &FieldElement<F> {
+        match variable {
+            Variable::Input(i) => &self.inputs[i],
+            Variable::Witness(i) => &self.witnesses[i],
+        }
+    }
+
+    pub fn set_value(&mut self, variable: Variable, value: FieldElement<F>) {
+        match variable {
+            Variable::Input(i) => self.inputs[i] = value,
+            Variable::Witness(i) => self.witnesses[i] = value,
+        }
+    }
+
+    pub fn evaluate(&self, lc: &LinearCombination<F>) -> FieldElement<F> {
+        let mut sum = FieldElement::<F>::zero();
+        for (variable, coefficient) in lc.terms.iter() {
+            sum += &(coefficient * self.value(*variable));
+        }
+        sum
+    }
+
+    // number of inputs including the constant one
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn num_witnesses(&self) -> usize {
+        self.witnesses.len()
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.inputs.len() + self.witnesses.len()
+    }
+
+    pub fn constraints(&self) -> &[Constraint<F>] {
+        &self.constraints
+    }
+
+    // position of a variable in the full assignment
+    pub fn column(&self, variable: Variable) -> usize {
+        match variable {
+            Variable::Input(i) => i,
+            Variable::Witness(i) => self.inputs.len() + i,
+        }
+    }
+
+    // (1, public inputs..., witnesses...)
+    pub fn assignment(&self) -> Vec<FieldElement<F>> {
+        self.inputs
+            .iter()
+            .chain(self.witnesses.iter())
+            .cloned()
+            .collect()
+    }
+
+    // public inputs without the leading one
+    pub fn public_inputs(&self) -> &[FieldElement<F>] {
+        &self.inputs[1..]
+    }
+
+    // the A, B and C matrices as sparse rows of (column, coefficient), with repeated
+    // variables within a row merged
+    pub fn matrices(&self) -> [SparseMatrix<F>; 3] {
+        let row = |lc: &LinearCombination<F>| {
+            let mut entries: Vec<(usize, FieldElement<F>)> = Vec::new();
+            for (variable, coefficient) in lc.terms.iter() {
+                let column = self.column(*variable);
+                match entries.iter_mut().find(|(c, _)| *c == column) {
+                    Some((_, sum)) => *sum += coefficient,
+                    None => entries.push((column, coefficient.clone())),
+                }
+            }
+            entries.retain(|(_, c)| !c.is_zero());
+            entries.sort_by_key(|(c, _)| *c);
+            entries
+        };
+
+        [
+            self.constraints.iter().map(|c| row(&c.a)).collect(),
+            self.constraints.iter().map(|c| row(&c.b)).collect(),
+            self.constraints.iter().map(|c| row(&c.c)).collect(),
+        ]
+    }
+
+    // index and label of the first constraint the assignment violates
+    pub fn which_is_unsatisfied(&self) -> Option<(usize, &str)> {
+        self.constraints
+            .iter()
+            .position(|c| &self.evaluate(&c.a) * &self.evaluate(&c.b) != self.evaluate(&c.c))
+            .map(|i| (i, &self.constraints[i].label[..]))
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.which_is_unsatisfied().is_none()
+    }
+
+    // gadgets
+
+    // new variable equal to a * b
+    pub fn mul(&mut self, a: &LinearCombination<F>, b: &LinearCombination<F>) -> Variable {
+        let product = &self.evaluate(a) * &self.evaluate(b);
+        let result = self.alloc(product);
+        self.enforce(a.clone(), b.clone(), result.into(), "multiplication");
+        result
+    }
+
+    // a = b
+    pub fn enforce_equal(&mut self, a: &LinearCombination<F>, b: &LinearCombination<F>) {
+        self.enforce(
+            a - b,
+            Variable::ONE.into(),
+            LinearCombination::zero(),
+            "equality",
+        );
+    }
+
+    // v (1 - v) = 0
+    pub fn enforce_boolean(&mut self, variable: Variable) {
+        let one: LinearCombination<F> = Variable::ONE.into();
+        self.enforce(
+            variable.into(),
+            &one - &variable.into(),
+            LinearCombination::zero(),
+            "boolean",
+        );
+    }
+
+    pub fn alloc_boolean(&mut self, value: bool) -> Variable {
+        let variable = self.alloc(if value {
+            FieldElement::<F>::one()
+        } else {
+            FieldElement::<F>::zero()
+        });
+        self.enforce_boolean(variable);
+        variable
+    }
+
+    // boolean that is 1 exactly when a = 0, with a * inverse = 1 - result and
+    // a * result = 0
+    pub fn is_zero(&mut self, a: &LinearCombination<F>) -> Variable {
+        let value = self.evaluate(a);
+        let inverse = self.alloc(value.inverse().unwrap_or_else(FieldElement::<F>::zero));
+        let result = self.alloc(if value.is_zero() {
+            FieldElement::<F>::one()
+        } else {
+            FieldElement::<F>::zero()
+        });
+
+        let one: LinearCombination<F> = Variable::ONE.into();
+        self.enforce(
+            a.clone(),
+            inverse.into(),
+            &one - &result.into(),
+            "is zero inverse",
+        );
+        self.enforce(
+            a.clone(),
+            result.into(),
+            LinearCombination::zero(),
+            "is zero",
+        );
+        result
+    }
+
+    // boolean that is 1 exactly when a = b
+    pub fn is_equal(&mut self, a: &LinearCombination<F>, b: &LinearCombination<F>) -> Variable {
+        self.is_zero(&(a - b))
+    }
+
+    // condition ? a : b for a boolean condition, b + condition (a - b)
+    pub fn select(
+        &mut self,
+        condition: Variable,
+        a: &LinearCombination<F>,
+        b: &LinearCombination<F>,
+    ) -> Variable {
+        let difference = self.mul(&condition.into(), &(a - b));
+        let value = self.evaluate(&(b + &difference.into()));
+        let result = self.alloc(value);
+        self.enforce_equal(&result.into(), &(b + &difference.into()));
+        result
+    }
+}
+
+impl<F> ConstraintSystem<F>
+where
+    F: FieldBytes,
+{
+    // little-endian bits of a with sum 2^i b_i = a, so a < 2^bits. Meaningful in prime
+    // fields with 2^bits below the characteristic.
+    pub fn to_bits(&mut self, a: &LinearCombination<F>, bits: usize) -> Vec<Variable> {
+        let bytes = F::to_bytes(&self.evaluate(a).value);
+        let bit = |i: usize| {
+            bytes
+                .len()
+                .checked_sub(1 + i / 8)
+                .is_some_and(|j| (bytes[j] >> (i % 8)) & 1 == 1)
+        };
+
+        let variables: Vec<Variable> = (0..bits).map(|i| self.alloc_boolean(bit(i))).collect();
+
+        let mut sum = LinearCombination::zero();
+        let mut power = FieldElement::<F>::one();
+        for variable in variables.iter() {
+            sum = sum.term(*variable, power.clone());
+            power = power.double();
+        }
+        self.enforce_equal(&sum, a);
+        variables
+    }
+
+    pub fn range_check(&mut self, a: &LinearCombination<F>, bits: usize) {
+        self.to_bits(a, bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+    type Lc = LinearCombination<Goldilocks>;
+
+    // x^3 + x + 5 = out with public out
+    fn cubic(x: u64, out: u64) -> ConstraintSystem<Goldilocks> {
+        let mut cs = ConstraintSystem::new();
+        let out = cs.alloc_input(Element::from(out));
+        let x = cs.alloc(Element::from(x));
+
+        let x_squared = cs.mul(&x.into(), &x.into());
+        let x_cubed = cs.mul(&x_squared.into(), &x.into());
+        let lhs = &(&Lc::from(x_cubed) + &x.into()) + &Lc::constant(Element::from(5));
+        cs.enforce_equal(&lhs, &out.into());
+        cs
+    }
+
+    #[test]
+    fn test_satisfiability() {
+        let cs = cubic(3, 35);
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.num_inputs(), 2);
+        assert_eq!(cs.num_witnesses(), 3);
+        assert!(cs.public_inputs() == [Element::from(35)]);
+
+        assert_eq!(cubic(3, 36).which_is_unsatisfied(), Some((2, "equality")));
+
+        // a bad witness fails at the first constraint using it
+        let mut cs = cubic(3, 35);
+        cs.set_value(Variable::Witness(1), Element::from(10));
+        assert_eq!(cs.which_is_unsatisfied(), Some((0, "multiplication")));
+    }
+
+    #[test]
+    fn test_matrices() {
+        let cs = cubic(3, 35);
+        let [a, b, c] = cs.matrices();
+        let z = cs.assignment();
+        let dot = |row: &Vec<(usize, Element)>| {
+            row.iter().fold(Element::zero(), |acc, (i, coefficient)| {
+                &acc + &(coefficient * &z[*i])
+            })
+        };
+
+        assert_eq!(a.len(), 3);
+        for i in 0..3 {
+            assert!(&dot(&a[i]) * &dot(&b[i]) == dot(&c[i]));
+        }
+
+        // x + x merges into a single entry
+        let mut cs = ConstraintSystem::<Goldilocks>::new();
+        let x = cs.alloc(Element::from(2));
+        cs.enforce(
+            &Lc::from(x) + &x.into(),
+            Variable::ONE.into(),
+            Lc::zero(),
+            "x",
+        );
+        assert!(cs.matrices()[0][0] == vec![(1, Element::from(2))]);
+    }
+
+    #[test]
+    fn test_boolean_and_range() {
+        let mut cs = ConstraintSystem::<Goldilocks>::new();
+        let bit = cs.alloc_boolean(true);
+        let value = cs.alloc(Element::from(200));
+        let bits = cs.to_bits(&value.into(), 8);
+        assert!(cs.is_satisfied());
+        assert!(*cs.value(bit) == Element::one());
+        assert!(*cs.value(bits[3]) == Element::one());
+        assert!(cs.value(bits[0]).is_zero());
+
+        cs.set_value(bit, Element::from(2));
+        assert_eq!(cs.which_is_unsatisfied(), Some((0, "boolean")));
+
+        let mut cs = ConstraintSystem::<Goldilocks>::new();
+        let value = cs.alloc(Element::from(256));
+        cs.range_check(&value.into(), 8);
+        assert_eq!(cs.which_is_unsatisfied(), Some((8, "equality")));
+    }
+
+    #[test]
+    fn test_equality_and_select() {
+        let mut cs = ConstraintSystem::<Goldilocks>::new();
+        let a = cs.alloc(Element::from(7));
+        let b = cs.alloc(Element::from(7));
+        let c = cs.alloc(Element::from(9));
+
+        let equal = cs.is_equal(&a.into(), &b.into());
+        let different = cs.is_equal(&a.into(), &c.into());
+        let selected = cs.select(different, &a.into(), &c.into());
+        assert!(cs.is_satisfied());
+        assert!(cs.value(equal).is_one());
+        assert!(cs.value(different).is_zero());
+        assert!(*cs.value(selected) == Element::from(9));
+
+        // claiming a != b is caught
+        cs.set_value(equal, Element::zero());
+        assert!(cs.which_is_unsatisfied().is_some());
+    }
+}
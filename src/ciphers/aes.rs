@@ -1,4 +1,5 @@
-use cryptography::{galois_multiplication, AdvancedEncryptionStandard, Cipher};
+use crate::fields::binary::AesField;
+use cryptography::{AdvancedEncryptionStandard, Cipher, Field};
 
 static SBOX: [[u8; 16]; 16] = 
 [
@@ -42,6 +43,10 @@ static INVERSE_SBOX: [[u8; 16]; 16] =
 
 static RCON: [u8;10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1B, 0x36];
 
+fn galois_multiplication(a: u8, b: u8) -> u8 {
+    AesField::mul(&(a as u128), &(b as u128)) as u8
+}
+
 
 pub struct AdvancedEncryptionStandard128Bit {
     round_keys: [[u8; 4]; 44]
@@ -363,10 +368,10 @@ mod tests {
 
         AdvancedEncryptionStandard::mix_columns(&mut aes, &mut state);
 
-        let col1: Vec<u8> = state.iter().map(|s| *s.iter().nth(0).unwrap()).collect::<Vec<_>>();
-        let col2: Vec<u8> = state.iter().map(|s| *s.iter().nth(1).unwrap()).collect::<Vec<_>>();
-        let col3: Vec<u8> = state.iter().map(|s| *s.iter().nth(2).unwrap()).collect::<Vec<_>>();
-        let col4: Vec<u8> = state.iter().map(|s| *s.iter().nth(3).unwrap()).collect::<Vec<_>>();
+        let col1: Vec<u8> = state.iter().map(|s| *s.first().unwrap()).collect::<Vec<_>>();
+        let col2: Vec<u8> = state.iter().map(|s| *s.get(1).unwrap()).collect::<Vec<_>>();
+        let col3: Vec<u8> = state.iter().map(|s| *s.get(2).unwrap()).collect::<Vec<_>>();
+        let col4: Vec<u8> = state.iter().map(|s| *s.get(3).unwrap()).collect::<Vec<_>>();
         
         assert_eq!(col1, [0x59, 0xc2, 0xca, 0x4a], "Column 1 did not match expected output.");
         assert_eq!(col2, [0x1c, 0x86, 0xdd, 0x27], "Column 2 did not match expected output.");
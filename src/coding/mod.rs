@@ -0,0 +1 @@
+pub mod reed_solomon;
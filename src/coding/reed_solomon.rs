@@ -0,0 +1,740 @@
+use crate::fields::batch::batch_inverse;
+use crate::fields::binary::{BinaryField, BinaryFieldTables};
+use crate::fields::element::FieldElement;
+use crate::polynomial::dense::Polynomial;
+use crate::polynomial::lagrange::LagrangeInterpolation;
+use cryptography::Field;
+
+// [n, k] code whose codewords are the evaluations of polynomials of degree < k at
+// x_i = alpha^i, systematic so that the first k symbols are the message. Up to
+// (n - k) / 2 errors or n - k erasures can be corrected.
+pub struct ReedSolomon<F: Field> {
+    n: usize,
+    k: usize,
+    alpha: FieldElement<F>,
+    points: Vec<FieldElement<F>>,
+    // v_i = 1 / prod_{j != i} (x_i - x_j), sum v_i x_i^j c_i = 0 for j < n - k
+    multipliers: Vec<FieldElement<F>>,
+}
+
+impl<F> ReedSolomon<F>
+where
+    F: Field,
+{
+    // alpha must have multiplicative order at least n
+    pub fn new(n: usize, k: usize, alpha: FieldElement<F>) -> Result<Self, &'static str> {
+        if k == 0 || k > n {
+            return Err("code dimension must be between 1 and the length");
+        }
+
+        let mut points = Vec::with_capacity(n);
+        let mut x = FieldElement::<F>::one();
+        for i in 0..n {
+            if i > 0 && x.is_one() || x.is_zero() {
+                return Err("alpha does not have enough distinct powers");
+            }
+            points.push(x.clone());
+            x *= &alpha;
+        }
+
+        let multipliers = points
+            .iter()
+            .enumerate()
+            .map(|(i, xi)| {
+                let mut product = FieldElement::<F>::one();
+                for (j, xj) in points.iter().enumerate() {
+                    if i != j {
+                        product *= &(xi - xj);
+                    }
+                }
+                product.inverse().unwrap()
+            })
+            .collect();
+
+        Ok(Self {
+            n,
+            k,
+            alpha,
+            points,
+            multipliers,
+        })
+    }
+
+    pub fn length(&self) -> usize {
+        self.n
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.k
+    }
+
+    pub fn points(&self) -> &[FieldElement<F>] {
+        &self.points
+    }
+
+    fn interpolate(
+        &self,
+        indices: &[usize],
+        values: &[FieldElement<F>],
+    ) -> Result<Polynomial<F>, &'static str> {
+        let xs: Vec<FieldElement<F>> = indices.iter().map(|i| self.points[*i].clone()).collect();
+        LagrangeInterpolation::interpolate_polynomial(&xs, values)
+    }
+
+    // L_i(x_m) at every point x_m for the Lagrange basis through the points at indices, so
+    // that a codeword follows from its symbols at indices by one product per position
+    fn lagrange_basis(&self, indices: &[usize]) -> Vec<Vec<FieldElement<F>>> {
+        let mut weights: Vec<FieldElement<F>> = indices
+            .iter()
+            .map(|i| {
+                let mut product = FieldElement::<F>::one();
+                for j in indices.iter().filter(|j| *j != i) {
+                    product *= &(&self.points[*i] - &self.points[*j]);
+                }
+                product
+            })
+            .collect();
+        weights = batch_inverse(&weights);
+
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(m, x)| {
+                let mut row = vec![FieldElement::<F>::zero(); indices.len()];
+                if let Some(position) = indices.iter().position(|i| *i == m) {
+                    row[position] = FieldElement::one();
+                    return row;
+                }
+
+                // L_i(x) = w_i / (x - x_i) * prod_j (x - x_j)
+                let differences: Vec<FieldElement<F>> =
+                    indices.iter().map(|i| x - &self.points[*i]).collect();
+                let mut product = FieldElement::<F>::one();
+                for difference in differences.iter() {
+                    product *= difference;
+                }
+                for ((entry, inverse), weight) in row
+                    .iter_mut()
+                    .zip(batch_inverse(&differences).iter())
+                    .zip(weights.iter())
+                {
+                    *entry = &(&product * inverse) * weight;
+                }
+                row
+            })
+            .collect()
+    }
+
+    fn evaluate_all(&self, polynomial: &Polynomial<F>) -> Vec<FieldElement<F>> {
+        self.points.iter().map(|x| polynomial.evaluate(x)).collect()
+    }
+
+    pub fn encode(
+        &self,
+        message: &[FieldElement<F>],
+    ) -> Result<Vec<FieldElement<F>>, &'static str> {
+        if message.len() != self.k {
+            return Err("message length must equal the code dimension");
+        }
+
+        let indices: Vec<usize> = (0..self.k).collect();
+        let polynomial = self.interpolate(&indices, message)?;
+        let mut codeword = message.to_vec();
+        codeword.extend(self.points[self.k..].iter().map(|x| polynomial.evaluate(x)));
+        Ok(codeword)
+    }
+
+    pub fn message(&self, codeword: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        codeword[..self.k].to_vec()
+    }
+
+    // S_j = sum v_i x_i^j c_i for j < n - k, all zero exactly for codewords
+    pub fn syndromes(&self, received: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        let mut terms: Vec<FieldElement<F>> = received
+            .iter()
+            .zip(self.multipliers.iter())
+            .map(|(c, v)| c * v)
+            .collect();
+
+        (0..self.n - self.k)
+            .map(|_| {
+                let mut syndrome = FieldElement::<F>::zero();
+                for (term, x) in terms.iter_mut().zip(self.points.iter()) {
+                    syndrome += term;
+                    *term *= x;
+                }
+                syndrome
+            })
+            .collect()
+    }
+
+    pub fn is_codeword(&self, received: &[FieldElement<F>]) -> bool {
+        received.len() == self.n && self.syndromes(received).iter().all(|s| s.is_zero())
+    }
+
+    // recovers the codeword from any k known symbols, None marks an erasure
+    pub fn decode_erasures(
+        &self,
+        received: &[Option<FieldElement<F>>],
+    ) -> Result<Vec<FieldElement<F>>, &'static str> {
+        if received.len() != self.n {
+            return Err("received word has the wrong length");
+        }
+
+        let (indices, values): (Vec<usize>, Vec<FieldElement<F>>) = received
+            .iter()
+            .enumerate()
+            .filter_map(|(i, symbol)| symbol.clone().map(|symbol| (i, symbol)))
+            .take(self.k)
+            .unzip();
+        if indices.len() < self.k {
+            return Err("not enough symbols to recover the codeword");
+        }
+
+        Ok(self.evaluate_all(&self.interpolate(&indices, &values)?))
+    }
+
+    // Berlekamp-Welch over the known symbols, corrects e errors and s erasures
+    // whenever 2e + s <= n - k by finding E of degree e and Q of degree < k + e
+    // with Q(x_i) = y_i E(x_i), the message polynomial is then Q / E
+    pub fn decode_berlekamp_welch(
+        &self,
+        received: &[Option<FieldElement<F>>],
+    ) -> Result<Vec<FieldElement<F>>, &'static str> {
+        if received.len() != self.n {
+            return Err("received word has the wrong length");
+        }
+
+        let known: Vec<(FieldElement<F>, FieldElement<F>)> = received
+            .iter()
+            .zip(self.points.iter())
+            .filter_map(|(symbol, x)| symbol.clone().map(|y| (x.clone(), y)))
+            .collect();
+        if known.len() < self.k {
+            return Err("not enough symbols to recover the codeword");
+        }
+
+        let errors = (known.len() - self.k) / 2;
+        let unknowns = self.k + 2 * errors;
+
+        // columns are Q_0, ..., Q_{k+e-1}, E_0, ..., E_{e-1}, with E monic
+        let mut matrix = Vec::with_capacity(known.len());
+        let mut rhs = Vec::with_capacity(known.len());
+        for (x, y) in known.iter() {
+            let mut row = Vec::with_capacity(unknowns);
+            let mut power = FieldElement::<F>::one();
+            for _ in 0..self.k + errors {
+                row.push(power.clone());
+                power *= x;
+            }
+            let mut power = FieldElement::<F>::one();
+            for _ in 0..errors {
+                row.push(-&(y * &power));
+                power *= x;
+            }
+            matrix.push(row);
+            rhs.push(y * &power);
+        }
+
+        let solution = solve_linear_system(matrix, rhs).ok_or("too many errors to correct")?;
+        let q = Polynomial::new(solution[..self.k + errors].to_vec());
+        let mut e = solution[self.k + errors..].to_vec();
+        e.push(FieldElement::<F>::one());
+
+        let (message, remainder) = q.div_rem(&Polynomial::new(e))?;
+        if !remainder.is_zero() || message.degree().is_some_and(|d| d >= self.k) {
+            return Err("too many errors to correct");
+        }
+
+        let codeword = self.evaluate_all(&message);
+        let disagreements = received
+            .iter()
+            .zip(codeword.iter())
+            .filter(|(symbol, c)| symbol.as_ref().is_some_and(|symbol| symbol != *c))
+            .count();
+        if disagreements > errors {
+            return Err("too many errors to correct");
+        }
+        Ok(codeword)
+    }
+
+    // syndrome decoding: Berlekamp-Massey finds the error locator, Chien search its
+    // roots and Forney's formula the error values, corrects up to (n - k) / 2 errors
+    pub fn decode_berlekamp_massey(
+        &self,
+        received: &[FieldElement<F>],
+    ) -> Result<Vec<FieldElement<F>>, &'static str> {
+        if received.len() != self.n {
+            return Err("received word has the wrong length");
+        }
+
+        let syndromes = self.syndromes(received);
+        if syndromes.iter().all(|s| s.is_zero()) {
+            return Ok(received.to_vec());
+        }
+
+        let locator = berlekamp_massey(&syndromes);
+        let errors = locator.degree().unwrap_or(0);
+        if 2 * errors > self.n - self.k {
+            return Err("too many errors to correct");
+        }
+
+        let positions = self.chien_search(&locator);
+        if positions.len() != errors {
+            return Err("too many errors to correct");
+        }
+
+        // Omega = S(z) Lambda(z) mod z^(n - k)
+        let evaluator = (&Polynomial::new(syndromes.clone()) * &locator).truncate(self.n - self.k);
+        let derivative = locator.derivative();
+
+        let mut corrected = received.to_vec();
+        for i in positions {
+            // Y_i = -X_i Omega(1 / X_i) / Lambda'(1 / X_i) and e_i = Y_i / v_i
+            let x_inv = self.points[i].inverse().unwrap();
+            let denominator = &derivative.evaluate(&x_inv) * &self.multipliers[i];
+            let magnitude = &(&self.points[i] * &evaluator.evaluate(&x_inv))
+                * &denominator.inverse().ok_or("too many errors to correct")?;
+            corrected[i] += &magnitude;
+        }
+
+        if !self.is_codeword(&corrected) {
+            return Err("too many errors to correct");
+        }
+        Ok(corrected)
+    }
+
+    // positions i with Lambda(alpha^-i) = 0, stepping every term lambda_j alpha^(-ij)
+    // by alpha^-j instead of evaluating from scratch
+    fn chien_search(&self, locator: &Polynomial<F>) -> Vec<usize> {
+        let alpha_inv = self.alpha.inverse().unwrap();
+        let mut steps = Vec::with_capacity(locator.coefficients.len());
+        let mut step = FieldElement::<F>::one();
+        for _ in 0..locator.coefficients.len() {
+            steps.push(step.clone());
+            step *= &alpha_inv;
+        }
+
+        let mut terms = locator.coefficients.clone();
+        let mut positions = Vec::new();
+        for i in 0..self.n {
+            let mut sum = FieldElement::<F>::zero();
+            for term in terms.iter() {
+                sum += term;
+            }
+            if sum.is_zero() {
+                positions.push(i);
+            }
+            for (term, step) in terms.iter_mut().zip(steps.iter()) {
+                *term *= step;
+            }
+        }
+        positions
+    }
+}
+
+// shortest linear recurrence Lambda with Lambda_0 = 1 generating the syndromes
+fn berlekamp_massey<F: Field>(syndromes: &[FieldElement<F>]) -> Polynomial<F> {
+    let mut current = vec![FieldElement::<F>::one()];
+    let mut previous = vec![FieldElement::<F>::one()];
+    let mut length = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = FieldElement::<F>::one();
+
+    for r in 0..syndromes.len() {
+        let mut discrepancy = syndromes[r].clone();
+        for i in 1..=length.min(current.len() - 1) {
+            discrepancy += &(&current[i] * &syndromes[r - i]);
+        }
+
+        if discrepancy.is_zero() {
+            shift += 1;
+            continue;
+        }
+
+        // current -= discrepancy / previous_discrepancy * x^shift * previous
+        let factor = &discrepancy * &previous_discrepancy.inverse().unwrap();
+        let mut next = current.clone();
+        next.resize(
+            next.len().max(previous.len() + shift),
+            FieldElement::<F>::zero(),
+        );
+        for (i, b) in previous.iter().enumerate() {
+            next[i + shift] -= &(&factor * b);
+        }
+
+        if 2 * length <= r {
+            length = r + 1 - length;
+            previous = current;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        current = next;
+    }
+
+    Polynomial::new(current)
+}
+
+// any solution of matrix * x = rhs by Gauss-Jordan elimination, free variables set to zero
+fn solve_linear_system<F: Field>(
+    mut matrix: Vec<Vec<FieldElement<F>>>,
+    mut rhs: Vec<FieldElement<F>>,
+) -> Option<Vec<FieldElement<F>>> {
+    let columns = matrix.first().map_or(0, |row| row.len());
+    let mut pivots = Vec::new();
+    let mut row = 0;
+
+    for column in 0..columns {
+        let Some(pivot) = (row..matrix.len()).find(|r| !matrix[*r][column].is_zero()) else {
+            continue;
+        };
+        matrix.swap(row, pivot);
+        rhs.swap(row, pivot);
+
+        let inverse = matrix[row][column].inverse().unwrap();
+        for value in matrix[row].iter_mut() {
+            *value *= &inverse;
+        }
+        rhs[row] *= &inverse;
+
+        for other in 0..matrix.len() {
+            if other == row || matrix[other][column].is_zero() {
+                continue;
+            }
+            let factor = matrix[other][column].clone();
+            let pivot_row = matrix[row].clone();
+            for (value, pivot_value) in matrix[other].iter_mut().zip(pivot_row.iter()).skip(column)
+            {
+                *value -= &(&factor * pivot_value);
+            }
+            let term = &factor * &rhs[row];
+            rhs[other] -= &term;
+        }
+
+        pivots.push(column);
+        row += 1;
+    }
+
+    // leftover rows are 0 = rhs
+    if rhs[row..].iter().any(|value| !value.is_zero()) {
+        return None;
+    }
+
+    let mut solution = vec![FieldElement::<F>::zero(); columns];
+    for (r, column) in pivots.into_iter().enumerate() {
+        solution[column] = rhs[r].clone();
+    }
+    Some(solution)
+}
+
+// sum of row_i * values_i
+fn combine<F: Field>(row: &[FieldElement<F>], values: &[FieldElement<F>]) -> FieldElement<F> {
+    let mut sum = FieldElement::<F>::zero();
+    for (l, y) in row.iter().zip(values.iter()) {
+        sum += &(l * y);
+    }
+    sum
+}
+
+impl<const POLYNOMIAL: u128> ReedSolomon<BinaryField<8, POLYNOMIAL>> {
+    // byte oriented code over GF(2^8) using the smallest generator as alpha, n <= 255
+    pub fn bytes(n: usize, k: usize) -> Result<Self, &'static str> {
+        let tables = BinaryFieldTables::<8, POLYNOMIAL>::new()?;
+        Self::new(n, k, FieldElement::new(tables.generator()))
+    }
+
+    // splits data into k zero padded data shards and n - k parity shards of equal length,
+    // byte j of every shard is one codeword
+    pub fn encode_shards(&self, data: &[u8]) -> Vec<Vec<u8>> {
+        let shard_length = data.len().div_ceil(self.k).max(1);
+        let mut padded = data.to_vec();
+        padded.resize(shard_length * self.k, 0);
+
+        let mut shards: Vec<Vec<u8>> = padded.chunks(shard_length).map(|c| c.to_vec()).collect();
+        shards.resize(self.n, vec![0; shard_length]);
+
+        // the data shards are the same k positions of every codeword
+        let indices: Vec<usize> = (0..self.k).collect();
+        let basis = self.lagrange_basis(&indices);
+
+        for j in 0..shard_length {
+            let message: Vec<FieldElement<BinaryField<8, POLYNOMIAL>>> = shards[..self.k]
+                .iter()
+                .map(|shard| FieldElement::from(shard[j] as u64))
+                .collect();
+            for (i, row) in basis.iter().enumerate().skip(self.k) {
+                shards[i][j] = combine(row, &message).value as u8;
+            }
+        }
+        shards
+    }
+
+    // missing shards are None, corrupted shards are corrected while
+    // 2 * corrupted + missing <= n - k, length is the original data length
+    pub fn decode_shards(
+        &self,
+        shards: &[Option<Vec<u8>>],
+        length: usize,
+    ) -> Result<Vec<u8>, &'static str> {
+        if shards.len() != self.n {
+            return Err("wrong number of shards");
+        }
+        let shard_length = shards
+            .iter()
+            .flatten()
+            .map(|shard| shard.len())
+            .next()
+            .ok_or("no shards available")?;
+        if shards
+            .iter()
+            .flatten()
+            .any(|shard| shard.len() != shard_length)
+        {
+            return Err("shards have different lengths");
+        }
+        if length > shard_length * self.k {
+            return Err("length exceeds the encoded data");
+        }
+
+        // every column is missing the same shards, so a single basis through the first k
+        // available shards recovers all of them
+        let indices: Vec<usize> = shards
+            .iter()
+            .enumerate()
+            .filter(|(_, shard)| shard.is_some())
+            .map(|(i, _)| i)
+            .take(self.k)
+            .collect();
+        if indices.len() < self.k {
+            return Err("not enough symbols to recover the codeword");
+        }
+        let basis = self.lagrange_basis(&indices);
+
+        let mut data = vec![0u8; shard_length * self.k];
+        for j in 0..shard_length {
+            let received: Vec<Option<FieldElement<BinaryField<8, POLYNOMIAL>>>> = shards
+                .iter()
+                .map(|shard| shard.as_ref().map(|s| FieldElement::from(s[j] as u64)))
+                .collect();
+            let known: Vec<FieldElement<BinaryField<8, POLYNOMIAL>>> = indices
+                .iter()
+                .map(|i| received[*i].clone().unwrap())
+                .collect();
+
+            // the common case of intact shards needs only the basis
+            let mut codeword: Vec<FieldElement<BinaryField<8, POLYNOMIAL>>> =
+                basis.iter().map(|row| combine(row, &known)).collect();
+            let consistent = received
+                .iter()
+                .zip(codeword.iter())
+                .all(|(symbol, c)| symbol.as_ref().is_none_or(|symbol| symbol == c));
+            if !consistent {
+                codeword = self.decode_berlekamp_welch(&received)?;
+            }
+
+            for (i, symbol) in codeword[..self.k].iter().enumerate() {
+                data[i * shard_length + j] = symbol.value as u8;
+            }
+        }
+
+        data.truncate(length);
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::binary::{AesField, ReedSolomonField};
+    use crate::fields::u64_field::U64Field;
+    use cryptography::FftField;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Byte = FieldElement<ReedSolomonField>;
+
+    fn goldilocks_code(n: usize, k: usize) -> ReedSolomon<Goldilocks> {
+        ReedSolomon::new(
+            n,
+            k,
+            FieldElement::new(Goldilocks::multiplicative_generator()),
+        )
+        .unwrap()
+    }
+
+    fn corrupt<F: Field>(codeword: &mut [FieldElement<F>], positions: &[usize]) {
+        for i in positions {
+            codeword[*i] += &FieldElement::<F>::from(1 + *i as u64);
+        }
+    }
+
+    #[test]
+    fn test_systematic_encoding() {
+        let code = goldilocks_code(12, 5);
+        let message: Vec<FieldElement<Goldilocks>> =
+            (0..5).map(|_| FieldElement::random()).collect();
+
+        let codeword = code.encode(&message).unwrap();
+
+        assert_eq!(codeword.len(), 12);
+        assert!(code.message(&codeword) == message);
+        assert!(code.is_codeword(&codeword));
+        assert!(code.encode(&message[1..]).is_err());
+    }
+
+    #[test]
+    fn test_erasure_decoding() {
+        let code = goldilocks_code(10, 4);
+        let message: Vec<FieldElement<Goldilocks>> =
+            (0..4).map(|_| FieldElement::random()).collect();
+        let codeword = code.encode(&message).unwrap();
+
+        let mut received: Vec<Option<FieldElement<Goldilocks>>> =
+            codeword.iter().cloned().map(Some).collect();
+        for i in [0, 2, 3, 7, 8, 9] {
+            received[i] = None;
+        }
+        assert!(code.decode_erasures(&received).unwrap() == codeword);
+
+        // the precomputed basis extends the same symbols to the same codeword
+        let basis = code.lagrange_basis(&[1, 4, 5, 6]);
+        let known: Vec<FieldElement<Goldilocks>> =
+            [1, 4, 5, 6].iter().map(|i| codeword[*i].clone()).collect();
+        let extended: Vec<FieldElement<Goldilocks>> =
+            basis.iter().map(|row| combine(row, &known)).collect();
+        assert!(extended == codeword);
+
+        received[1] = None;
+        assert!(code.decode_erasures(&received).is_err());
+    }
+
+    #[test]
+    fn test_berlekamp_welch() {
+        let code = goldilocks_code(15, 7);
+        let message: Vec<FieldElement<Goldilocks>> =
+            (0..7).map(|_| FieldElement::random()).collect();
+        let codeword = code.encode(&message).unwrap();
+
+        // four errors
+        let mut corrupted = codeword.clone();
+        corrupt(&mut corrupted, &[0, 5, 6, 14]);
+        let received: Vec<Option<FieldElement<Goldilocks>>> =
+            corrupted.iter().cloned().map(Some).collect();
+        assert!(code.decode_berlekamp_welch(&received).unwrap() == codeword);
+
+        // two errors and four erasures
+        let mut received = received;
+        for i in [1, 2, 3, 4] {
+            received[i] = None;
+        }
+        received[5] = Some(codeword[5].clone());
+        received[6] = Some(codeword[6].clone());
+        assert!(code.decode_berlekamp_welch(&received).unwrap() == codeword);
+
+        // five errors are beyond the capacity
+        let mut corrupted = codeword.clone();
+        corrupt(&mut corrupted, &[0, 1, 2, 3, 4]);
+        let received: Vec<Option<FieldElement<Goldilocks>>> =
+            corrupted.iter().cloned().map(Some).collect();
+        assert!(code.decode_berlekamp_welch(&received) != Ok(codeword));
+    }
+
+    #[test]
+    fn test_berlekamp_massey_prime_field() {
+        let code = goldilocks_code(16, 8);
+        let message: Vec<FieldElement<Goldilocks>> =
+            (0..8).map(|_| FieldElement::random()).collect();
+        let codeword = code.encode(&message).unwrap();
+
+        for positions in [vec![], vec![3], vec![0, 15], vec![1, 4, 9, 12]] {
+            let mut corrupted = codeword.clone();
+            corrupt(&mut corrupted, &positions);
+            assert!(code.decode_berlekamp_massey(&corrupted).unwrap() == codeword);
+        }
+    }
+
+    #[test]
+    fn test_berlekamp_massey_gf256() {
+        let code = ReedSolomon::<ReedSolomonField>::bytes(255, 223).unwrap();
+        let message: Vec<Byte> = (0..223).map(|i| Byte::from(i as u64 * 7 % 256)).collect();
+        let codeword = code.encode(&message).unwrap();
+
+        let positions: Vec<usize> = (0..16).map(|i| i * 15 + 2).collect();
+        let mut corrupted = codeword.clone();
+        corrupt(&mut corrupted, &positions);
+        assert!(code.decode_berlekamp_massey(&corrupted).unwrap() == codeword);
+
+        let positions: Vec<usize> = (0..17).map(|i| i * 13).collect();
+        let mut corrupted = codeword.clone();
+        corrupt(&mut corrupted, &positions);
+        assert!(code.decode_berlekamp_massey(&corrupted) != Ok(codeword));
+    }
+
+    #[test]
+    fn test_decoders_agree_over_aes_field() {
+        let code = ReedSolomon::<AesField>::bytes(20, 10).unwrap();
+        let message: Vec<FieldElement<AesField>> =
+            (0..10).map(|_| FieldElement::random()).collect();
+        let codeword = code.encode(&message).unwrap();
+
+        let mut corrupted = codeword.clone();
+        corrupt(&mut corrupted, &[2, 3, 11, 17, 19]);
+        let received: Vec<Option<FieldElement<AesField>>> =
+            corrupted.iter().cloned().map(Some).collect();
+
+        assert!(code.decode_berlekamp_massey(&corrupted).unwrap() == codeword);
+        assert!(code.decode_berlekamp_welch(&received).unwrap() == codeword);
+    }
+
+    #[test]
+    fn test_shards() {
+        let code = ReedSolomon::<ReedSolomonField>::bytes(9, 6).unwrap();
+        let data = b"storage shards survive lost and damaged disks".to_vec();
+
+        let shards = code.encode_shards(&data);
+        assert_eq!(shards.len(), 9);
+        assert!(shards
+            .iter()
+            .all(|shard| shard.len() == data.len().div_ceil(6)));
+        for j in 0..shards[0].len() {
+            let message: Vec<Byte> = shards[..6]
+                .iter()
+                .map(|shard| Byte::from(shard[j] as u64))
+                .collect();
+            let codeword = code.encode(&message).unwrap();
+            assert!(shards
+                .iter()
+                .zip(codeword.iter())
+                .all(|(shard, symbol)| shard[j] == symbol.value as u8));
+        }
+
+        // three lost shards
+        let mut received: Vec<Option<Vec<u8>>> = shards.iter().cloned().map(Some).collect();
+        received[0] = None;
+        received[4] = None;
+        received[8] = None;
+        assert_eq!(code.decode_shards(&received, data.len()).unwrap(), data);
+
+        // one lost and one damaged shard
+        let mut received: Vec<Option<Vec<u8>>> = shards.iter().cloned().map(Some).collect();
+        received[2] = None;
+        received[5].as_mut().unwrap()[3] ^= 0x55;
+        assert_eq!(code.decode_shards(&received, data.len()).unwrap(), data);
+
+        // four lost shards
+        let mut received: Vec<Option<Vec<u8>>> = shards.iter().cloned().map(Some).collect();
+        for shard in received.iter_mut().take(4) {
+            *shard = None;
+        }
+        assert!(code.decode_shards(&received, data.len()).is_err());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(ReedSolomon::<ReedSolomonField>::bytes(256, 10).is_err());
+        assert!(ReedSolomon::<ReedSolomonField>::bytes(10, 0).is_err());
+        assert!(ReedSolomon::<ReedSolomonField>::bytes(10, 11).is_err());
+        assert!(ReedSolomon::new(4, 2, FieldElement::<Goldilocks>::one()).is_err());
+    }
+}
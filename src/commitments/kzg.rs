@@ -0,0 +1,180 @@
+use crate::curves::bn254::{multi_pairing, scalar_mul, Fr, G1, G2};
+use crate::fields::element::FieldElement;
+use crate::polynomial::dense::Polynomial;
+
+type Scalar = FieldElement<Fr>;
+
+// structured reference string [τ^i]_1 for i <= max degree and [1]_2, [τ]_2
+pub struct Kzg {
+    pub powers_g1: Vec<G1>,
+    pub g2: G2,
+    pub tau_g2: G2,
+}
+
+// quotient (p(x) - p(z)) / (x - z) by synthetic division, and p(z)
+fn divide_by_linear(polynomial: &Polynomial<Fr>, z: &Scalar) -> (Polynomial<Fr>, Scalar) {
+    let mut quotient = vec![Scalar::zero(); polynomial.coefficients.len().saturating_sub(1)];
+    let mut remainder = Scalar::zero();
+    for (i, coefficient) in polynomial.coefficients.iter().enumerate().rev() {
+        let next = &(&remainder * z) + coefficient;
+        if i > 0 {
+            quotient[i - 1] = next.clone();
+        }
+        remainder = next;
+    }
+    (Polynomial::new(quotient), remainder)
+}
+
+// Σ v^i p_i
+fn combine(polynomials: &[&Polynomial<Fr>], v: &Scalar) -> Polynomial<Fr> {
+    polynomials
+        .iter()
+        .rev()
+        .fold(Polynomial::zero(), |acc, p| &acc.scale(v) + *p)
+}
+
+impl Kzg {
+    // locally generated reference string, τ is dropped on return
+    pub fn setup(max_degree: usize) -> Self {
+        Self::from_trapdoor(max_degree, &Scalar::random())
+    }
+
+    pub fn from_trapdoor(max_degree: usize, tau: &Scalar) -> Self {
+        let g1 = G1::generator();
+        let mut powers_g1 = Vec::with_capacity(max_degree + 1);
+        let mut power = Scalar::one();
+        for _ in 0..=max_degree {
+            powers_g1.push(scalar_mul(&g1, &power));
+            power *= tau;
+        }
+
+        let g2 = G2::generator();
+        let tau_g2 = scalar_mul(&g2, tau);
+        Self {
+            powers_g1,
+            g2,
+            tau_g2,
+        }
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.powers_g1.len() - 1
+    }
+
+    // [p(τ)]_1
+    pub fn commit(&self, polynomial: &Polynomial<Fr>) -> Result<G1, &'static str> {
+        if polynomial.coefficients.len() > self.powers_g1.len() {
+            return Err("polynomial degree exceeds the reference string");
+        }
+        Ok(polynomial
+            .coefficients
+            .iter()
+            .zip(self.powers_g1.iter())
+            .filter(|(c, _)| !c.is_zero())
+            .fold(G1::identity(), |acc, (c, power)| {
+                &acc + &scalar_mul(power, c)
+            }))
+    }
+
+    // p(z) and the witness [(p(τ) - p(z)) / (τ - z)]_1
+    pub fn open(
+        &self,
+        polynomial: &Polynomial<Fr>,
+        z: &Scalar,
+    ) -> Result<(Scalar, G1), &'static str> {
+        let (quotient, value) = divide_by_linear(polynomial, z);
+        Ok((value, self.commit(&quotient)?))
+    }
+
+    // e(C - [v]_1, [1]_2) = e(W, [τ]_2 - [z]_2)
+    pub fn verify(&self, commitment: &G1, z: &Scalar, value: &Scalar, proof: &G1) -> bool {
+        let lhs = commitment - &scalar_mul(&G1::generator(), value);
+        let shifted = &self.tau_g2 - &scalar_mul(&self.g2, z);
+        multi_pairing(&[(lhs, self.g2.clone()), (-proof, shifted)]).is_one()
+    }
+
+    // one witness for several polynomials at the same point, for the combination
+    // Σ v^i p_i with a verifier chosen v
+    pub fn open_batch(
+        &self,
+        polynomials: &[&Polynomial<Fr>],
+        z: &Scalar,
+        v: &Scalar,
+    ) -> Result<G1, &'static str> {
+        Ok(self.open(&combine(polynomials, v), z)?.1)
+    }
+
+    pub fn verify_batch(
+        &self,
+        commitments: &[G1],
+        z: &Scalar,
+        values: &[Scalar],
+        v: &Scalar,
+        proof: &G1,
+    ) -> bool {
+        if commitments.len() != values.len() {
+            return false;
+        }
+        let commitment = commitments
+            .iter()
+            .rev()
+            .fold(G1::identity(), |acc, c| &scalar_mul(&acc, v) + c);
+        let value = values
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |acc, value| &(&acc * v) + value);
+        self.verify(&commitment, z, &value, proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_polynomial(degree: usize) -> Polynomial<Fr> {
+        Polynomial::new((0..=degree).map(|_| Scalar::random()).collect())
+    }
+
+    #[test]
+    fn test_open_and_verify() {
+        let kzg = Kzg::setup(8);
+        let p = random_polynomial(8);
+        let commitment = kzg.commit(&p).unwrap();
+        let z = Scalar::random();
+
+        let (value, proof) = kzg.open(&p, &z).unwrap();
+        assert!(value == p.evaluate(&z));
+        assert!(kzg.verify(&commitment, &z, &value, &proof));
+        assert!(!kzg.verify(&commitment, &z, &(&value + &Scalar::one()), &proof));
+        assert!(!kzg.verify(&commitment, &(&z + &Scalar::one()), &value, &proof));
+
+        assert!(kzg.commit(&random_polynomial(9)).is_err());
+    }
+
+    #[test]
+    fn test_batch() {
+        let kzg = Kzg::setup(4);
+        let polynomials: Vec<Polynomial<Fr>> = (0..3).map(|i| random_polynomial(2 + i)).collect();
+        let references: Vec<&Polynomial<Fr>> = polynomials.iter().collect();
+        let commitments: Vec<G1> = polynomials.iter().map(|p| kzg.commit(p).unwrap()).collect();
+        let z = Scalar::random();
+        let v = Scalar::random();
+        let mut values: Vec<Scalar> = polynomials.iter().map(|p| p.evaluate(&z)).collect();
+
+        let proof = kzg.open_batch(&references, &z, &v).unwrap();
+        assert!(kzg.verify_batch(&commitments, &z, &values, &v, &proof));
+        assert!(!kzg.verify_batch(&commitments, &z, &values, &Scalar::random(), &proof));
+
+        values[1] = Scalar::random();
+        assert!(!kzg.verify_batch(&commitments, &z, &values, &v, &proof));
+    }
+
+    #[test]
+    fn test_synthetic_division() {
+        let p = random_polynomial(5);
+        let z = Scalar::random();
+        let (quotient, value) = divide_by_linear(&p, &z);
+
+        assert!(&(&quotient * &Polynomial::linear(&z)) + &Polynomial::constant(value) == p);
+    }
+}
@@ -0,0 +1,566 @@
+use cryptography::HashFunction;
+
+// domain separation between leaves and internal nodes, so a node can never be
+// presented as a leaf
+const LEAF: u8 = 0;
+const NODE: u8 = 1;
+const KEY: u8 = 2;
+
+// hashing rules shared by trees, provers and verifiers
+pub struct MerkleHasher<H: HashFunction> {
+    hasher: H,
+    arity: usize,
+    digest_length: usize,
+}
+
+impl<H> MerkleHasher<H>
+where
+    H: HashFunction,
+{
+    pub fn new(mut hasher: H, arity: usize) -> Result<Self, &'static str> {
+        if arity < 2 {
+            return Err("arity must be at least 2");
+        }
+        let digest_length = hasher.hash(&[]).len();
+
+        Ok(Self {
+            hasher,
+            arity,
+            digest_length,
+        })
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn digest_length(&self) -> usize {
+        self.digest_length
+    }
+
+    pub fn hash_leaf(&mut self, leaf: &[u8]) -> Vec<u8> {
+        let mut input = vec![LEAF];
+        input.extend_from_slice(leaf);
+        self.hasher.hash(&input)
+    }
+
+    pub fn hash_node(&mut self, children: &[Vec<u8>]) -> Vec<u8> {
+        let mut input = vec![NODE];
+        for child in children {
+            input.extend_from_slice(child);
+        }
+        self.hasher.hash(&input)
+    }
+
+    // position of a key in a sparse tree
+    pub fn hash_key(&mut self, key: &[u8]) -> Vec<u8> {
+        let mut input = vec![KEY];
+        input.extend_from_slice(key);
+        self.hasher.hash(&input)
+    }
+
+    // fills the positions past the last leaf, nobody knows a preimage of zero
+    pub(crate) fn padding(&self) -> Vec<u8> {
+        vec![0; self.digest_length]
+    }
+
+    // whether a tree of the given depth has a leaf at index
+    fn in_range(&self, index: usize, depth: usize) -> bool {
+        u32::try_from(depth)
+            .ok()
+            .and_then(|depth| self.arity.checked_pow(depth))
+            .is_none_or(|width| index < width)
+    }
+
+    pub fn verify(&mut self, root: &[u8], leaf: &[u8], proof: &MerkleProof) -> bool {
+        if proof.siblings.iter().any(|s| s.len() != self.arity - 1) {
+            return false;
+        }
+        if !self.in_range(proof.index, proof.siblings.len()) {
+            return false;
+        }
+
+        let mut digest = self.hash_leaf(leaf);
+        let mut index = proof.index;
+        for siblings in proof.siblings.iter() {
+            // the current digest goes between the siblings at its position
+            let position = index % self.arity;
+            let mut children = siblings.clone();
+            children.insert(position, digest);
+            digest = self.hash_node(&children);
+            index /= self.arity;
+        }
+        digest == root
+    }
+
+    // leaves[i] is the leaf at proof.indices[i]
+    pub fn verify_batch<T: AsRef<[u8]>>(
+        &mut self,
+        root: &[u8],
+        leaves: &[T],
+        proof: &MerkleMultiProof,
+    ) -> bool {
+        if leaves.len() != proof.indices.len() || leaves.is_empty() {
+            return false;
+        }
+        if proof.indices.windows(2).any(|w| w[0] >= w[1]) {
+            return false;
+        }
+        if !self.in_range(*proof.indices.last().unwrap(), proof.depth) {
+            return false;
+        }
+
+        let mut known: Vec<(usize, Vec<u8>)> = proof
+            .indices
+            .iter()
+            .zip(leaves.iter())
+            .map(|(index, leaf)| (*index, self.hash_leaf(leaf.as_ref())))
+            .collect();
+        let mut nodes = proof.nodes.iter();
+
+        for _ in 0..proof.depth {
+            let mut next = Vec::new();
+            let mut i = 0;
+            while i < known.len() {
+                let parent = known[i].0 / self.arity;
+                let mut children = Vec::with_capacity(self.arity);
+                for position in parent * self.arity..(parent + 1) * self.arity {
+                    if i < known.len() && known[i].0 == position {
+                        children.push(known[i].1.clone());
+                        i += 1;
+                    } else {
+                        match nodes.next() {
+                            Some(node) => children.push(node.clone()),
+                            None => return false,
+                        }
+                    }
+                }
+                next.push((parent, self.hash_node(&children)));
+            }
+            known = next;
+        }
+
+        nodes.next().is_none() && known.len() == 1 && known[0].1 == root
+    }
+}
+
+// authentication path of one leaf, the arity - 1 siblings at every level from the
+// leaves up
+pub struct MerkleProof {
+    pub index: usize,
+    pub siblings: Vec<Vec<Vec<u8>>>,
+}
+
+// index (u64), arity (u32), digest length (u32), depth (u32), then the siblings,
+// all integers little-endian
+impl MerkleProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // a proof for a single leaf tree has no siblings to tell its arity by
+        let arity = self.siblings.first().map_or(2, |s| s.len() + 1);
+        let digest_length = self
+            .siblings
+            .first()
+            .and_then(|s| s.first())
+            .map_or(0, |d| d.len());
+
+        let mut bytes = (self.index as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(arity as u32).to_le_bytes());
+        bytes.extend_from_slice(&(digest_length as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.siblings.len() as u32).to_le_bytes());
+        for digest in self.siblings.iter().flatten() {
+            bytes.extend_from_slice(digest);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader::new(bytes);
+        let index = reader.u64()? as usize;
+        let arity = reader.u32()? as usize;
+        let digest_length = reader.u32()? as usize;
+        let depth = reader.u32()? as usize;
+        if arity < 2 || (depth > 0 && digest_length == 0) {
+            return Err("invalid proof header");
+        }
+        // checked before allocating anything the header asks for
+        let length = depth
+            .checked_mul(arity - 1)
+            .and_then(|n| n.checked_mul(digest_length));
+        if length != Some(reader.remaining()) {
+            return Err("proof length does not match its header");
+        }
+
+        let mut siblings = Vec::with_capacity(depth);
+        for _ in 0..depth {
+            let mut level = Vec::with_capacity(arity - 1);
+            for _ in 0..arity - 1 {
+                level.push(reader.bytes(digest_length)?.to_vec());
+            }
+            siblings.push(level);
+        }
+        reader.finish()?;
+
+        Ok(Self { index, siblings })
+    }
+}
+
+// proof for a set of leaves, holding each node that cannot be recomputed from the
+// leaves themselves exactly once, ordered by level and then by position
+pub struct MerkleMultiProof {
+    pub depth: usize,
+    pub indices: Vec<usize>,
+    pub nodes: Vec<Vec<u8>>,
+}
+
+// depth (u32), digest length (u32), number of indices (u64), the indices (u64 each),
+// number of nodes (u64), then the nodes
+impl MerkleMultiProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let digest_length = self.nodes.first().map_or(0, |d| d.len());
+
+        let mut bytes = (self.depth as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(digest_length as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.indices.len() as u64).to_le_bytes());
+        for index in self.indices.iter() {
+            bytes.extend_from_slice(&(*index as u64).to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.nodes.len() as u64).to_le_bytes());
+        for node in self.nodes.iter() {
+            bytes.extend_from_slice(node);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader::new(bytes);
+        let depth = reader.u32()? as usize;
+        let digest_length = reader.u32()? as usize;
+
+        let count = reader.u64()? as usize;
+        let mut indices = Vec::new();
+        for _ in 0..count {
+            indices.push(reader.u64()? as usize);
+        }
+        let count = reader.u64()? as usize;
+        if count > 0 && digest_length == 0 {
+            return Err("invalid proof header");
+        }
+        let mut nodes = Vec::new();
+        for _ in 0..count {
+            nodes.push(reader.bytes(digest_length)?.to_vec());
+        }
+        reader.finish()?;
+
+        Ok(Self {
+            depth,
+            indices,
+            nodes,
+        })
+    }
+}
+
+pub(crate) fn write_blob(bytes: &mut Vec<u8>, blob: &[u8]) {
+    bytes.extend_from_slice(&(blob.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(blob);
+}
+
+// cursor over a serialized proof
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub(crate) fn bytes(&mut self, length: usize) -> Result<&'a [u8], &'static str> {
+        if self.bytes.len() < length {
+            return Err("proof is truncated");
+        }
+        let (head, tail) = self.bytes.split_at(length);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, &'static str> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, &'static str> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    // u64 length followed by that many bytes
+    pub(crate) fn blob(&mut self) -> Result<&'a [u8], &'static str> {
+        let length = usize::try_from(self.u64()?).map_err(|_| "proof is truncated")?;
+        self.bytes(length)
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub(crate) fn finish(&self) -> Result<(), &'static str> {
+        if !self.bytes.is_empty() {
+            return Err("trailing bytes after proof");
+        }
+        Ok(())
+    }
+}
+
+// vector commitment to a list of byte strings, the leaves are padded up to a power
+// of the arity
+pub struct MerkleTree<H: HashFunction> {
+    hasher: MerkleHasher<H>,
+    num_leaves: usize,
+    // levels[0] holds the leaf digests, the last level the root
+    levels: Vec<Vec<Vec<u8>>>,
+}
+
+impl<H> MerkleTree<H>
+where
+    H: HashFunction,
+{
+    pub fn new<T: AsRef<[u8]>>(
+        leaves: &[T],
+        arity: usize,
+        hasher: H,
+    ) -> Result<Self, &'static str> {
+        let mut hasher = MerkleHasher::new(hasher, arity)?;
+        if leaves.is_empty() {
+            return Err("tree needs at least one leaf");
+        }
+
+        let mut width = 1;
+        while width < leaves.len() {
+            width *= arity;
+        }
+
+        let mut level: Vec<Vec<u8>> = leaves
+            .iter()
+            .map(|leaf| hasher.hash_leaf(leaf.as_ref()))
+            .collect();
+        level.resize(width, hasher.padding());
+
+        let mut levels = vec![level];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(arity)
+                .map(|children| hasher.hash_node(children))
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(Self {
+            hasher,
+            num_leaves: leaves.len(),
+            levels,
+        })
+    }
+
+    pub fn root(&self) -> &[u8] {
+        &self.levels.last().unwrap()[0]
+    }
+
+    pub fn arity(&self) -> usize {
+        self.hasher.arity
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    pub fn prove(&self, index: usize) -> Result<MerkleProof, &'static str> {
+        if index >= self.num_leaves {
+            return Err("leaf index out of range");
+        }
+
+        let arity = self.hasher.arity;
+        let mut siblings = Vec::with_capacity(self.depth());
+        let mut position = index;
+        for level in self.levels[..self.depth()].iter() {
+            let first = position - position % arity;
+            siblings.push(
+                (first..first + arity)
+                    .filter(|i| *i != position)
+                    .map(|i| level[i].clone())
+                    .collect(),
+            );
+            position /= arity;
+        }
+
+        Ok(MerkleProof { index, siblings })
+    }
+
+    pub fn prove_batch(&self, indices: &[usize]) -> Result<MerkleMultiProof, &'static str> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() {
+            return Err("no leaves to prove");
+        }
+        if *indices.last().unwrap() >= self.num_leaves {
+            return Err("leaf index out of range");
+        }
+
+        // same traversal as verify_batch, recording the nodes it cannot compute
+        let arity = self.hasher.arity;
+        let mut nodes = Vec::new();
+        let mut known = indices.clone();
+        for level in self.levels[..self.depth()].iter() {
+            let mut next = Vec::new();
+            let mut i = 0;
+            while i < known.len() {
+                let parent = known[i] / arity;
+                let children = level.iter().enumerate().skip(parent * arity).take(arity);
+                for (position, node) in children {
+                    if i < known.len() && known[i] == position {
+                        i += 1;
+                    } else {
+                        nodes.push(node.clone());
+                    }
+                }
+                next.push(parent);
+            }
+            known = next;
+        }
+
+        Ok(MerkleMultiProof {
+            depth: self.depth(),
+            indices,
+            nodes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::md5::MessageDigestAlgorithm;
+    use crate::hash::sha3::Sha3_256;
+
+    fn leaves(n: usize) -> Vec<Vec<u8>> {
+        (0..n).map(|i| format!("leaf {}", i).into_bytes()).collect()
+    }
+
+    #[test]
+    fn test_inclusion_proofs() {
+        for (n, arity) in [(1, 2), (5, 2), (8, 2), (10, 3), (17, 4), (16, 16)] {
+            let leaves = leaves(n);
+            let tree = MerkleTree::new(&leaves, arity, Sha3_256::new()).unwrap();
+            let mut verifier = MerkleHasher::new(Sha3_256::new(), arity).unwrap();
+
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.prove(i).unwrap();
+                assert!(verifier.verify(tree.root(), leaf, &proof));
+                assert!(!verifier.verify(tree.root(), b"forged", &proof));
+            }
+            assert!(tree.prove(n).is_err());
+        }
+    }
+
+    #[test]
+    fn test_tampered_proofs_are_rejected() {
+        let leaves = leaves(9);
+        let tree = MerkleTree::new(&leaves, 3, MessageDigestAlgorithm::new()).unwrap();
+        let mut verifier = MerkleHasher::new(MessageDigestAlgorithm::new(), 3).unwrap();
+
+        let mut proof = tree.prove(4).unwrap();
+        proof.index = 5;
+        assert!(!verifier.verify(tree.root(), &leaves[4], &proof));
+
+        let mut proof = tree.prove(4).unwrap();
+        proof.siblings[1][0][0] ^= 1;
+        assert!(!verifier.verify(tree.root(), &leaves[4], &proof));
+
+        // an internal node opened as a leaf
+        let proof = tree.prove(0).unwrap();
+        let shortened = MerkleProof {
+            index: 0,
+            siblings: proof.siblings[1..].to_vec(),
+        };
+        let node: Vec<u8> = tree.levels[1][0].clone();
+        assert!(!verifier.verify(tree.root(), &node, &shortened));
+    }
+
+    #[test]
+    fn test_batch_proofs() {
+        let leaves = leaves(20);
+        let tree = MerkleTree::new(&leaves, 2, Sha3_256::new()).unwrap();
+        let mut verifier = MerkleHasher::new(Sha3_256::new(), 2).unwrap();
+
+        let proof = tree.prove_batch(&[13, 2, 3, 19, 2]).unwrap();
+        assert_eq!(proof.indices, vec![2, 3, 13, 19]);
+        let opened: Vec<&[u8]> = proof.indices.iter().map(|i| &leaves[*i][..]).collect();
+        assert!(verifier.verify_batch(tree.root(), &opened, &proof));
+
+        // siblings shared between the openings are sent once
+        let separate: usize = proof.indices.iter().map(|_| tree.depth()).sum();
+        assert!(proof.nodes.len() < separate);
+
+        let mut wrong = opened.clone();
+        wrong.swap(0, 1);
+        assert!(!verifier.verify_batch(tree.root(), &wrong, &proof));
+        assert!(!verifier.verify_batch(tree.root(), &opened[..3], &proof));
+
+        let all: Vec<usize> = (0..20).collect();
+        let proof = tree.prove_batch(&all).unwrap();
+        assert!(verifier.verify_batch(tree.root(), &leaves, &proof));
+        assert!(tree.prove_batch(&[20]).is_err());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let leaves = leaves(30);
+        let tree = MerkleTree::new(&leaves, 4, Sha3_256::new()).unwrap();
+        let mut verifier = MerkleHasher::new(Sha3_256::new(), 4).unwrap();
+
+        let proof = MerkleProof::from_bytes(&tree.prove(11).unwrap().to_bytes()).unwrap();
+        assert_eq!(proof.to_bytes().len(), 20 + 3 * 3 * 32);
+        assert!(verifier.verify(tree.root(), &leaves[11], &proof));
+
+        let bytes = tree.prove_batch(&[0, 1, 29]).unwrap().to_bytes();
+        let proof = MerkleMultiProof::from_bytes(&bytes).unwrap();
+        let opened = [&leaves[0], &leaves[1], &leaves[29]];
+        assert!(verifier.verify_batch(tree.root(), &opened, &proof));
+
+        assert!(MerkleProof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(MerkleMultiProof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let single = MerkleTree::new(&leaves[..1], 4, Sha3_256::new()).unwrap();
+        let bytes = single.prove(0).unwrap().to_bytes();
+        assert!(MerkleProof::from_bytes(&bytes).unwrap().siblings.is_empty());
+    }
+
+    #[test]
+    fn test_malformed_headers() {
+        let header = |arity: u32, digest_length: u32, depth: u32| {
+            let mut bytes = 0u64.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&arity.to_le_bytes());
+            bytes.extend_from_slice(&digest_length.to_le_bytes());
+            bytes.extend_from_slice(&depth.to_le_bytes());
+            bytes
+        };
+
+        for arity in [0, 1] {
+            assert!(MerkleProof::from_bytes(&header(arity, 32, 0)).is_err());
+            assert!(MerkleProof::from_bytes(&header(arity, 32, 1)).is_err());
+        }
+        // a huge arity or depth must fail on the length, not on an allocation
+        let mut bytes = header(u32::MAX, 32, 1);
+        bytes.extend_from_slice(&[0; 64]);
+        assert!(MerkleProof::from_bytes(&bytes).is_err());
+        assert!(MerkleProof::from_bytes(&header(2, u32::MAX, u32::MAX)).is_err());
+
+        let mut bytes = header(3, 1, 2);
+        bytes.extend_from_slice(&[0; 4]);
+        assert_eq!(MerkleProof::from_bytes(&bytes).unwrap().siblings.len(), 2);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod kzg;
+pub mod merkle;
+pub mod sparse_merkle;
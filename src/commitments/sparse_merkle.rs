@@ -0,0 +1,305 @@
+use crate::commitments::merkle::{MerkleHasher, Reader};
+use cryptography::HashFunction;
+use std::collections::HashMap;
+
+// i-th bit of a path, most significant first
+fn bit(path: &[u8], i: usize) -> bool {
+    (path[i / 8] >> (7 - i % 8)) & 1 == 1
+}
+
+// the first length bits of path, the rest cleared
+fn prefix(path: &[u8], length: usize) -> Vec<u8> {
+    path.iter()
+        .enumerate()
+        .map(|(i, byte)| match length.saturating_sub(8 * i) {
+            0 => 0,
+            bits if bits >= 8 => *byte,
+            bits => byte & (0xff << (8 - bits)),
+        })
+        .collect()
+}
+
+// roots of empty subtrees, defaults[h] has height h
+fn empty_roots<H: HashFunction>(hasher: &mut MerkleHasher<H>, depth: usize) -> Vec<Vec<u8>> {
+    let mut defaults = vec![hasher.padding()];
+    for h in 0..depth {
+        let child = defaults[h].clone();
+        defaults.push(hasher.hash_node(&[child.clone(), child]));
+    }
+    defaults
+}
+
+// binary Merkle tree over all 2^depth digests, a key sits at the leaf hash_key(key)
+// and only the nodes above present keys are stored, everything else is the root of
+// an empty subtree
+pub struct SparseMerkleTree<H: HashFunction> {
+    hasher: MerkleHasher<H>,
+    depth: usize,
+    defaults: Vec<Vec<u8>>,
+    // non-empty nodes by height and path prefix
+    nodes: HashMap<(usize, Vec<u8>), Vec<u8>>,
+    values: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl<H> SparseMerkleTree<H>
+where
+    H: HashFunction,
+{
+    pub fn new(hasher: H) -> Self {
+        let mut hasher = MerkleHasher::new(hasher, 2).unwrap();
+        let depth = 8 * hasher.digest_length();
+        let defaults = empty_roots(&mut hasher, depth);
+
+        Self {
+            hasher,
+            depth,
+            defaults,
+            nodes: HashMap::new(),
+            values: HashMap::new(),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn root(&self) -> &[u8] {
+        self.node(self.depth, &vec![0; self.depth / 8])
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.values.get(key).map(|value| &value[..])
+    }
+
+    fn node(&self, height: usize, prefix: &[u8]) -> &[u8] {
+        self.nodes
+            .get(&(height, prefix.to_vec()))
+            .unwrap_or(&self.defaults[height])
+    }
+
+    // recomputes the path from the leaf at path to the root
+    fn update(&mut self, path: &[u8], leaf: Vec<u8>) {
+        let mut current = leaf;
+        for h in 0..=self.depth {
+            let own = prefix(path, self.depth - h);
+            if current == self.defaults[h] {
+                self.nodes.remove(&(h, own.clone()));
+            } else {
+                self.nodes.insert((h, own.clone()), current.clone());
+            }
+            if h == self.depth {
+                break;
+            }
+
+            let mut sibling = own;
+            let i = self.depth - 1 - h;
+            sibling[i / 8] ^= 0x80 >> (i % 8);
+            let sibling = self.node(h, &sibling).to_vec();
+
+            current = if bit(path, i) {
+                self.hasher.hash_node(&[sibling, current])
+            } else {
+                self.hasher.hash_node(&[current, sibling])
+            };
+        }
+    }
+
+    // returns the previous value
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> Option<Vec<u8>> {
+        let path = self.hasher.hash_key(key);
+        let leaf = self.hasher.hash_leaf(value);
+        self.update(&path, leaf);
+        self.values.insert(key.to_vec(), value.to_vec())
+    }
+
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let previous = self.values.remove(key)?;
+        let path = self.hasher.hash_key(key);
+        self.update(&path, self.defaults[0].clone());
+        Some(previous)
+    }
+
+    // proves the current value of key, or that it is absent
+    pub fn prove(&mut self, key: &[u8]) -> SparseMerkleProof {
+        let path = self.hasher.hash_key(key);
+        let siblings = (0..self.depth)
+            .map(|h| {
+                let mut sibling = prefix(&path, self.depth - h);
+                let i = self.depth - 1 - h;
+                sibling[i / 8] ^= 0x80 >> (i % 8);
+                self.nodes.get(&(h, sibling)).cloned()
+            })
+            .collect();
+
+        SparseMerkleProof { siblings }
+    }
+}
+
+// siblings from the leaf up, None for the root of an empty subtree
+pub struct SparseMerkleProof {
+    pub siblings: Vec<Option<Vec<u8>>>,
+}
+
+// depth (u32), digest length (u32), a bitmap with bit h set when sibling h is present,
+// then the present siblings. Proofs in a sparse tree are mostly empty subtrees, so this
+// is far shorter than depth digests.
+impl SparseMerkleProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let digest_length = self.siblings.iter().flatten().next().map_or(0, |d| d.len());
+
+        let mut bytes = (self.siblings.len() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(digest_length as u32).to_le_bytes());
+        let mut bitmap = vec![0u8; self.siblings.len().div_ceil(8)];
+        for (h, sibling) in self.siblings.iter().enumerate() {
+            if sibling.is_some() {
+                bitmap[h / 8] |= 0x80 >> (h % 8);
+            }
+        }
+        bytes.extend(bitmap);
+        for sibling in self.siblings.iter().flatten() {
+            bytes.extend_from_slice(sibling);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader::new(bytes);
+        let depth = reader.u32()? as usize;
+        let digest_length = reader.u32()? as usize;
+        let bitmap = reader.bytes(depth.div_ceil(8))?;
+
+        let mut siblings = Vec::with_capacity(depth);
+        for h in 0..depth {
+            siblings.push(if bit(bitmap, h) {
+                Some(reader.bytes(digest_length)?.to_vec())
+            } else {
+                None
+            });
+        }
+        reader.finish()?;
+
+        Ok(Self { siblings })
+    }
+}
+
+impl<H> MerkleHasher<H>
+where
+    H: HashFunction,
+{
+    // value None checks that key is absent
+    pub fn verify_sparse(
+        &mut self,
+        root: &[u8],
+        key: &[u8],
+        value: Option<&[u8]>,
+        proof: &SparseMerkleProof,
+    ) -> bool {
+        let depth = 8 * self.digest_length();
+        if self.arity() != 2 || proof.siblings.len() != depth {
+            return false;
+        }
+
+        let defaults = empty_roots(self, depth);
+        let path = self.hash_key(key);
+        let mut current = match value {
+            Some(value) => self.hash_leaf(value),
+            None => defaults[0].clone(),
+        };
+        for (h, sibling) in proof.siblings.iter().enumerate() {
+            let sibling = sibling.clone().unwrap_or_else(|| defaults[h].clone());
+            current = if bit(&path, depth - 1 - h) {
+                self.hash_node(&[sibling, current])
+            } else {
+                self.hash_node(&[current, sibling])
+            };
+        }
+        current == root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::md5::MessageDigestAlgorithm;
+    use crate::hash::sha3::Sha3_256;
+
+    #[test]
+    fn test_inclusion_and_exclusion() {
+        let mut tree = SparseMerkleTree::new(Sha3_256::new());
+        let mut verifier = MerkleHasher::new(Sha3_256::new(), 2).unwrap();
+        let empty_root = tree.root().to_vec();
+
+        for i in 0..20u32 {
+            tree.insert(&i.to_le_bytes(), format!("value {}", i).as_bytes());
+        }
+        assert_eq!(tree.len(), 20);
+        let root = tree.root().to_vec();
+
+        for i in 0..20u32 {
+            let key = i.to_le_bytes();
+            let value = format!("value {}", i);
+            let proof = tree.prove(&key);
+            assert!(verifier.verify_sparse(&root, &key, Some(value.as_bytes()), &proof));
+            assert!(!verifier.verify_sparse(&root, &key, Some(b"other"), &proof));
+            assert!(!verifier.verify_sparse(&root, &key, None, &proof));
+        }
+
+        let absent = 100u32.to_le_bytes();
+        let proof = tree.prove(&absent);
+        assert!(verifier.verify_sparse(&root, &absent, None, &proof));
+        assert!(!verifier.verify_sparse(&root, &absent, Some(b"value 0"), &proof));
+        assert!(!verifier.verify_sparse(&empty_root, &0u32.to_le_bytes(), None, &proof));
+    }
+
+    #[test]
+    fn test_updates() {
+        let mut tree = SparseMerkleTree::new(MessageDigestAlgorithm::new());
+        let empty_root = tree.root().to_vec();
+
+        assert_eq!(tree.insert(b"a", b"1"), None);
+        tree.insert(b"b", b"2");
+        let root = tree.root().to_vec();
+
+        // the root depends on the contents only, not on the insertion order
+        let mut other = SparseMerkleTree::new(MessageDigestAlgorithm::new());
+        other.insert(b"b", b"2");
+        other.insert(b"a", b"0");
+        assert_ne!(other.root(), &root[..]);
+        assert_eq!(other.insert(b"a", b"1"), Some(b"0".to_vec()));
+        assert_eq!(other.root(), &root[..]);
+
+        assert_eq!(tree.get(b"a"), Some(&b"1"[..]));
+        assert_eq!(tree.remove(b"a"), Some(b"1".to_vec()));
+        assert_eq!(tree.remove(b"a"), None);
+        assert_eq!(tree.get(b"a"), None);
+        tree.remove(b"b");
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), &empty_root[..]);
+        assert!(tree.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_compact_proofs() {
+        let mut tree = SparseMerkleTree::new(Sha3_256::new());
+        let mut verifier = MerkleHasher::new(Sha3_256::new(), 2).unwrap();
+        for i in 0..8u8 {
+            tree.insert(&[i], &[i]);
+        }
+
+        let bytes = tree.prove(&[3]).to_bytes();
+        // with 8 keys only about log2(8) siblings are non-empty
+        assert!(bytes.len() < 8 + 32 + 6 * 32);
+
+        let proof = SparseMerkleProof::from_bytes(&bytes).unwrap();
+        assert!(verifier.verify_sparse(tree.root(), &[3], Some(&[3]), &proof));
+        assert!(SparseMerkleProof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::curves::pairing::{self, PairingConfig, Twist};
+use crate::curves::weierstrass::{AffinePoint, JacobianPoint, WeierstrassConfig};
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+use crate::fields::tower::{Fp12, Fp2, TowerConfig};
+use cryptography::Field;
+use num_bigint::BigUint;
+
+// BLS12 curve with u = -U, p = (u - 1)^2 (u^4 - u^2 + 1) / 3 + u and
+// r = u^4 - u^2 + 1
+pub const U: u64 = 0xd201000000010000;
+
+#[derive(Debug)]
+pub struct FqConfig;
+
+impl MontgomeryConfig<6> for FqConfig {
+    const MODULUS: [u64; 6] = [
+        0xb9feffffffffaaab,
+        0x1eabfffeb153ffff,
+        0x6730d2a0f6b0f624,
+        0x64774b84f38512bf,
+        0x4b1ba7b6434bacd7,
+        0x1a0111ea397fe69a,
+    ];
+    const GENERATOR: u64 = 2;
+}
+
+#[derive(Debug)]
+pub struct FrConfig;
+
+impl MontgomeryConfig<4> for FrConfig {
+    const MODULUS: [u64; 4] = [
+        0xffffffff00000001,
+        0x53bda402fffe5bfe,
+        0x3339d80809a1d805,
+        0x73eda753299d7d48,
+    ];
+    const GENERATOR: u64 = 7;
+}
+
+// base field of 381 bits
+pub type Fq = MontgomeryField<FqConfig, 6>;
+
+// scalar field, r - 1 is divisible by 2^32
+pub type Fr = MontgomeryField<FrConfig, 4>;
+
+// Fq2 = Fq[u] / (u^2 + 1), Fq6 = Fq2[v] / (v^3 - (1 + u)), Fq12 = Fq6[w] / (w^2 - v)
+#[derive(Debug)]
+pub struct Bls12Tower;
+
+impl TowerConfig for Bls12Tower {
+    type Fp = Fq;
+
+    fn fp2_non_residue() -> [u64; 6] {
+        Fq::neg(&Fq::one())
+    }
+
+    fn fp6_non_residue() -> [[u64; 6]; 2] {
+        [Fq::one(), Fq::one()]
+    }
+}
+
+pub type Fq2 = Fp2<Bls12Tower>;
+pub type Fq12 = Fp12<Bls12Tower>;
+
+// y^2 = x^3 + 4 over Fq
+#[derive(Debug)]
+pub struct G1Config;
+
+impl WeierstrassConfig for G1Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    fn a() -> FieldElement<Fq> {
+        FieldElement::zero()
+    }
+
+    fn b() -> FieldElement<Fq> {
+        FieldElement::from(4)
+    }
+
+    fn generator() -> (FieldElement<Fq>, FieldElement<Fq>) {
+        (
+            FieldElement::new(Fq::from_hex(
+                "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+            )),
+            FieldElement::new(Fq::from_hex(
+                "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1",
+            )),
+        )
+    }
+
+    // (u - 1)^2 / 3
+    fn cofactor() -> BigUint {
+        BigUint::parse_bytes(b"396c8c005555e1568c00aaab0000aaab", 16).unwrap()
+    }
+}
+
+// the M-type sextic twist y^2 = x^3 + 4 (1 + u) over Fq2
+#[derive(Debug)]
+pub struct G2Config;
+
+impl WeierstrassConfig for G2Config {
+    type BaseField = Fq2;
+    type ScalarField = Fr;
+
+    fn a() -> FieldElement<Fq2> {
+        FieldElement::zero()
+    }
+
+    fn b() -> FieldElement<Fq2> {
+        FieldElement::new([Fq::from_u64(4), Fq::from_u64(4)])
+    }
+
+    fn generator() -> (FieldElement<Fq2>, FieldElement<Fq2>) {
+        let fq2 =
+            |c0: &str, c1: &str| FieldElement::<Fq2>::new([Fq::from_hex(c0), Fq::from_hex(c1)]);
+        (
+            fq2(
+                "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+                "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+            ),
+            fq2(
+                "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+                "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+            ),
+        )
+    }
+
+    fn cofactor() -> BigUint {
+        BigUint::parse_bytes(
+            b"5d543a95414e7f1091d50792876a202cd91de4547085abaa68a205b2e5a7ddfa628f1cb4d9e82ef21537e293a6691ae1616ec6e786f0c70cf1c38e31c7238e5",
+            16,
+        )
+        .unwrap()
+    }
+}
+
+pub type G1 = JacobianPoint<G1Config>;
+pub type G2 = JacobianPoint<G2Config>;
+pub type G1Affine = AffinePoint<G1Config>;
+pub type G2Affine = AffinePoint<G2Config>;
+
+// target group, the r-th roots of unity in Fq12
+pub type Gt = FieldElement<Fq12>;
+
+// optimal ate pairing over |u| with an M-type twist, conjugated as u < 0
+#[derive(Debug)]
+pub struct Bls12_381;
+
+impl PairingConfig for Bls12_381 {
+    type Tower = Bls12Tower;
+    type G1 = G1Config;
+    type G2 = G2Config;
+
+    const TWIST: Twist = Twist::M;
+    const NEGATIVE_LOOP: bool = true;
+
+    fn loop_count() -> BigUint {
+        BigUint::from(U)
+    }
+}
+
+pub fn pairing(p: &G1, q: &G2) -> Gt {
+    pairing::pairing::<Bls12_381>(p, q)
+}
+
+// product of pairings with a single final exponentiation
+pub fn multi_pairing(pairs: &[(G1, G2)]) -> Gt {
+    pairing::multi_pairing::<Bls12_381>(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameters() {
+        let u = BigUint::from(U);
+        let r = Fr::characteristic();
+        let p = Fq::characteristic();
+        let u2 = &u * &u;
+        assert!(r == &u2 * &u2 - &u2 + 1u64);
+        // with u negative, (u - 1)^2 = (U + 1)^2 and p = ... - U
+        let h = (&u + 1u64) * (&u + 1u64);
+        assert!(p == &h * &r / 3u64 - &u);
+        assert!(G1Config::cofactor() == &h / 3u64);
+
+        // E(Fq) has p + 1 - t points with trace t = u + 1
+        assert!(G1Config::cofactor() * &r == &p + &u);
+
+        // (u^8 - 4u^7 + 5u^6 - 4u^4 + 6u^3 - 4u^2 - 4u + 13) / 9 in terms of U = -u
+        let power = |k: u32| u.pow(k);
+        let positive = power(8) + power(7) * 4u64 + power(6) * 5u64 + &u * 4u64 + 13u64;
+        let negative = power(4) * 4u64 + power(3) * 6u64 + power(2) * 4u64;
+        assert!(G2Config::cofactor() == (positive - negative) / 9u64);
+    }
+
+    #[test]
+    fn test_generators() {
+        assert!(G1Affine::generator().is_on_curve());
+        assert!(G2Affine::generator().is_on_curve());
+        assert!(G1::generator().is_in_subgroup());
+        assert!(G2::generator().is_in_subgroup());
+
+        let h = G1Config::cofactor().to_u64_digits();
+        let p = G1::generator().mul_limbs(&[12345]);
+        assert!(p.clear_cofactor() == p.mul_limbs(&h));
+        assert!(!G2::generator().mul_limbs(&[U]).is_identity());
+
+        let p = p.to_affine();
+        assert!(G1Affine::from_bytes(&p.to_compressed()).unwrap() == p);
+    }
+
+    fn fq2(c0: &str, c1: &str) -> [[u64; 6]; 2] {
+        [Fq::from_hex(c0), Fq::from_hex(c1)]
+    }
+
+    // e(G1, G2)^3 as computed by RELIC and used as a test vector by the zkcrypto
+    // bls12_381 crate, whose final exponentiation yields the cube of the pairing
+    #[test]
+    fn test_known_answer() {
+        let expected = Gt::new([
+            [
+                fq2(
+                    "1250ebd871fc0a92a7b2d83168d0d727272d441befa15c503dd8e90ce98db3e7b6d194f60839c508a84305aaca1789b6",
+                    "089a1c5b46e5110b86750ec6a532348868a84045483c92b7af5af689452eafabf1a8943e50439f1d59882a98eaa0170f",
+                ),
+                fq2(
+                    "1368bb445c7c2d209703f239689ce34c0378a68e72a6b3b216da0e22a5031b54ddff57309396b38c881c4c849ec23e87",
+                    "193502b86edb8857c273fa075a50512937e0794e1e65a7617c90d8bd66065b1fffe51d7a579973b1315021ec3c19934f",
+                ),
+                fq2(
+                    "01b2f522473d171391125ba84dc4007cfbf2f8da752f7c74185203fcca589ac719c34dffbbaad8431dad1c1fb597aaa5",
+                    "018107154f25a764bd3c79937a45b84546da634b8f6be14a8061e55cceba478b23f7dacaa35c8ca78beae9624045b4b6",
+                ),
+            ],
+            [
+                fq2(
+                    "19f26337d205fb469cd6bd15c3d5a04dc88784fbb3d0b2dbdea54d43b2b73f2cbb12d58386a8703e0f948226e47ee89d",
+                    "06fba23eb7c5af0d9f80940ca771b6ffd5857baaf222eb95a7d2809d61bfe02e1bfd1b68ff02f0b8102ae1c2d5d5ab1a",
+                ),
+                fq2(
+                    "11b8b424cd48bf38fcef68083b0b0ec5c81a93b330ee1a677d0d15ff7b984e8978ef48881e32fac91b93b47333e2ba57",
+                    "03350f55a7aefcd3c31b4fcb6ce5771cc6a0e9786ab5973320c806ad360829107ba810c5a09ffdd9be2291a0c25a99a2",
+                ),
+                fq2(
+                    "04c581234d086a9902249b64728ffd21a189e87935a954051c7cdba7b3872629a4fafc05066245cb9108f0242d0fe3ef",
+                    "0f41e58663bf08cf068672cbd01a7ec73baca4d72ca93544deff686bfd6df543d48eaa24afe47e1efde449383b676631",
+                ),
+            ],
+        ]);
+        let e = pairing(&G1::generator(), &G2::generator());
+        assert!(e.pow_u64(3) == expected);
+    }
+}
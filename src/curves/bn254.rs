@@ -0,0 +1,270 @@
+use crate::curves::pairing::{self, PairingConfig, Twist};
+use crate::curves::weierstrass::{AffinePoint, JacobianPoint, WeierstrassConfig};
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+use crate::fields::tower::{Fp12, Fp2, TowerConfig};
+use cryptography::Field;
+use num_bigint::BigUint;
+
+// BN curve with u = 4965661367192848881, p = 36u^4 + 36u^3 + 24u^2 + 6u + 1 and
+// r = 36u^4 + 36u^3 + 18u^2 + 6u + 1
+pub const U: u64 = 4965661367192848881;
+
+#[derive(Debug)]
+pub struct FqConfig;
+
+impl MontgomeryConfig<4> for FqConfig {
+    const MODULUS: [u64; 4] = [
+        0x3c208c16d87cfd47,
+        0x97816a916871ca8d,
+        0xb85045b68181585d,
+        0x30644e72e131a029,
+    ];
+    const GENERATOR: u64 = 3;
+}
+
+#[derive(Debug)]
+pub struct FrConfig;
+
+impl MontgomeryConfig<4> for FrConfig {
+    const MODULUS: [u64; 4] = [
+        0x43e1f593f0000001,
+        0x2833e84879b97091,
+        0xb85045b68181585d,
+        0x30644e72e131a029,
+    ];
+    const GENERATOR: u64 = 5;
+}
+
+// base field
+pub type Fq = MontgomeryField<FqConfig, 4>;
+
+// scalar field, r - 1 is divisible by 2^28
+pub type Fr = MontgomeryField<FrConfig, 4>;
+
+// Fq2 = Fq[u] / (u^2 + 1), Fq6 = Fq2[v] / (v^3 - (9 + u)), Fq12 = Fq6[w] / (w^2 - v)
+#[derive(Debug)]
+pub struct Bn254Tower;
+
+impl TowerConfig for Bn254Tower {
+    type Fp = Fq;
+
+    fn fp2_non_residue() -> [u64; 4] {
+        Fq::neg(&Fq::one())
+    }
+
+    fn fp6_non_residue() -> [[u64; 4]; 2] {
+        [Fq::from_u64(9), Fq::one()]
+    }
+}
+
+pub type Fq2 = Fp2<Bn254Tower>;
+pub type Fq12 = Fp12<Bn254Tower>;
+
+// y^2 = x^3 + 3 over Fq
+#[derive(Debug)]
+pub struct G1Config;
+
+impl WeierstrassConfig for G1Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    fn a() -> FieldElement<Fq> {
+        FieldElement::zero()
+    }
+
+    fn b() -> FieldElement<Fq> {
+        FieldElement::from(3)
+    }
+
+    fn generator() -> (FieldElement<Fq>, FieldElement<Fq>) {
+        (FieldElement::from(1), FieldElement::from(2))
+    }
+}
+
+// the sextic twist y^2 = x^3 + 3 / ξ over Fq2, ξ = 9 + u
+#[derive(Debug)]
+pub struct G2Config;
+
+impl WeierstrassConfig for G2Config {
+    type BaseField = Fq2;
+    type ScalarField = Fr;
+
+    fn a() -> FieldElement<Fq2> {
+        FieldElement::zero()
+    }
+
+    fn b() -> FieldElement<Fq2> {
+        &FieldElement::<Fq2>::from(3) * &xi().inverse().unwrap()
+    }
+
+    fn generator() -> (FieldElement<Fq2>, FieldElement<Fq2>) {
+        let fq2 = |c0: &str, c1: &str| {
+            FieldElement::<Fq2>::new([Fq::from_decimal(c0), Fq::from_decimal(c1)])
+        };
+        (
+            fq2(
+                "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+                "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+            ),
+            fq2(
+                "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+                "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+            ),
+        )
+    }
+
+    // the twist has p + t - 1 = 2p - r points
+    fn cofactor() -> BigUint {
+        Fq::characteristic() * 2u64 - Fr::characteristic()
+    }
+}
+
+pub type G1 = JacobianPoint<G1Config>;
+pub type G2 = JacobianPoint<G2Config>;
+pub type G1Affine = AffinePoint<G1Config>;
+pub type G2Affine = AffinePoint<G2Config>;
+
+// target group, the r-th roots of unity in Fq12
+pub type Gt = FieldElement<Fq12>;
+
+fn xi() -> FieldElement<Fq2> {
+    FieldElement::new(Bn254Tower::fp6_non_residue())
+}
+
+// [k]P for a scalar field element k
+pub fn scalar_mul<C: WeierstrassConfig>(
+    point: &JacobianPoint<C>,
+    k: &FieldElement<Fr>,
+) -> JacobianPoint<C> {
+    point.mul_limbs(&Fr::to_limbs(&k.value))
+}
+
+// optimal ate pairing over 6u + 2 with a D-type twist
+#[derive(Debug)]
+pub struct Bn254;
+
+impl PairingConfig for Bn254 {
+    type Tower = Bn254Tower;
+    type G1 = G1Config;
+    type G2 = G2Config;
+
+    const TWIST: Twist = Twist::D;
+    const FROBENIUS_LINES: bool = true;
+
+    fn loop_count() -> BigUint {
+        BigUint::from(U) * 6u64 + 2u64
+    }
+}
+
+pub fn pairing(p: &G1, q: &G2) -> Gt {
+    pairing::pairing::<Bn254>(p, q)
+}
+
+// product of pairings with a single final exponentiation
+pub fn multi_pairing(pairs: &[(G1, G2)]) -> Gt {
+    pairing::multi_pairing::<Bn254>(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators() {
+        assert!(G1Affine::generator().is_on_curve());
+        assert!(G2Affine::generator().is_on_curve());
+
+        let r = Fr::characteristic().to_u64_digits();
+        assert!(G1::generator().mul_limbs(&r).is_identity());
+        assert!(G2::generator().mul_limbs(&r).is_identity());
+        assert!(!G2::generator().mul_limbs(&[U]).is_identity());
+
+        assert!(G2::generator().mul_limbs(&[12345]).is_in_subgroup());
+        let p = G1::generator().mul_wnaf(&[12345], 4).to_affine();
+        assert!(G1Affine::from_bytes(&p.to_compressed()).unwrap() == p);
+    }
+
+    #[test]
+    fn test_bilinearity() {
+        let p = G1::generator();
+        let q = G2::generator();
+        let a = FieldElement::<Fr>::random();
+        let b = FieldElement::<Fr>::random();
+
+        let e = pairing(&p, &q);
+        assert!(!e.is_one());
+        assert!(e.pow_limbs(&Fr::characteristic().to_u64_digits()).is_one());
+
+        let ab = Fr::to_limbs(&(&a * &b).value);
+        assert!(pairing(&scalar_mul(&p, &a), &scalar_mul(&q, &b)) == e.pow_limbs(&ab));
+        assert!(pairing(&scalar_mul(&p, &b), &scalar_mul(&q, &a)) == e.pow_limbs(&ab));
+    }
+
+    #[test]
+    fn test_multi_pairing() {
+        let p = G1::generator();
+        let q = G2::generator();
+        let a = FieldElement::<Fr>::random();
+
+        // e(aP, Q) e(-P, aQ) = 1
+        let pairs = [(scalar_mul(&p, &a), q.clone()), (-&p, scalar_mul(&q, &a))];
+        assert!(multi_pairing(&pairs).is_one());
+        assert!(pairing(&G1::identity(), &q).is_one());
+        assert!(!multi_pairing(&[(p.clone(), q.clone()), (p, q)]).is_one());
+    }
+
+    // the "jeff1" pairing check from the Ethereum EIP-197 precompile tests,
+    // e(P1, Q1) e(P2, G2) = 1
+    #[test]
+    fn test_eip197() {
+        let g1 = |x: &str, y: &str| {
+            G1Affine::new(
+                FieldElement::new(Fq::from_hex(x)),
+                FieldElement::new(Fq::from_hex(y)),
+            )
+        };
+        // Fq2 elements are encoded with the imaginary part first
+        let g2 = |x1: &str, x0: &str, y1: &str, y0: &str| {
+            G2Affine::new(
+                FieldElement::new([Fq::from_hex(x0), Fq::from_hex(x1)]),
+                FieldElement::new([Fq::from_hex(y0), Fq::from_hex(y1)]),
+            )
+        };
+
+        let p1 = g1(
+            "1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59",
+            "3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41",
+        );
+        let q1 = g2(
+            "209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7",
+            "04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678",
+            "2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d",
+            "120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550",
+        );
+        let p2 = g1(
+            "111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c",
+            "2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411",
+        );
+        let q2 = g2(
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        );
+        assert!(q2 == G2Affine::generator());
+        for p in [&p1, &p2] {
+            assert!(p.is_on_curve());
+        }
+        for q in [&q1, &q2] {
+            assert!(q.is_on_curve() && q.is_in_subgroup());
+        }
+
+        let pairs = [
+            (p1.to_jacobian(), q1.to_jacobian()),
+            (p2.to_jacobian(), q2.to_jacobian()),
+        ];
+        assert!(multi_pairing(&pairs).is_one());
+        assert!(!pairing(&pairs[0].0, &pairs[0].1).is_one());
+    }
+}
@@ -0,0 +1,370 @@
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+use std::ops::{Add, Neg, Sub};
+
+// p = 2^255 - 19
+#[derive(Debug)]
+pub struct FqConfig;
+
+impl MontgomeryConfig<4> for FqConfig {
+    const MODULUS: [u64; 4] = [
+        0xffffffffffffffed,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ];
+    const GENERATOR: u64 = 2;
+}
+
+// l = 2^252 + 27742317777372353535851937790883648493, the order of the base point
+#[derive(Debug)]
+pub struct FrConfig;
+
+impl MontgomeryConfig<4> for FrConfig {
+    const MODULUS: [u64; 4] = [
+        0x5812631a5cf5d3ed,
+        0x14def9dea2f79cd6,
+        0x0000000000000000,
+        0x1000000000000000,
+    ];
+    const GENERATOR: u64 = 2;
+}
+
+pub type Fq = MontgomeryField<FqConfig, 4>;
+pub type Fr = MontgomeryField<FrConfig, 4>;
+
+type Element = FieldElement<Fq>;
+
+// 32 little-endian bytes, the byte order of RFC 7748 and RFC 8032
+pub fn to_le_bytes(a: &Element) -> [u8; 32] {
+    let mut bytes: [u8; 32] = Fq::to_bytes(&a.value).try_into().unwrap();
+    bytes.reverse();
+    bytes
+}
+
+// None unless the value is below p
+pub fn from_le_bytes(bytes: &[u8; 32]) -> Option<Element> {
+    let mut reversed = *bytes;
+    reversed.reverse();
+    Fq::from_bytes(&reversed).map(FieldElement::new)
+}
+
+// d = -121665 / 121666
+fn edwards_d() -> Element {
+    &(-&Element::from(121665)) * &Element::from(121666).inverse().unwrap()
+}
+
+// the twisted Edwards curve -x^2 + y^2 = 1 + d x^2 y^2, birationally equivalent to
+// the Montgomery curve v^2 = u^3 + 486662 u^2 + u. Extended coordinates (X, Y, Z, T)
+// stand for (X / Z, Y / Z) with T = XY / Z.
+#[derive(Debug)]
+pub struct EdwardsPoint {
+    pub x: Element,
+    pub y: Element,
+    pub z: Element,
+    pub t: Element,
+}
+
+impl Clone for EdwardsPoint {
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: self.z.clone(),
+            t: self.t.clone(),
+        }
+    }
+}
+
+impl PartialEq for EdwardsPoint {
+    fn eq(&self, other: &Self) -> bool {
+        &self.x * &other.z == &other.x * &self.z && &self.y * &other.z == &other.y * &self.z
+    }
+}
+
+impl EdwardsPoint {
+    pub fn identity() -> Self {
+        Self {
+            x: Element::zero(),
+            y: Element::one(),
+            z: Element::one(),
+            t: Element::zero(),
+        }
+    }
+
+    // the point with y = 4 / 5 and even x
+    pub fn generator() -> Self {
+        let mut bytes = [0x66; 32];
+        bytes[0] = 0x58;
+        Self::decompress(&bytes).unwrap()
+    }
+
+    // does not check that the point is on the curve
+    pub fn from_affine(x: Element, y: Element) -> Self {
+        let t = &x * &y;
+        Self {
+            x,
+            y,
+            z: Element::one(),
+            t,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && self.y == self.z
+    }
+
+    pub fn to_affine(&self) -> (Element, Element) {
+        let z_inv = self.z.inverse().unwrap();
+        (&self.x * &z_inv, &self.y * &z_inv)
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        let (x, y) = self.to_affine();
+        let (xx, yy) = (x.square(), y.square());
+        &yy - &xx == &Element::one() + &(&edwards_d() * &(&xx * &yy))
+    }
+
+    // dbl-2008-hwcd with a = -1
+    pub fn double(&self) -> Self {
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = self.z.square().double();
+        let d = -&a;
+        let e = &(&(&self.x + &self.y).square() - &a) - &b;
+        let g = &d + &b;
+        let f = &g - &c;
+        let h = &d - &b;
+        Self {
+            x: &e * &f,
+            y: &g * &h,
+            z: &f * &g,
+            t: &e * &h,
+        }
+    }
+
+    // double-and-add, scalar given as little-endian u64 limbs
+    pub fn mul_limbs(&self, scalar: &[u64]) -> Self {
+        let mut result = Self::identity();
+        for limb in scalar.iter().rev() {
+            for i in (0..64).rev() {
+                result = result.double();
+                if (limb >> i) & 1 == 1 {
+                    result = &result + self;
+                }
+            }
+        }
+        result
+    }
+
+    pub fn mul_scalar(&self, scalar: &FieldElement<Fr>) -> Self {
+        self.mul_limbs(&Fr::to_limbs(&scalar.value))
+    }
+
+    // multiplying by the cofactor 8 clears any small order component
+    pub fn mul_by_cofactor(&self) -> Self {
+        self.double().double().double()
+    }
+
+    // RFC 8032 section 5.1.2, y with the parity of x in the top bit
+    pub fn compress(&self) -> [u8; 32] {
+        let (x, y) = self.to_affine();
+        let mut bytes = to_le_bytes(&y);
+        bytes[31] |= (to_le_bytes(&x)[0] & 1) << 7;
+        bytes
+    }
+
+    // RFC 8032 section 5.1.3, rejects y >= p and the negative zero x
+    pub fn decompress(bytes: &[u8; 32]) -> Result<Self, &'static str> {
+        let sign = bytes[31] >> 7;
+        let mut y_bytes = *bytes;
+        y_bytes[31] &= 0x7f;
+        let y = from_le_bytes(&y_bytes).ok_or("non-canonical y coordinate")?;
+
+        let yy = y.square();
+        let numerator = &yy - &Element::one();
+        let denominator = &(&edwards_d() * &yy) + &Element::one();
+        let x = (&numerator * &denominator.inverse().unwrap())
+            .sqrt()
+            .ok_or("point is not on the curve")?;
+        if x.is_zero() && sign == 1 {
+            return Err("invalid encoding of x = 0");
+        }
+        let x = if to_le_bytes(&x)[0] & 1 == sign {
+            x
+        } else {
+            -&x
+        };
+        Ok(Self::from_affine(x, y))
+    }
+}
+
+// add-2008-hwcd-3, complete for a = -1 and non-square d
+impl Add<&EdwardsPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn add(self, other: &EdwardsPoint) -> EdwardsPoint {
+        let a = &(&self.y - &self.x) * &(&other.y - &other.x);
+        let b = &(&self.y + &self.x) * &(&other.y + &other.x);
+        let c = &(&self.t * &edwards_d().double()) * &other.t;
+        let d = (&self.z * &other.z).double();
+        let e = &b - &a;
+        let f = &d - &c;
+        let g = &d + &c;
+        let h = &b + &a;
+        EdwardsPoint {
+            x: &e * &f,
+            y: &g * &h,
+            z: &f * &g,
+            t: &e * &h,
+        }
+    }
+}
+
+impl Sub<&EdwardsPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn sub(self, other: &EdwardsPoint) -> EdwardsPoint {
+        self + &(-other)
+    }
+}
+
+impl Neg for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn neg(self) -> EdwardsPoint {
+        EdwardsPoint {
+            x: -&self.x,
+            y: self.y.clone(),
+            z: self.z.clone(),
+            t: -&self.t,
+        }
+    }
+}
+
+// RFC 7748 section 5, the x-only ladder on the Montgomery curve returning x([k]P)
+// for the point with x = u, the scalar given as little-endian bytes
+pub fn montgomery_ladder(k: &[u8; 32], u: &Element) -> Element {
+    let a24 = Element::from(121665);
+    let x1 = u.clone();
+    let (mut x2, mut z2) = (Element::one(), Element::zero());
+    let (mut x3, mut z3) = (u.clone(), Element::one());
+    let mut swap = 0;
+
+    for t in (0..255).rev() {
+        let bit = (k[t / 8] >> (t % 8)) & 1;
+        if swap ^ bit == 1 {
+            std::mem::swap(&mut x2, &mut x3);
+            std::mem::swap(&mut z2, &mut z3);
+        }
+        swap = bit;
+
+        let a = &x2 + &z2;
+        let aa = a.square();
+        let b = &x2 - &z2;
+        let bb = b.square();
+        let e = &aa - &bb;
+        let c = &x3 + &z3;
+        let d = &x3 - &z3;
+        let da = &d * &a;
+        let cb = &c * &b;
+        x3 = (&da + &cb).square();
+        z3 = &x1 * &(&da - &cb).square();
+        x2 = &aa * &bb;
+        z2 = &e * &(&aa + &(&a24 * &e));
+    }
+    if swap == 1 {
+        std::mem::swap(&mut x2, &mut x3);
+        std::mem::swap(&mut z2, &mut z3);
+    }
+    // z = 0 for the point at infinity, which maps to 0
+    &x2 * &z2.inverse().unwrap_or_else(Element::zero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cryptography::Field;
+
+    #[test]
+    fn test_generator() {
+        let g = EdwardsPoint::generator();
+        assert!(g.is_on_curve());
+        assert!(
+            g.to_affine().0
+                == FieldElement::new(Fq::from_decimal(
+                    "15112221349535400772501151409588531511454012693041857206046113283949847762202"
+                ))
+        );
+
+        // the base point has order l
+        let l = Fr::characteristic().to_u64_digits();
+        assert!(g.mul_limbs(&l).is_identity());
+        assert!(!g.mul_limbs(&[8]).is_identity());
+    }
+
+    #[test]
+    fn test_group_law() {
+        let g = EdwardsPoint::generator();
+        let p = g.mul_limbs(&[12345]);
+        let q = g.mul_limbs(&[678]);
+
+        assert!(&p + &q == g.mul_limbs(&[13023]));
+        assert!(&p - &q == g.mul_limbs(&[11667]));
+        assert!(&p + &p == p.double());
+        assert!((&p - &p).is_identity());
+        assert!(&p + &EdwardsPoint::identity() == p);
+        assert!((&p + &q).is_on_curve());
+    }
+
+    #[test]
+    fn test_compression() {
+        let g = EdwardsPoint::generator();
+        for k in 0..20u64 {
+            let p = g.mul_limbs(&[k * 1000 + 1]);
+            assert!(EdwardsPoint::decompress(&p.compress()).unwrap() == p);
+        }
+        assert_eq!(EdwardsPoint::identity().compress()[0], 1);
+
+        // y = p is non-canonical, and y = 2 gives no point
+        let mut non_canonical = [0xff; 32];
+        non_canonical[0] = 0xed;
+        non_canonical[31] = 0x7f;
+        assert!(EdwardsPoint::decompress(&non_canonical).is_err());
+        let mut off_curve = [0; 32];
+        off_curve[0] = 2;
+        assert!(EdwardsPoint::decompress(&off_curve).is_err());
+
+        // y = 1 has x = 0, which has no negative encoding
+        let mut negative_zero = [0; 32];
+        negative_zero[0] = 1;
+        negative_zero[31] = 0x80;
+        assert!(EdwardsPoint::decompress(&negative_zero).is_err());
+    }
+
+    // the Edwards and Montgomery forms agree through u = (1 + y) / (1 - y)
+    #[test]
+    fn test_birational_map() {
+        let g = EdwardsPoint::generator();
+        let mut k = [0u8; 32];
+        k[0] = 77;
+        k[5] = 3;
+
+        let (_, y) = g.to_affine();
+        let u = &(&Element::one() + &y) * &(&Element::one() - &y).inverse().unwrap();
+        assert!(u == Element::from(9));
+
+        let scalar = FieldElement::<Fr>::new(
+            Fr::from_bytes(&{
+                let mut be = k;
+                be.reverse();
+                be
+            })
+            .unwrap(),
+        );
+        let (_, y) = g.mul_scalar(&scalar).to_affine();
+        let expected = &(&Element::one() + &y) * &(&Element::one() - &y).inverse().unwrap();
+        assert!(montgomery_ladder(&k, &u) == expected);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod bls12_381;
+pub mod bn254;
+pub mod curve25519;
+pub mod p256;
+pub mod p384;
+pub mod pairing;
+pub mod secp256k1;
+pub mod weierstrass;
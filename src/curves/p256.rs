@@ -0,0 +1,89 @@
+use crate::curves::weierstrass::{AffinePoint, JacobianPoint, WeierstrassConfig};
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+
+// p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+#[derive(Debug)]
+pub struct FqConfig;
+
+impl MontgomeryConfig<4> for FqConfig {
+    const MODULUS: [u64; 4] = [
+        0xffffffffffffffff,
+        0x00000000ffffffff,
+        0x0000000000000000,
+        0xffffffff00000001,
+    ];
+    const GENERATOR: u64 = 6;
+}
+
+#[derive(Debug)]
+pub struct FrConfig;
+
+impl MontgomeryConfig<4> for FrConfig {
+    const MODULUS: [u64; 4] = [
+        0xf3b9cac2fc632551,
+        0xbce6faada7179e84,
+        0xffffffffffffffff,
+        0xffffffff00000000,
+    ];
+    const GENERATOR: u64 = 7;
+}
+
+pub type Fq = MontgomeryField<FqConfig, 4>;
+pub type Fr = MontgomeryField<FrConfig, 4>;
+
+// FIPS 186-4, y^2 = x^3 - 3x + b over Fq with prime order
+#[derive(Debug)]
+pub struct P256;
+
+impl WeierstrassConfig for P256 {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    fn a() -> FieldElement<Fq> {
+        -&FieldElement::from(3)
+    }
+
+    fn b() -> FieldElement<Fq> {
+        FieldElement::new(Fq::from_hex(
+            "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+        ))
+    }
+
+    fn generator() -> (FieldElement<Fq>, FieldElement<Fq>) {
+        (
+            FieldElement::new(Fq::from_hex(
+                "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+            )),
+            FieldElement::new(Fq::from_hex(
+                "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+            )),
+        )
+    }
+}
+
+pub type Point = JacobianPoint<P256>;
+pub type Affine = AffinePoint<P256>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cryptography::Field;
+
+    #[test]
+    fn test_generator() {
+        assert!(Affine::generator().is_on_curve());
+        assert!(Point::generator().is_in_subgroup());
+
+        let n_minus_one = (Fr::characteristic() - 1u64).to_u64_digits();
+        assert!(Point::generator().mul_wnaf(&n_minus_one, 5) == -&Point::generator());
+
+        // the complete formulas agree with the Jacobian ones
+        let projective = Affine::generator()
+            .to_projective()
+            .mul_limbs(&n_minus_one[..1]);
+        assert!(
+            projective.to_affine() == Point::generator().mul_limbs(&n_minus_one[..1]).to_affine()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::curves::weierstrass::{AffinePoint, JacobianPoint, WeierstrassConfig};
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+
+// p = 2^384 - 2^128 - 2^96 + 2^32 - 1
+#[derive(Debug)]
+pub struct FqConfig;
+
+impl MontgomeryConfig<6> for FqConfig {
+    const MODULUS: [u64; 6] = [
+        0x00000000ffffffff,
+        0xffffffff00000000,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+    const GENERATOR: u64 = 19;
+}
+
+#[derive(Debug)]
+pub struct FrConfig;
+
+impl MontgomeryConfig<6> for FrConfig {
+    const MODULUS: [u64; 6] = [
+        0xecec196accc52973,
+        0x581a0db248b0a77a,
+        0xc7634d81f4372ddf,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+    const GENERATOR: u64 = 2;
+}
+
+pub type Fq = MontgomeryField<FqConfig, 6>;
+pub type Fr = MontgomeryField<FrConfig, 6>;
+
+// FIPS 186-4, y^2 = x^3 - 3x + b over Fq with prime order
+#[derive(Debug)]
+pub struct P384;
+
+impl WeierstrassConfig for P384 {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    fn a() -> FieldElement<Fq> {
+        -&FieldElement::from(3)
+    }
+
+    fn b() -> FieldElement<Fq> {
+        FieldElement::new(Fq::from_hex(
+            "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+        ))
+    }
+
+    fn generator() -> (FieldElement<Fq>, FieldElement<Fq>) {
+        (
+            FieldElement::new(Fq::from_hex(
+                "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7",
+            )),
+            FieldElement::new(Fq::from_hex(
+                "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+            )),
+        )
+    }
+}
+
+pub type Point = JacobianPoint<P384>;
+pub type Affine = AffinePoint<P384>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cryptography::Field;
+
+    #[test]
+    fn test_generator() {
+        assert!(Affine::generator().is_on_curve());
+        assert!(Point::generator().is_in_subgroup());
+
+        let n_minus_one = (Fr::characteristic() - 1u64).to_u64_digits();
+        assert!(Point::generator().mul_wnaf(&n_minus_one, 5) == -&Point::generator());
+
+        // the complete formulas agree with the Jacobian ones
+        let projective = Affine::generator()
+            .to_projective()
+            .mul_limbs(&n_minus_one[..1]);
+        assert!(
+            projective.to_affine() == Point::generator().mul_limbs(&n_minus_one[..1]).to_affine()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::curves::weierstrass::{AffinePoint, JacobianPoint, WeierstrassConfig};
+use crate::fields::element::FieldElement;
+use crate::fields::tower::{Fp12, Fp2, TowerConfig};
+use cryptography::Field;
+use num_bigint::BigUint;
+
+// how points of the sextic twist E' over Fp2 map into E over Fp12, where w^6 = ξ
+pub enum Twist {
+    // y^2 = x^3 + b / ξ, (x, y) -> (x * w^2, y * w^3)
+    D,
+    // y^2 = x^3 + b * ξ, (x, y) -> (x / w^2, y / w^3)
+    M,
+}
+
+// a curve of embedding degree 12 with G1 over Fp and G2 on a sextic twist over Fp2,
+// both of the same prime order r
+pub trait PairingConfig {
+    type Tower: TowerConfig;
+
+    type G1: WeierstrassConfig<BaseField = <Self::Tower as TowerConfig>::Fp>;
+
+    type G2: WeierstrassConfig<
+        BaseField = Fp2<Self::Tower>,
+        ScalarField = <Self::G1 as WeierstrassConfig>::ScalarField,
+    >;
+
+    const TWIST: Twist;
+
+    // the optimal ate Miller loop runs over |6u + 2| for BN curves and |u| for BLS12
+    fn loop_count() -> BigUint;
+
+    // a negative u conjugates the Miller loop output
+    const NEGATIVE_LOOP: bool = false;
+
+    // BN curves close the loop with the lines through π(Q) and -π^2(Q)
+    const FROBENIUS_LINES: bool = false;
+}
+
+type Fp<P> = <<P as PairingConfig>::Tower as TowerConfig>::Fp;
+type Fq2<P> = FieldElement<Fp2<<P as PairingConfig>::Tower>>;
+
+// target group, the r-th roots of unity in Fp12
+pub type Gt<P> = FieldElement<Fp12<<P as PairingConfig>::Tower>>;
+
+type TwistPoint<P> = (Fq2<P>, Fq2<P>);
+
+// a point of G1 with one of G2
+pub type PairingInput<P> = (
+    JacobianPoint<<P as PairingConfig>::G1>,
+    JacobianPoint<<P as PairingConfig>::G2>,
+);
+
+fn xi<P: PairingConfig>() -> Fq2<P> {
+    FieldElement::new(P::Tower::fp6_non_residue())
+}
+
+// an element of Fp as one of Fp2
+fn embed<P: PairingConfig>(a: &FieldElement<Fp<P>>) -> Fq2<P> {
+    FieldElement::new([a.value.clone(), Fp::<P>::zero()])
+}
+
+// The line through T with slope λ on the twist, evaluated at P. For a D-type twist it
+// is y_P - λ x_P w + (λ x_T - y_T) w^3 with w^3 = v * w. For an M-type twist the slope
+// on E is λ / w, and the line times w^3 is (λ x_T - y_T) - λ x_P v + y_P v w, the
+// factor w^3 lying in Fp4 which the final exponentiation sends to one.
+fn line<P: PairingConfig>(t: &TwistPoint<P>, lambda: &Fq2<P>, p: &AffinePoint<P::G1>) -> Gt<P> {
+    let zero = Fp2::<P::Tower>::zero();
+    let y = embed::<P>(&p.y).value;
+    let slope = (-&(lambda * &embed::<P>(&p.x))).value;
+    let constant = (&(lambda * &t.0) - &t.1).value;
+    match P::TWIST {
+        Twist::D => FieldElement::new([[y, zero.clone(), zero.clone()], [slope, constant, zero]]),
+        Twist::M => FieldElement::new([[constant, slope, zero.clone()], [zero.clone(), y, zero]]),
+    }
+}
+
+// adds q to t in affine coordinates on the twist and returns the line through them,
+// vertical lines lie in a proper subfield and are dropped since the final
+// exponentiation sends them to one
+fn add_step<P: PairingConfig>(
+    t: &mut Option<TwistPoint<P>>,
+    q: &TwistPoint<P>,
+    p: &AffinePoint<P::G1>,
+) -> Option<Gt<P>> {
+    let current = match t {
+        Some(current) => current.clone(),
+        None => {
+            *t = Some(q.clone());
+            return None;
+        }
+    };
+
+    let lambda = if current.0 == q.0 {
+        if current.1 != q.1 || current.1.is_zero() {
+            *t = None;
+            return None;
+        }
+        let xx = current.0.square();
+        &(&xx.double() + &xx) * &current.1.double().inverse().unwrap()
+    } else {
+        &(&q.1 - &current.1) * &(&q.0 - &current.0).inverse().unwrap()
+    };
+
+    let x = &(&lambda.square() - &current.0) - &q.0;
+    let y = &(&lambda * &(&current.0 - &x)) - &current.1;
+    let l = line::<P>(&current, &lambda, p);
+    *t = Some((x, y));
+    Some(l)
+}
+
+// twisted Frobenius (x, y) -> (x^p γ^2, y^p γ^3) with γ = ξ^((p - 1) / 6), inverted for
+// an M-type twist
+fn frobenius<P: PairingConfig>(q: &TwistPoint<P>) -> TwistPoint<P> {
+    let p = Fp::<P>::characteristic();
+    let mut gamma_x = xi::<P>().pow_limbs(&((&p - 1u64) / 3u64).to_u64_digits());
+    let mut gamma_y = xi::<P>().pow_limbs(&((&p - 1u64) / 2u64).to_u64_digits());
+    if let Twist::M = P::TWIST {
+        gamma_x = gamma_x.inverse().unwrap();
+        gamma_y = gamma_y.inverse().unwrap();
+    }
+    (
+        &FieldElement::new(Fp2::<P::Tower>::conjugate(&q.0.value)) * &gamma_x,
+        &FieldElement::new(Fp2::<P::Tower>::conjugate(&q.1.value)) * &gamma_y,
+    )
+}
+
+pub fn miller_loop<P: PairingConfig>(p: &AffinePoint<P::G1>, q: &AffinePoint<P::G2>) -> Gt<P> {
+    let mut f = Gt::<P>::one();
+    if p.is_identity() || q.is_identity() {
+        return f;
+    }
+
+    let base = (q.x.clone(), q.y.clone());
+    let loop_count = P::loop_count();
+    let mut t = Some(base.clone());
+
+    for i in (0..loop_count.bits() - 1).rev() {
+        f = f.square();
+        let current = t.clone().unwrap();
+        if let Some(l) = add_step::<P>(&mut t, &current, p) {
+            f = &f * &l;
+        }
+        if loop_count.bit(i) {
+            if let Some(l) = add_step::<P>(&mut t, &base, p) {
+                f = &f * &l;
+            }
+        }
+    }
+
+    if P::FROBENIUS_LINES {
+        let q1 = frobenius::<P>(&base);
+        let q2 = frobenius::<P>(&q1);
+        for q in [q1, (q2.0, -&q2.1)] {
+            if let Some(l) = add_step::<P>(&mut t, &q, p) {
+                f = &f * &l;
+            }
+        }
+    }
+    if P::NEGATIVE_LOOP {
+        f = FieldElement::new(Fp12::<P::Tower>::conjugate(&f.value));
+    }
+    f
+}
+
+// raises to (p^12 - 1) / r, the easy part p^6 - 1 and p^2 + 1 through conjugation
+// and the Frobenius, the hard part (p^4 - p^2 + 1) / r by plain exponentiation
+pub fn final_exponentiation<P: PairingConfig>(f: &Gt<P>) -> Gt<P> {
+    let inverse = match f.inverse() {
+        Some(inverse) => inverse,
+        None => return Gt::<P>::zero(),
+    };
+    let f = &FieldElement::new(Fp12::<P::Tower>::conjugate(&f.value)) * &inverse;
+    let f = &f.frobenius_map(2) * &f;
+
+    let p = Fp::<P>::characteristic();
+    let p2 = &p * &p;
+    let hard = (&p2 * &p2 - &p2 + 1u64) / P::G1::order();
+    f.pow_limbs(&hard.to_u64_digits())
+}
+
+pub fn pairing<P: PairingConfig>(p: &JacobianPoint<P::G1>, q: &JacobianPoint<P::G2>) -> Gt<P> {
+    final_exponentiation::<P>(&miller_loop::<P>(&p.to_affine(), &q.to_affine()))
+}
+
+// product of pairings with a single final exponentiation
+pub fn multi_pairing<P: PairingConfig>(pairs: &[PairingInput<P>]) -> Gt<P> {
+    let mut f = Gt::<P>::one();
+    for (p, q) in pairs {
+        f = &f * &miller_loop::<P>(&p.to_affine(), &q.to_affine());
+    }
+    final_exponentiation::<P>(&f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::bls12_381::Bls12_381;
+    use crate::curves::bn254::Bn254;
+
+    fn check_bilinearity<P: PairingConfig>() {
+        let p = JacobianPoint::<P::G1>::generator();
+        let q = JacobianPoint::<P::G2>::generator();
+        let r = P::G1::order().to_u64_digits();
+        let a = rand::random::<u64>();
+        let b = rand::random::<u64>();
+
+        let e = pairing::<P>(&p, &q);
+        assert!(!e.is_one());
+        assert!(e.pow_limbs(&r).is_one());
+
+        let ab = (BigUint::from(a) * b).to_u64_digits();
+        let (pa, qb) = (p.mul_limbs(&[a]), q.mul_limbs(&[b]));
+        assert!(pairing::<P>(&pa, &qb) == e.pow_limbs(&ab));
+        assert!(pairing::<P>(&pa, &q) == pairing::<P>(&p, &q.mul_limbs(&[a])));
+
+        // e(aP, bQ) e(-abP, Q) = 1
+        let pairs = [(pa, qb), (-&p.mul_limbs(&ab), q.clone())];
+        assert!(multi_pairing::<P>(&pairs).is_one());
+        assert!(pairing::<P>(&JacobianPoint::identity(), &q).is_one());
+        assert!(pairing::<P>(&p, &JacobianPoint::identity()).is_one());
+    }
+
+    #[test]
+    fn test_bn254_bilinearity() {
+        check_bilinearity::<Bn254>();
+    }
+
+    #[test]
+    fn test_bls12_381_bilinearity() {
+        check_bilinearity::<Bls12_381>();
+    }
+
+    // the Frobenius acts on G2 as multiplication by p, for either twist
+    fn check_frobenius<P: PairingConfig>() {
+        let q = JacobianPoint::<P::G2>::generator()
+            .mul_limbs(&[7])
+            .to_affine();
+        let (x, y) = frobenius::<P>(&(q.x.clone(), q.y.clone()));
+        let p = Fp::<P>::characteristic() % P::G1::order();
+        let expected = JacobianPoint::<P::G2>::generator()
+            .mul_limbs(&(p * 7u64).to_u64_digits())
+            .to_affine();
+        assert!(x == expected.x && y == expected.y);
+    }
+
+    #[test]
+    fn test_frobenius() {
+        check_frobenius::<Bn254>();
+        check_frobenius::<Bls12_381>();
+    }
+}
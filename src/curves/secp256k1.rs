@@ -0,0 +1,89 @@
+use crate::curves::weierstrass::{AffinePoint, JacobianPoint, WeierstrassConfig};
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+
+// p = 2^256 - 2^32 - 977
+#[derive(Debug)]
+pub struct FqConfig;
+
+impl MontgomeryConfig<4> for FqConfig {
+    const MODULUS: [u64; 4] = [
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ];
+    const GENERATOR: u64 = 3;
+}
+
+#[derive(Debug)]
+pub struct FrConfig;
+
+impl MontgomeryConfig<4> for FrConfig {
+    const MODULUS: [u64; 4] = [
+        0xbfd25e8cd0364141,
+        0xbaaedce6af48a03b,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+    ];
+    const GENERATOR: u64 = 7;
+}
+
+pub type Fq = MontgomeryField<FqConfig, 4>;
+pub type Fr = MontgomeryField<FrConfig, 4>;
+
+// SEC 2, y^2 = x^3 + 7 over Fq with prime order
+#[derive(Debug)]
+pub struct Secp256k1;
+
+impl WeierstrassConfig for Secp256k1 {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    fn a() -> FieldElement<Fq> {
+        FieldElement::zero()
+    }
+
+    fn b() -> FieldElement<Fq> {
+        FieldElement::from(7)
+    }
+
+    fn generator() -> (FieldElement<Fq>, FieldElement<Fq>) {
+        (
+            FieldElement::new(Fq::from_hex(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )),
+            FieldElement::new(Fq::from_hex(
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            )),
+        )
+    }
+}
+
+pub type Point = JacobianPoint<Secp256k1>;
+pub type Affine = AffinePoint<Secp256k1>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cryptography::Field;
+
+    #[test]
+    fn test_generator() {
+        assert!(Affine::generator().is_on_curve());
+        assert!(Point::generator().is_in_subgroup());
+
+        // 2G from the standard test vectors
+        let double = Point::generator().double().to_affine();
+        assert!(
+            double.x
+                == FieldElement::new(Fq::from_hex(
+                    "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"
+                ))
+        );
+
+        // n - 1 gives -G
+        let n_minus_one = (Fr::characteristic() - 1u64).to_u64_digits();
+        assert!(Point::generator().mul_wnaf(&n_minus_one, 5) == -&Point::generator());
+    }
+}
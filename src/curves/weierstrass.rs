@@ -0,0 +1,992 @@
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::fields::sqrt::SquareRoot;
+use cryptography::Field;
+use num_bigint::BigUint;
+use std::ops::{Add, Neg, Sub};
+
+// y^2 = x^3 + a * x + b over the base field
+pub trait WeierstrassConfig {
+    type BaseField: Field;
+
+    // integers modulo the order of the generator
+    type ScalarField: Field;
+
+    fn a() -> FieldElement<Self::BaseField>;
+
+    fn b() -> FieldElement<Self::BaseField>;
+
+    // generator of the prime order subgroup
+    fn generator() -> (FieldElement<Self::BaseField>, FieldElement<Self::BaseField>);
+
+    // prime order of the generator
+    fn order() -> BigUint {
+        Self::ScalarField::characteristic()
+    }
+
+    // number of points on the curve over the order
+    fn cofactor() -> BigUint {
+        BigUint::from(1u64)
+    }
+}
+
+#[derive(Debug)]
+pub struct AffinePoint<C: WeierstrassConfig> {
+    pub x: FieldElement<C::BaseField>,
+    pub y: FieldElement<C::BaseField>,
+    pub infinity: bool,
+}
+
+// (X, Y, Z) stands for (X / Z^2, Y / Z^3), the identity has Z = 0
+#[derive(Debug)]
+pub struct JacobianPoint<C: WeierstrassConfig> {
+    pub x: FieldElement<C::BaseField>,
+    pub y: FieldElement<C::BaseField>,
+    pub z: FieldElement<C::BaseField>,
+}
+
+// (X, Y, Z) stands for (X / Z, Y / Z), the identity is (0, 1, 0)
+#[derive(Debug)]
+pub struct ProjectivePoint<C: WeierstrassConfig> {
+    pub x: FieldElement<C::BaseField>,
+    pub y: FieldElement<C::BaseField>,
+    pub z: FieldElement<C::BaseField>,
+}
+
+impl<C> Clone for AffinePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            infinity: self.infinity,
+        }
+    }
+}
+
+impl<C> Clone for JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: self.z.clone(),
+        }
+    }
+}
+
+impl<C> Clone for ProjectivePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: self.z.clone(),
+        }
+    }
+}
+
+impl<C> PartialEq for AffinePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self.infinity, other.infinity) {
+            (true, true) => true,
+            (false, false) => self.x == other.x && self.y == other.y,
+            _ => false,
+        }
+    }
+}
+
+// X1 Z2^2 = X2 Z1^2 and Y1 Z2^3 = Y2 Z1^3
+impl<C> PartialEq for JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_identity(), other.is_identity()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1z1 = self.z.square();
+                let z2z2 = other.z.square();
+                &self.x * &z2z2 == &other.x * &z1z1
+                    && &self.y * &(&z2z2 * &other.z) == &other.y * &(&z1z1 * &self.z)
+            }
+            _ => false,
+        }
+    }
+}
+
+// X1 Z2 = X2 Z1 and Y1 Z2 = Y2 Z1
+impl<C> PartialEq for ProjectivePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_identity(), other.is_identity()) {
+            (true, true) => true,
+            (false, false) => {
+                &self.x * &other.z == &other.x * &self.z && &self.y * &other.z == &other.y * &self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+// the group operations the scalar multiplication algorithms are written against
+trait GroupOps: Clone {
+    fn zero() -> Self;
+    fn dbl(&self) -> Self;
+    fn plus(&self, other: &Self) -> Self;
+    fn minus(&self) -> Self;
+}
+
+fn limbs_to_biguint(limbs: &[u64]) -> BigUint {
+    let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+    BigUint::from_bytes_le(&bytes)
+}
+
+// left to right over every bit of the limbs
+fn double_and_add<G: GroupOps>(point: &G, scalar: &[u64]) -> G {
+    let mut result = G::zero();
+    for limb in scalar.iter().rev() {
+        for i in (0..64).rev() {
+            result = result.dbl();
+            if (limb >> i) & 1 == 1 {
+                result = result.plus(point);
+            }
+        }
+    }
+    result
+}
+
+// supported wNAF widths, others are clamped into this range
+pub const MIN_WINDOW: usize = 2;
+pub const MAX_WINDOW: usize = 16;
+
+// width-w non-adjacent form, least significant digit first. Non-zero digits are
+// odd, below 2^(w - 1) in absolute value and followed by at least w - 1 zeros.
+// The window is clamped to MIN_WINDOW..=MAX_WINDOW.
+pub fn wnaf_digits(scalar: &[u64], window: usize) -> Vec<i64> {
+    let window = window.clamp(MIN_WINDOW, MAX_WINDOW);
+    let mut k = limbs_to_biguint(scalar);
+    let modulus = 1i64 << window;
+    let mut digits = Vec::new();
+
+    while k.bits() > 0 {
+        let mut digit = 0;
+        if k.bit(0) {
+            let low = k.iter_u64_digits().next().unwrap_or(0) & (modulus as u64 - 1);
+            digit = low as i64;
+            if digit >= modulus / 2 {
+                digit -= modulus;
+            }
+            if digit > 0 {
+                k -= digit as u64;
+            } else {
+                k += (-digit) as u64;
+            }
+        }
+        digits.push(digit);
+        k >>= 1;
+    }
+    digits
+}
+
+// one addition per non-zero wNAF digit from the odd multiples P, 3P, ..., (2^(w-1) - 1)P
+fn wnaf<G: GroupOps>(point: &G, scalar: &[u64], window: usize) -> G {
+    let window = window.clamp(MIN_WINDOW, MAX_WINDOW);
+    let digits = wnaf_digits(scalar, window);
+    let double = point.dbl();
+    let mut table = vec![point.clone()];
+    for i in 1..1 << (window - 2) {
+        table.push(table[i - 1].plus(&double));
+    }
+
+    let mut result = G::zero();
+    for digit in digits.iter().rev() {
+        result = result.dbl();
+        if *digit > 0 {
+            result = result.plus(&table[(*digit as usize) / 2]);
+        } else if *digit < 0 {
+            result = result.plus(&table[(-*digit as usize) / 2].minus());
+        }
+    }
+    result
+}
+
+// keeps R1 - R0 = P, one addition and one doubling for every bit whatever its value
+fn ladder<G: GroupOps>(point: &G, scalar: &[u64]) -> G {
+    let mut r0 = G::zero();
+    let mut r1 = point.clone();
+    for limb in scalar.iter().rev() {
+        for i in (0..64).rev() {
+            if (limb >> i) & 1 == 1 {
+                r0 = r0.plus(&r1);
+                r1 = r1.dbl();
+            } else {
+                r1 = r0.plus(&r1);
+                r0 = r0.dbl();
+            }
+        }
+    }
+    r0
+}
+
+impl<C> AffinePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    // does not check that the point is on the curve
+    pub fn new(x: FieldElement<C::BaseField>, y: FieldElement<C::BaseField>) -> Self {
+        Self {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self {
+            x: FieldElement::zero(),
+            y: FieldElement::zero(),
+            infinity: true,
+        }
+    }
+
+    pub fn generator() -> Self {
+        let (x, y) = C::generator();
+        Self::new(x, y)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.infinity
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        if self.infinity {
+            return true;
+        }
+        self.y.square() == curve_equation::<C>(&self.x)
+    }
+
+    // on the curve and killed by the order of the generator
+    pub fn is_in_subgroup(&self) -> bool {
+        self.is_on_curve() && self.to_jacobian().is_in_subgroup()
+    }
+
+    pub fn to_jacobian(&self) -> JacobianPoint<C> {
+        if self.infinity {
+            return JacobianPoint::identity();
+        }
+        JacobianPoint {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: FieldElement::one(),
+        }
+    }
+
+    pub fn to_projective(&self) -> ProjectivePoint<C> {
+        if self.infinity {
+            return ProjectivePoint::identity();
+        }
+        ProjectivePoint {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: FieldElement::one(),
+        }
+    }
+}
+
+// x^3 + a x + b
+fn curve_equation<C: WeierstrassConfig>(
+    x: &FieldElement<C::BaseField>,
+) -> FieldElement<C::BaseField> {
+    &(&(&x.square() * x) + &(&C::a() * x)) + &C::b()
+}
+
+// SEC 1 encodings, 0 for the identity, 4 || x || y uncompressed and 2 || x or 3 || x
+// compressed, with 3 when the canonical y is odd
+impl<C> AffinePoint<C>
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes,
+{
+    pub fn to_uncompressed(&self) -> Vec<u8> {
+        if self.infinity {
+            return vec![0];
+        }
+        let mut bytes = vec![4];
+        bytes.extend(C::BaseField::to_bytes(&self.x.value));
+        bytes.extend(C::BaseField::to_bytes(&self.y.value));
+        bytes
+    }
+
+    pub fn to_compressed(&self) -> Vec<u8> {
+        if self.infinity {
+            return vec![0];
+        }
+        let mut bytes = vec![2 + Self::parity(&self.y)];
+        bytes.extend(C::BaseField::to_bytes(&self.x.value));
+        bytes
+    }
+
+    fn parity(y: &FieldElement<C::BaseField>) -> u8 {
+        C::BaseField::to_bytes(&y.value)
+            .last()
+            .map_or(0, |byte| byte & 1)
+    }
+}
+
+impl<C> AffinePoint<C>
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes + SquareRoot,
+{
+    // either encoding, rejects points off the curve or outside the subgroup
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let length = C::BaseField::ELEMENT_BYTES;
+        let element = |chunk: &[u8]| {
+            C::BaseField::from_bytes(chunk)
+                .map(FieldElement::new)
+                .ok_or("invalid coordinate encoding")
+        };
+
+        let point = match (bytes.first(), bytes.len()) {
+            (Some(0), 1) => return Ok(Self::identity()),
+            (Some(4), n) if n == 1 + 2 * length => {
+                let point = Self::new(
+                    element(&bytes[1..1 + length])?,
+                    element(&bytes[1 + length..])?,
+                );
+                if !point.is_on_curve() {
+                    return Err("point is not on the curve");
+                }
+                point
+            }
+            (Some(&tag @ (2 | 3)), n) if n == 1 + length => {
+                let x = element(&bytes[1..])?;
+                let y = curve_equation::<C>(&x)
+                    .sqrt()
+                    .ok_or("point is not on the curve")?;
+                let y = if Self::parity(&y) == tag - 2 { y } else { -&y };
+                // y = 0 is its own negation and only has the even tag
+                if Self::parity(&y) != tag - 2 {
+                    return Err("invalid point encoding");
+                }
+                Self::new(x, y)
+            }
+            _ => return Err("invalid point encoding"),
+        };
+
+        if C::cofactor() != BigUint::from(1u64) && !point.to_jacobian().is_in_subgroup() {
+            return Err("point is not in the prime order subgroup");
+        }
+        Ok(point)
+    }
+}
+
+impl<C> Neg for &AffinePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    type Output = AffinePoint<C>;
+
+    fn neg(self) -> AffinePoint<C> {
+        AffinePoint {
+            x: self.x.clone(),
+            y: -&self.y,
+            infinity: self.infinity,
+        }
+    }
+}
+
+impl<C> GroupOps for JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn zero() -> Self {
+        Self::identity()
+    }
+
+    fn dbl(&self) -> Self {
+        self.double()
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn minus(&self) -> Self {
+        -self
+    }
+}
+
+impl<C> JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+{
+    pub fn identity() -> Self {
+        Self {
+            x: FieldElement::one(),
+            y: FieldElement::one(),
+            z: FieldElement::zero(),
+        }
+    }
+
+    pub fn generator() -> Self {
+        AffinePoint::<C>::generator().to_jacobian()
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn to_affine(&self) -> AffinePoint<C> {
+        let z_inv = match self.z.inverse() {
+            Some(z_inv) => z_inv,
+            None => return AffinePoint::identity(),
+        };
+        let z_inv2 = z_inv.square();
+        AffinePoint::new(&self.x * &z_inv2, &self.y * &(&z_inv2 * &z_inv))
+    }
+
+    // dbl-2007-bl
+    pub fn double(&self) -> Self {
+        if self.is_identity() {
+            return self.clone();
+        }
+        let xx = self.x.square();
+        let yy = self.y.square();
+        let yyyy = yy.square();
+        let zz = self.z.square();
+        let s = (&(&(&self.x + &yy).square() - &xx) - &yyyy).double();
+        let m = &(&xx.double() + &xx) + &(&C::a() * &zz.square());
+
+        let x = &m.square() - &s.double();
+        let y = &(&m * &(&s - &x)) - &yyyy.double().double().double();
+        let z = &(&(&self.y + &self.z).square() - &yy) - &zz;
+        Self { x, y, z }
+    }
+
+    // double-and-add, scalar given as little-endian u64 limbs
+    pub fn mul_limbs(&self, scalar: &[u64]) -> Self {
+        double_and_add(self, scalar)
+    }
+
+    // window between MIN_WINDOW and MAX_WINDOW, clamped otherwise
+    pub fn mul_wnaf(&self, scalar: &[u64], window: usize) -> Self {
+        wnaf(self, scalar, window)
+    }
+
+    pub fn mul_ladder(&self, scalar: &[u64]) -> Self {
+        ladder(self, scalar)
+    }
+
+    pub fn is_in_subgroup(&self) -> bool {
+        self.mul_limbs(&C::order().to_u64_digits()).is_identity()
+    }
+
+    // maps any point on the curve into the prime order subgroup
+    pub fn clear_cofactor(&self) -> Self {
+        self.mul_limbs(&C::cofactor().to_u64_digits())
+    }
+}
+
+// add-2007-bl, falls back to doubling for equal inputs
+impl<C> Add<&JacobianPoint<C>> for &JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+{
+    type Output = JacobianPoint<C>;
+
+    fn add(self, other: &JacobianPoint<C>) -> JacobianPoint<C> {
+        if self.is_identity() {
+            return other.clone();
+        }
+        if other.is_identity() {
+            return self.clone();
+        }
+
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = &self.x * &z2z2;
+        let u2 = &other.x * &z1z1;
+        let s1 = &(&self.y * &other.z) * &z2z2;
+        let s2 = &(&other.y * &self.z) * &z1z1;
+        let h = &u2 - &u1;
+        let r = (&s2 - &s1).double();
+
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double()
+            } else {
+                JacobianPoint::identity()
+            };
+        }
+
+        let i = h.double().square();
+        let j = &h * &i;
+        let v = &u1 * &i;
+        let x = &(&r.square() - &j) - &v.double();
+        let y = &(&r * &(&v - &x)) - &(&s1 * &j).double();
+        let z = &(&(&(&self.z + &other.z).square() - &z1z1) - &z2z2) * &h;
+        JacobianPoint { x, y, z }
+    }
+}
+
+impl<C> Sub<&JacobianPoint<C>> for &JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+{
+    type Output = JacobianPoint<C>;
+
+    fn sub(self, other: &JacobianPoint<C>) -> JacobianPoint<C> {
+        self + &(-other)
+    }
+}
+
+impl<C> Neg for &JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+{
+    type Output = JacobianPoint<C>;
+
+    fn neg(self) -> JacobianPoint<C> {
+        JacobianPoint {
+            x: self.x.clone(),
+            y: -&self.y,
+            z: self.z.clone(),
+        }
+    }
+}
+
+impl<C> GroupOps for ProjectivePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    fn zero() -> Self {
+        Self::identity()
+    }
+
+    fn dbl(&self) -> Self {
+        self.double()
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn minus(&self) -> Self {
+        -self
+    }
+}
+
+impl<C> ProjectivePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    pub fn identity() -> Self {
+        Self {
+            x: FieldElement::zero(),
+            y: FieldElement::one(),
+            z: FieldElement::zero(),
+        }
+    }
+
+    pub fn generator() -> Self {
+        AffinePoint::<C>::generator().to_projective()
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn to_affine(&self) -> AffinePoint<C> {
+        match self.z.inverse() {
+            Some(z_inv) => AffinePoint::new(&self.x * &z_inv, &self.y * &z_inv),
+            None => AffinePoint::identity(),
+        }
+    }
+
+    // Renes, Costello and Batina 2016, algorithm 3, exception free
+    pub fn double(&self) -> Self {
+        let a = C::a();
+        let b3 = &C::b().double() + &C::b();
+        let (x, y, z) = (&self.x, &self.y, &self.z);
+
+        let t0 = x.square();
+        let t1 = y.square();
+        let mut t2 = z.square();
+        let mut t3 = (x * y).double();
+        let mut z3 = (x * z).double();
+        let mut x3 = &a * &z3;
+        let mut y3 = &b3 * &t2;
+        y3 = &x3 + &y3;
+        x3 = &t1 - &y3;
+        y3 = &t1 + &y3;
+        y3 = &x3 * &y3;
+        x3 = &t3 * &x3;
+        z3 = &b3 * &z3;
+        t2 = &a * &t2;
+        t3 = &t0 - &t2;
+        t3 = &a * &t3;
+        t3 = &t3 + &z3;
+        z3 = t0.double();
+        let mut t0 = &z3 + &t0;
+        t0 = &t0 + &t2;
+        t0 = &t0 * &t3;
+        y3 = &y3 + &t0;
+        let t2 = (y * z).double();
+        let t0 = &t2 * &t3;
+        x3 = &x3 - &t0;
+        z3 = &t2 * &t1;
+        z3 = z3.double().double();
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    pub fn mul_limbs(&self, scalar: &[u64]) -> Self {
+        double_and_add(self, scalar)
+    }
+
+    pub fn mul_wnaf(&self, scalar: &[u64], window: usize) -> Self {
+        wnaf(self, scalar, window)
+    }
+
+    pub fn mul_ladder(&self, scalar: &[u64]) -> Self {
+        ladder(self, scalar)
+    }
+}
+
+// Renes, Costello and Batina 2016, algorithm 1. Complete on curves of odd order,
+// the same formula serves for doubling, the identity and inverse points.
+impl<C> Add<&ProjectivePoint<C>> for &ProjectivePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    type Output = ProjectivePoint<C>;
+
+    fn add(self, other: &ProjectivePoint<C>) -> ProjectivePoint<C> {
+        let a = C::a();
+        let b3 = &C::b().double() + &C::b();
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = &(x1 + y1) * &(x2 + y2);
+        let mut t4 = &t0 + &t1;
+        t3 = &t3 - &t4;
+        t4 = &(x1 + z1) * &(x2 + z2);
+        let mut t5 = &t0 + &t2;
+        t4 = &t4 - &t5;
+        t5 = &(y1 + z1) * &(y2 + z2);
+        let mut x3 = &t1 + &t2;
+        t5 = &t5 - &x3;
+        let mut z3 = &a * &t4;
+        x3 = &b3 * &t2;
+        z3 = &x3 + &z3;
+        x3 = &t1 - &z3;
+        z3 = &t1 + &z3;
+        let mut y3 = &x3 * &z3;
+        t1 = &t0.double() + &t0;
+        t2 = &a * &t2;
+        t4 = &b3 * &t4;
+        t1 = &t1 + &t2;
+        t2 = &t0 - &t2;
+        t2 = &a * &t2;
+        t4 = &t4 + &t2;
+        t0 = &t1 * &t4;
+        y3 = &y3 + &t0;
+        t0 = &t5 * &t4;
+        x3 = &t3 * &x3;
+        x3 = &x3 - &t0;
+        t0 = &t3 * &t1;
+        z3 = &t5 * &z3;
+        z3 = &z3 + &t0;
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+}
+
+impl<C> Sub<&ProjectivePoint<C>> for &ProjectivePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    type Output = ProjectivePoint<C>;
+
+    fn sub(self, other: &ProjectivePoint<C>) -> ProjectivePoint<C> {
+        self + &(-other)
+    }
+}
+
+impl<C> Neg for &ProjectivePoint<C>
+where
+    C: WeierstrassConfig,
+{
+    type Output = ProjectivePoint<C>;
+
+    fn neg(self) -> ProjectivePoint<C> {
+        ProjectivePoint {
+            x: self.x.clone(),
+            y: -&self.y,
+            z: self.z.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::u64_field::U64Field;
+
+    // y^2 = x^3 + 2x + 4 over F_109 has 127 points, a prime
+    #[derive(Debug)]
+    struct PrimeCurve;
+
+    type F109 = U64Field<109>;
+
+    impl WeierstrassConfig for PrimeCurve {
+        type BaseField = F109;
+        type ScalarField = U64Field<127>;
+
+        fn a() -> FieldElement<F109> {
+            FieldElement::from(2)
+        }
+
+        fn b() -> FieldElement<F109> {
+            FieldElement::from(4)
+        }
+
+        fn generator() -> (FieldElement<F109>, FieldElement<F109>) {
+            (FieldElement::from(0), FieldElement::from(2))
+        }
+    }
+
+    // y^2 = x^3 + x + 3 over F_101 has 87 = 3 * 29 points
+    #[derive(Debug)]
+    struct CofactorCurve;
+
+    type F101 = U64Field<101>;
+
+    impl WeierstrassConfig for CofactorCurve {
+        type BaseField = F101;
+        type ScalarField = U64Field<29>;
+
+        fn a() -> FieldElement<F101> {
+            FieldElement::from(1)
+        }
+
+        fn b() -> FieldElement<F101> {
+            FieldElement::from(3)
+        }
+
+        fn generator() -> (FieldElement<F101>, FieldElement<F101>) {
+            (FieldElement::from(4), FieldElement::from(24))
+        }
+
+        fn cofactor() -> BigUint {
+            BigUint::from(3u64)
+        }
+    }
+
+    // y^2 = x^3 + 2x + 7 over F_101 has 106 = 2 * 53 points, (69, 0) of order two
+    #[derive(Debug)]
+    struct TwoTorsionCurve;
+
+    impl WeierstrassConfig for TwoTorsionCurve {
+        type BaseField = F101;
+        type ScalarField = U64Field<2>;
+
+        fn a() -> FieldElement<F101> {
+            FieldElement::from(2)
+        }
+
+        fn b() -> FieldElement<F101> {
+            FieldElement::from(7)
+        }
+
+        fn generator() -> (FieldElement<F101>, FieldElement<F101>) {
+            (FieldElement::from(69), FieldElement::from(0))
+        }
+
+        fn cofactor() -> BigUint {
+            BigUint::from(53u64)
+        }
+    }
+
+    fn points<C: WeierstrassConfig>(p: u64) -> Vec<AffinePoint<C>> {
+        let mut points = vec![AffinePoint::identity()];
+        for x in 0..p {
+            for y in 0..p {
+                let point = AffinePoint::new(FieldElement::from(x), FieldElement::from(y));
+                if point.is_on_curve() {
+                    points.push(point);
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_group_law() {
+        let points = points::<PrimeCurve>(109);
+        assert_eq!(points.len(), 127);
+
+        for p in points.iter().step_by(7) {
+            for q in points.iter().step_by(11) {
+                let (pj, qj) = (p.to_jacobian(), q.to_jacobian());
+                let sum = &pj + &qj;
+
+                assert!(sum.to_affine().is_on_curve());
+                assert!(sum == &qj + &pj);
+                assert!(&sum - &qj == pj);
+                assert!(&pj + &pj == pj.double());
+
+                // the complete formulas agree, including doubling and P + (-P)
+                let (pp, qp) = (p.to_projective(), q.to_projective());
+                assert!((&pp + &qp).to_affine() == sum.to_affine());
+                assert!((&pp + &pp).to_affine() == pj.double().to_affine());
+                assert!(pp.double() == &pp + &pp);
+                assert!((&pp - &pp).is_identity());
+            }
+            assert!(p.is_in_subgroup());
+            assert!(p.to_jacobian().mul_limbs(&[127]).is_identity());
+        }
+    }
+
+    #[test]
+    fn test_scalar_multiplication() {
+        let g = JacobianPoint::<PrimeCurve>::generator();
+        let gp = ProjectivePoint::<PrimeCurve>::generator();
+        let mut expected = JacobianPoint::identity();
+        for k in 0..300u64 {
+            assert!(g.mul_limbs(&[k]) == expected);
+            assert!(g.mul_ladder(&[k]) == expected);
+            for window in 2..6 {
+                assert!(g.mul_wnaf(&[k], window) == expected);
+            }
+            let affine = expected.to_affine();
+            assert!(gp.mul_limbs(&[k]).to_affine() == affine);
+            assert!(gp.mul_ladder(&[k]).to_affine() == affine);
+            assert!(gp.mul_wnaf(&[k], 4).to_affine() == affine);
+            expected = &expected + &g;
+        }
+        assert!((&g + &(-&g)).is_identity());
+
+        // multi-limb scalars reduce modulo the order
+        let big = [5, 1];
+        let reduced = ((1u128 << 64) + 5) % 127;
+        assert!(g.mul_wnaf(&big, 5) == g.mul_limbs(&[reduced as u64]));
+        assert!(g.mul_ladder(&big) == g.mul_limbs(&[reduced as u64]));
+    }
+
+    #[test]
+    fn test_wnaf_digits() {
+        for k in [1u64, 7, 255, 1000, 0xdeadbeef, u64::MAX] {
+            for window in 2..8 {
+                let digits = wnaf_digits(&[k], window);
+                let value = digits
+                    .iter()
+                    .rev()
+                    .fold(0i128, |acc, d| 2 * acc + *d as i128);
+                assert_eq!(value, k as i128);
+                for (i, d) in digits.iter().enumerate() {
+                    if *d != 0 {
+                        assert!(d % 2 != 0 && d.abs() < 1 << (window - 1));
+                        let following = &digits[i + 1..digits.len().min(i + window)];
+                        assert!(following.iter().all(|d| *d == 0));
+                    }
+                }
+            }
+        }
+        assert!(wnaf_digits(&[0], 4).is_empty());
+
+        // out of range windows are clamped instead of panicking
+        assert_eq!(wnaf_digits(&[1000], 0), wnaf_digits(&[1000], MIN_WINDOW));
+        assert_eq!(wnaf_digits(&[1000], 64), wnaf_digits(&[1000], MAX_WINDOW));
+        let g = JacobianPoint::<PrimeCurve>::generator();
+        for window in [0, 1, 17, usize::MAX] {
+            assert!(g.mul_wnaf(&[100], window) == g.mul_limbs(&[100]));
+        }
+    }
+
+    #[test]
+    fn test_subgroup() {
+        let points = points::<CofactorCurve>(101);
+        assert_eq!(points.len(), 87);
+
+        let in_subgroup = points.iter().filter(|p| p.is_in_subgroup()).count();
+        assert_eq!(in_subgroup, 29);
+        for p in points.iter() {
+            assert!(p
+                .to_jacobian()
+                .clear_cofactor()
+                .to_affine()
+                .is_in_subgroup());
+        }
+        assert!(AffinePoint::<CofactorCurve>::generator().is_in_subgroup());
+        assert!(
+            !AffinePoint::<CofactorCurve>::new(FieldElement::from(1), FieldElement::from(45))
+                .is_in_subgroup()
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        for point in points::<PrimeCurve>(109) {
+            let compressed = point.to_compressed();
+            let uncompressed = point.to_uncompressed();
+            assert!(AffinePoint::<PrimeCurve>::from_bytes(&compressed).unwrap() == point);
+            assert!(AffinePoint::<PrimeCurve>::from_bytes(&uncompressed).unwrap() == point);
+            if !point.is_identity() {
+                assert_eq!(compressed.len(), 9);
+                assert_eq!(uncompressed.len(), 17);
+            }
+        }
+
+        // x = 3 gives 27 + 6 + 4 = 37, a non-residue modulo 109
+        let mut bytes = vec![2];
+        bytes.extend(3u64.to_be_bytes());
+        assert!(AffinePoint::<PrimeCurve>::from_bytes(&bytes).is_err());
+
+        let mut off_curve = AffinePoint::<PrimeCurve>::generator().to_uncompressed();
+        off_curve[16] ^= 1;
+        assert!(AffinePoint::<PrimeCurve>::from_bytes(&off_curve).is_err());
+        assert!(AffinePoint::<PrimeCurve>::from_bytes(&[5]).is_err());
+        assert!(AffinePoint::<PrimeCurve>::from_bytes(&[]).is_err());
+
+        // points outside the subgroup are rejected on curves with a cofactor
+        let outside =
+            AffinePoint::<CofactorCurve>::new(FieldElement::from(1), FieldElement::from(45));
+        assert!(AffinePoint::<CofactorCurve>::from_bytes(&outside.to_compressed()).is_err());
+        let inside = AffinePoint::<CofactorCurve>::generator();
+        assert!(
+            AffinePoint::<CofactorCurve>::from_bytes(&inside.to_compressed()).unwrap() == inside
+        );
+
+        // (69, 0) only has the even encoding
+        let torsion = AffinePoint::<TwoTorsionCurve>::generator();
+        let compressed = torsion.to_compressed();
+        assert_eq!(compressed[0], 2);
+        assert!(AffinePoint::<TwoTorsionCurve>::from_bytes(&compressed).unwrap() == torsion);
+        let mut odd = compressed.clone();
+        odd[0] = 3;
+        assert!(AffinePoint::<TwoTorsionCurve>::from_bytes(&odd).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+use crate::fields::element::FieldElement;
+use cryptography::Field;
+
+// bit i of a little-endian limb exponent
+fn bit(exp: &[u64], i: usize) -> u64 {
+    exp.get(i / 64).map_or(0, |limb| (limb >> (i % 64)) & 1)
+}
+
+// w bits of the exponent starting at bit i
+fn window(exp: &[u64], i: usize, width: usize) -> usize {
+    (0..width).fold(0, |acc, j| acc | ((bit(exp, i + j) as usize) << j))
+}
+
+fn bit_length(exp: &[u64]) -> usize {
+    exp.iter()
+        .rposition(|limb| *limb != 0)
+        .map_or(0, |i| i * 64 + 64 - exp[i].leading_zeros() as usize)
+}
+
+// Montgomery's trick, inverts every non-zero element with a single field inversion.
+// Zeros are left as zero.
+pub fn batch_inverse_in_place<F: Field>(elements: &mut [FieldElement<F>]) {
+    // prefix[i] is the product of the non-zero elements before i
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut accumulator = FieldElement::<F>::one();
+    for element in elements.iter() {
+        prefix.push(accumulator.clone());
+        if !element.is_zero() {
+            accumulator *= element;
+        }
+    }
+
+    // accumulator is a product of non-zero elements so it is invertible
+    let mut inverse = accumulator.inverse().unwrap();
+
+    for (element, before) in elements.iter_mut().zip(prefix.iter()).rev() {
+        if element.is_zero() {
+            continue;
+        }
+        let next = &inverse * element;
+        *element = &inverse * before;
+        inverse = next;
+    }
+}
+
+pub fn batch_inverse<F: Field>(elements: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+    let mut result = elements.to_vec();
+    batch_inverse_in_place(&mut result);
+    result
+}
+
+// raises every base to the same exponent
+pub fn batch_pow<F: Field>(bases: &[FieldElement<F>], exp: &[u64]) -> Vec<FieldElement<F>> {
+    bases
+        .iter()
+        .map(|base| sliding_window_pow(base, exp, 4))
+        .collect()
+}
+
+// product of bases[i] ^ exps[i], Straus' interleaved method sharing the squarings
+pub fn multi_pow<F: Field>(bases: &[FieldElement<F>], exps: &[Vec<u64>]) -> FieldElement<F> {
+    assert_eq!(bases.len(), exps.len(), "every base needs an exponent");
+
+    const WIDTH: usize = 4;
+
+    let tables: Vec<Vec<FieldElement<F>>> = bases
+        .iter()
+        .map(|base| {
+            let mut table = vec![FieldElement::<F>::one()];
+            for j in 1..1 << WIDTH {
+                table.push(&table[j - 1] * base);
+            }
+            table
+        })
+        .collect();
+
+    let bits = exps.iter().map(|exp| bit_length(exp)).max().unwrap_or(0);
+    let windows = bits.div_ceil(WIDTH);
+
+    let mut result = FieldElement::<F>::one();
+    for i in (0..windows).rev() {
+        for _ in 0..WIDTH {
+            result = result.square();
+        }
+        for (table, exp) in tables.iter().zip(exps.iter()) {
+            let digit = window(exp, i * WIDTH, WIDTH);
+            if digit != 0 {
+                result *= &table[digit];
+            }
+        }
+    }
+
+    result
+}
+
+// left-to-right sliding window exponentiation over the odd powers of base
+pub fn sliding_window_pow<F: Field>(
+    base: &FieldElement<F>,
+    exp: &[u64],
+    width: usize,
+) -> FieldElement<F> {
+    assert!(width > 0, "window width must be positive");
+
+    // odd_powers[j] = base ^ (2j + 1)
+    let square = base.square();
+    let mut odd_powers = vec![base.clone()];
+    for j in 1..1 << (width - 1) {
+        odd_powers.push(&odd_powers[j - 1] * &square);
+    }
+
+    let mut result = FieldElement::<F>::one();
+    let mut i = bit_length(exp) as isize - 1;
+    while i >= 0 {
+        if bit(exp, i as usize) == 0 {
+            result = result.square();
+            i -= 1;
+            continue;
+        }
+
+        // longest window ending in a set bit
+        let mut low = (i - width as isize + 1).max(0);
+        while bit(exp, low as usize) == 0 {
+            low += 1;
+        }
+        let length = (i - low + 1) as usize;
+        for _ in 0..length {
+            result = result.square();
+        }
+        result *= &odd_powers[window(exp, low as usize, length) >> 1];
+        i = low - 1;
+    }
+
+    result
+}
+
+// precomputed base ^ (j * 2^(width * i)) so that exponentiation needs no squarings
+pub struct FixedBaseTable<F: Field> {
+    width: usize,
+    table: Vec<Vec<FieldElement<F>>>,
+}
+
+impl<F> FixedBaseTable<F>
+where
+    F: Field,
+{
+    // supports exponents up to max_bits bits
+    pub fn new(base: &FieldElement<F>, width: usize, max_bits: usize) -> Self {
+        assert!(width > 0, "window width must be positive");
+
+        let windows = max_bits.div_ceil(width);
+        let mut table = Vec::with_capacity(windows);
+        let mut window_base = base.clone();
+
+        for _ in 0..windows {
+            let mut row = vec![FieldElement::<F>::one()];
+            for j in 1..1 << width {
+                row.push(&row[j - 1] * &window_base);
+            }
+            window_base = &row[(1 << width) - 1] * &window_base;
+            table.push(row);
+        }
+
+        Self { width, table }
+    }
+
+    pub fn max_bits(&self) -> usize {
+        self.width * self.table.len()
+    }
+
+    pub fn pow(&self, exp: &[u64]) -> FieldElement<F> {
+        assert!(
+            bit_length(exp) <= self.max_bits(),
+            "exponent is larger than the table"
+        );
+
+        let mut result = FieldElement::<F>::one();
+        for (i, row) in self.table.iter().enumerate() {
+            let digit = window(exp, i * self.width, self.width);
+            if digit != 0 {
+                result *= &row[digit];
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        batch_inverse, batch_inverse_in_place, batch_pow, multi_pow, sliding_window_pow,
+        FixedBaseTable,
+    };
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+
+    type TestField = U64Field<18446744069414584321>;
+
+    #[test]
+    fn test_batch_inverse_matches_single_inverse() {
+        let elements: Vec<FieldElement<TestField>> = (0..32)
+            .map(|_| FieldElement::<TestField>::random())
+            .collect();
+
+        let inverses = batch_inverse(&elements);
+
+        for (element, inverse) in elements.iter().zip(inverses.iter()) {
+            assert!(element.inverse().unwrap() == *inverse);
+        }
+    }
+
+    #[test]
+    fn test_batch_inverse_skips_zeros() {
+        let mut elements = vec![
+            FieldElement::<TestField>::from(0),
+            FieldElement::<TestField>::from(2),
+            FieldElement::<TestField>::from(0),
+            FieldElement::<TestField>::from(7),
+            FieldElement::<TestField>::from(0),
+        ];
+
+        batch_inverse_in_place(&mut elements);
+
+        assert!(elements[0].is_zero());
+        assert!(elements[1] == FieldElement::<TestField>::from(2).inverse().unwrap());
+        assert!(elements[2].is_zero());
+        assert!(elements[3] == FieldElement::<TestField>::from(7).inverse().unwrap());
+        assert!(elements[4].is_zero());
+    }
+
+    #[test]
+    fn test_batch_inverse_empty_and_all_zero() {
+        assert!(batch_inverse::<TestField>(&[]).is_empty());
+
+        let zeros = vec![FieldElement::<TestField>::zero(); 3];
+        assert!(batch_inverse(&zeros).iter().all(|x| x.is_zero()));
+    }
+
+    #[test]
+    fn test_batch_pow() {
+        let bases: Vec<FieldElement<TestField>> = (0..8)
+            .map(|_| FieldElement::<TestField>::random())
+            .collect();
+        let exp = [0x1234_5678_9abc_def0, 0x0fed_cba9];
+
+        let powers = batch_pow(&bases, &exp);
+
+        for (base, power) in bases.iter().zip(powers.iter()) {
+            assert!(base.pow_limbs(&exp) == *power);
+        }
+    }
+
+    #[test]
+    fn test_multi_pow() {
+        let bases: Vec<FieldElement<TestField>> = (0..5)
+            .map(|_| FieldElement::<TestField>::random())
+            .collect();
+        let exps: Vec<Vec<u64>> = vec![
+            vec![0],
+            vec![1],
+            vec![u64::MAX, 3],
+            vec![12345],
+            vec![7, 0, 1],
+        ];
+
+        let mut expected = FieldElement::<TestField>::one();
+        for (base, exp) in bases.iter().zip(exps.iter()) {
+            expected *= &base.pow_limbs(exp);
+        }
+
+        assert!(multi_pow(&bases, &exps) == expected);
+        assert!(multi_pow::<TestField>(&[], &[]).is_one());
+    }
+
+    #[test]
+    fn test_sliding_window_pow() {
+        let base = FieldElement::<TestField>::random();
+
+        for exp in [
+            vec![0],
+            vec![1],
+            vec![2],
+            vec![0b1011_0001],
+            vec![u64::MAX, u64::MAX],
+        ] {
+            for width in 1..6 {
+                assert!(sliding_window_pow(&base, &exp, width) == base.pow_limbs(&exp));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let base = FieldElement::<TestField>::random();
+        let table = FixedBaseTable::new(&base, 4, 128);
+
+        assert_eq!(table.max_bits(), 128);
+        for exp in [
+            vec![0],
+            vec![1],
+            vec![0xdead_beef],
+            vec![u64::MAX, u64::MAX],
+        ] {
+            assert!(table.pow(&exp) == base.pow_limbs(&exp));
+        }
+
+        let odd_table = FixedBaseTable::new(&base, 5, 70);
+        let exp = [u64::MAX, 0x3f];
+        assert!(odd_table.pow(&exp) == base.pow_limbs(&exp));
+    }
+}
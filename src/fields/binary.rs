@@ -0,0 +1,569 @@
+use cryptography::Field;
+use num_bigint::BigUint;
+use std::marker::PhantomData;
+
+// AES, x^8 + x^4 + x^3 + x + 1
+pub type AesField = BinaryField<8, 0x1B>;
+
+// GHASH, x^128 + x^7 + x^2 + x + 1. GCM stores its elements bit-reflected,
+// so blocks have to be reversed before they are used with this type.
+pub type GhashField = BinaryField<128, 0x87>;
+
+// Reed-Solomon over bytes, x^8 + x^4 + x^3 + x^2 + 1 which has x as a generator
+pub type ReedSolomonField = BinaryField<8, 0x1D>;
+
+// returns a^-1 = a^(2^n - 2) = (a^(2^(n - 1) - 1))^2 using the Itoh-Tsujii addition chain
+pub fn itoh_tsujii_inverse<F: Field>(a: &F::BaseType, degree: usize) -> Option<F::BaseType> {
+    if F::eq(a, &F::zero()) {
+        return None;
+    }
+
+    // beta holds a^(2^k - 1), built from the bits of n - 1 most significant first
+    let target = degree - 1;
+    let mut beta = a.clone();
+    let mut k = 1;
+
+    for i in (0..usize::BITS - target.leading_zeros() - 1).rev() {
+        let mut shifted = beta.clone();
+        for _ in 0..k {
+            shifted = F::square(&shifted);
+        }
+        beta = F::mul(&shifted, &beta);
+        k *= 2;
+
+        if (target >> i) & 1 == 1 {
+            beta = F::mul(&F::square(&beta), a);
+            k += 1;
+        }
+    }
+
+    Some(F::square(&beta))
+}
+
+// GF(2^N) for N in 2..=128, elements are polynomials over GF(2) with bit i
+// holding the coefficient of x^i. POLYNOMIAL is the irreducible reduction
+// polynomial without its leading x^N term.
+#[derive(Debug)]
+pub struct BinaryField<const N: u32, const POLYNOMIAL: u128>;
+
+impl<const N: u32, const POLYNOMIAL: u128> BinaryField<N, POLYNOMIAL> {
+    // fails to compile for N outside 2..=128, the bit operations below need N <= 128
+    // and the Itoh-Tsujii chain needs N >= 2
+    const DEGREE: u32 = {
+        assert!(N >= 2 && N <= 128, "binary fields need N in 2..=128");
+        N
+    };
+
+    const MASK: u128 = if Self::DEGREE == 128 {
+        u128::MAX
+    } else {
+        (1 << Self::DEGREE) - 1
+    };
+
+    // returns a ^ b, the exponent taken as an integer
+    pub fn pow(a: &u128, b: &u128) -> u128 {
+        Self::pow_limbs(a, &[*b as u64, (*b >> 64) as u64])
+    }
+
+    // carry-less multiplication, returns the (high, low) halves of the product
+    pub fn carryless_mul(a: u128, b: u128) -> (u128, u128) {
+        let mut high = 0u128;
+        let mut low = 0u128;
+
+        for i in 0..Self::DEGREE {
+            if (b >> i) & 1 == 1 {
+                low ^= a << i;
+                if i > 0 {
+                    high ^= a >> (128 - i);
+                }
+            }
+        }
+
+        (high, low)
+    }
+
+    // reduces a product of two field elements modulo x^N + POLYNOMIAL
+    pub fn reduce(high: u128, low: u128) -> u128 {
+        let mut high = high;
+        let mut low = low;
+
+        for i in (N..2 * N - 1).rev() {
+            let bit = if i >= 128 {
+                (high >> (i - 128)) & 1
+            } else {
+                (low >> i) & 1
+            };
+
+            if bit == 1 {
+                let shift = i - N;
+                if i >= 128 {
+                    high ^= 1 << (i - 128);
+                } else {
+                    low ^= 1 << i;
+                }
+                low ^= POLYNOMIAL << shift;
+                if shift > 0 {
+                    high ^= POLYNOMIAL >> (128 - shift);
+                }
+            }
+        }
+
+        low
+    }
+}
+
+impl<const N: u32, const POLYNOMIAL: u128> Field for BinaryField<N, POLYNOMIAL> {
+    type BaseType = u128;
+
+    fn add(a: &u128, b: &u128) -> u128 {
+        a ^ b
+    }
+
+    fn sub(a: &u128, b: &u128) -> u128 {
+        a ^ b
+    }
+
+    fn mul(a: &u128, b: &u128) -> u128 {
+        let (high, low) = Self::carryless_mul(*a, *b);
+        Self::reduce(high, low)
+    }
+
+    fn neg(a: &u128) -> u128 {
+        *a
+    }
+
+    // interprets the bits of a as a polynomial, reducing every term of degree N or more
+    fn from_u64(a: u64) -> u128 {
+        let mut value = a as u128;
+        for i in (N..64).rev() {
+            if (value >> i) & 1 == 1 {
+                value ^= (1 << i) | (POLYNOMIAL << (i - N));
+            }
+        }
+        value
+    }
+
+    fn eq(a: &u128, b: &u128) -> bool {
+        *a == *b
+    }
+
+    fn inverse(a: &u128) -> Option<u128> {
+        itoh_tsujii_inverse::<Self>(a, Self::DEGREE as usize)
+    }
+
+    fn characteristic() -> BigUint {
+        BigUint::from(2u64)
+    }
+
+    fn random() -> u128 {
+        rand::random::<u128>() & Self::MASK
+    }
+
+    fn double(_a: &u128) -> u128 {
+        0
+    }
+
+    // a^(2^power), squaring is linear in characteristic two
+    fn frobenius_map(a: &u128, power: usize) -> u128 {
+        let mut result = *a;
+        for _ in 0..power % N as usize {
+            result = Self::square(&result);
+        }
+        result
+    }
+}
+
+// log/antilog tables for GF(2^N) with N <= 16
+pub struct BinaryFieldTables<const N: u32, const POLYNOMIAL: u128> {
+    generator: u128,
+    exp: Vec<u16>,
+    log: Vec<u16>,
+}
+
+impl<const N: u32, const POLYNOMIAL: u128> BinaryFieldTables<N, POLYNOMIAL> {
+    pub fn new() -> Result<Self, &'static str> {
+        if N > 16 {
+            return Err("log tables are only built for fields up to GF(2^16)");
+        }
+
+        let generator = Self::find_generator().ok_or("reduction polynomial is not irreducible")?;
+        let order = (1usize << N) - 1;
+
+        // exp is doubled so that exp[log a + log b] needs no reduction
+        let mut exp = vec![0u16; 2 * order];
+        let mut log = vec![0u16; order + 1];
+        let mut x = 1u128;
+        for i in 0..order {
+            exp[i] = x as u16;
+            exp[i + order] = x as u16;
+            log[x as usize] = i as u16;
+            x = BinaryField::<N, POLYNOMIAL>::mul(&x, &generator);
+        }
+
+        Ok(Self {
+            generator,
+            exp,
+            log,
+        })
+    }
+
+    // smallest element whose order is 2^N - 1
+    fn find_generator() -> Option<u128> {
+        let order = (1u64 << N) - 1;
+
+        let mut factors = Vec::new();
+        let mut rest = order;
+        let mut q = 2;
+        while q * q <= rest {
+            if rest.is_multiple_of(q) {
+                factors.push(q);
+                while rest.is_multiple_of(q) {
+                    rest /= q;
+                }
+            }
+            q += 1;
+        }
+        if rest > 1 {
+            factors.push(rest);
+        }
+
+        (2..=order as u128).find(|candidate| {
+            BinaryField::<N, POLYNOMIAL>::pow(candidate, &(order as u128)) == 1
+                && factors.iter().all(|factor| {
+                    BinaryField::<N, POLYNOMIAL>::pow(candidate, &((order / factor) as u128)) != 1
+                })
+        })
+    }
+
+    pub fn generator(&self) -> u128 {
+        self.generator
+    }
+
+    // generator^i
+    pub fn antilog(&self, i: usize) -> u128 {
+        self.exp[i % self.order()] as u128
+    }
+
+    // discrete logarithm to the generator base, None for zero
+    pub fn log(&self, a: u128) -> Option<usize> {
+        if a == 0 {
+            None
+        } else {
+            Some(self.log[a as usize] as usize)
+        }
+    }
+
+    pub fn mul(&self, a: u128, b: u128) -> u128 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize] as u128
+    }
+
+    pub fn div(&self, a: u128, b: u128) -> Option<u128> {
+        Some(self.mul(a, self.inverse(b)?))
+    }
+
+    pub fn inverse(&self, a: u128) -> Option<u128> {
+        let log = self.log(a)?;
+        Some(self.exp[(self.order() - log) % self.order()] as u128)
+    }
+
+    pub fn pow(&self, a: u128, e: usize) -> u128 {
+        match self.log(a) {
+            None if e == 0 => 1,
+            None => 0,
+            Some(log) => self.exp[(log * (e % self.order())) % self.order()] as u128,
+        }
+    }
+
+    fn order(&self) -> usize {
+        (1 << N) - 1
+    }
+}
+
+pub trait WideBinaryFieldConfig {
+    // extension degree n
+    const DEGREE: usize;
+
+    // exponents of the reduction polynomial terms below x^n
+    const TERMS: &'static [usize];
+}
+
+// GF(2^n) for arbitrary n, elements are little-endian u64 limbs
+#[derive(Debug)]
+pub struct WideBinaryField<C>(PhantomData<C>);
+
+impl<C> WideBinaryField<C>
+where
+    C: WideBinaryFieldConfig,
+{
+    // fails to compile for degrees below two, where the Itoh-Tsujii chain underflows
+    const DEGREE: usize = {
+        assert!(C::DEGREE >= 2, "binary fields need degree at least 2");
+        C::DEGREE
+    };
+
+    fn limbs() -> usize {
+        Self::DEGREE.div_ceil(64)
+    }
+
+    fn carryless_mul_64(a: u64, b: u64) -> u128 {
+        let mut result = 0u128;
+        for i in 0..64 {
+            if (b >> i) & 1 == 1 {
+                result ^= (a as u128) << i;
+            }
+        }
+        result
+    }
+
+    pub fn carryless_mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+        let mut result = vec![0u64; a.len() + b.len()];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                let product = Self::carryless_mul_64(*x, *y);
+                result[i + j] ^= product as u64;
+                result[i + j + 1] ^= (product >> 64) as u64;
+            }
+        }
+        result
+    }
+
+    pub fn reduce(product: &[u64]) -> Vec<u64> {
+        let mut product = product.to_vec();
+        let top = product.len() * 64;
+
+        for i in (C::DEGREE..top).rev() {
+            if (product[i / 64] >> (i % 64)) & 1 == 1 {
+                product[i / 64] ^= 1 << (i % 64);
+                let shift = i - C::DEGREE;
+                for term in C::TERMS {
+                    let bit = term + shift;
+                    product[bit / 64] ^= 1 << (bit % 64);
+                }
+            }
+        }
+
+        product.truncate(Self::limbs());
+        product.resize(Self::limbs(), 0);
+        product
+    }
+}
+
+impl<C> Field for WideBinaryField<C>
+where
+    C: WideBinaryFieldConfig,
+{
+    type BaseType = Vec<u64>;
+
+    fn add(a: &Vec<u64>, b: &Vec<u64>) -> Vec<u64> {
+        a.iter().zip(b.iter()).map(|(x, y)| x ^ y).collect()
+    }
+
+    fn sub(a: &Vec<u64>, b: &Vec<u64>) -> Vec<u64> {
+        Self::add(a, b)
+    }
+
+    fn mul(a: &Vec<u64>, b: &Vec<u64>) -> Vec<u64> {
+        Self::reduce(&Self::carryless_mul(a, b))
+    }
+
+    fn neg(a: &Vec<u64>) -> Vec<u64> {
+        a.clone()
+    }
+
+    fn from_u64(a: u64) -> Vec<u64> {
+        let mut value = vec![0u64; Self::limbs()];
+        value[0] = a;
+        Self::reduce(&value)
+    }
+
+    fn eq(a: &Vec<u64>, b: &Vec<u64>) -> bool {
+        a == b
+    }
+
+    fn inverse(a: &Vec<u64>) -> Option<Vec<u64>> {
+        itoh_tsujii_inverse::<Self>(a, Self::DEGREE)
+    }
+
+    fn characteristic() -> BigUint {
+        BigUint::from(2u64)
+    }
+
+    fn random() -> Vec<u64> {
+        let mut value: Vec<u64> = (0..Self::limbs()).map(|_| rand::random::<u64>()).collect();
+        if !C::DEGREE.is_multiple_of(64) {
+            value[Self::limbs() - 1] &= (1 << (C::DEGREE % 64)) - 1;
+        }
+        value
+    }
+
+    fn zero() -> Vec<u64> {
+        vec![0u64; Self::limbs()]
+    }
+
+    fn double(_a: &Vec<u64>) -> Vec<u64> {
+        Self::zero()
+    }
+
+    fn frobenius_map(a: &Vec<u64>, power: usize) -> Vec<u64> {
+        let mut result = a.clone();
+        for _ in 0..power % C::DEGREE {
+            result = Self::square(&result);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AesField, BinaryField, BinaryFieldTables, GhashField, ReedSolomonField, WideBinaryField,
+        WideBinaryFieldConfig,
+    };
+    use crate::fields::element::FieldElement;
+    use cryptography::{galois_multiplication, Field};
+
+    // NIST B-163, x^163 + x^7 + x^6 + x^3 + 1
+    #[derive(Debug)]
+    struct B163;
+
+    impl WideBinaryFieldConfig for B163 {
+        const DEGREE: usize = 163;
+        const TERMS: &'static [usize] = &[7, 6, 3, 0];
+    }
+
+    // same polynomial as GhashField, used to cross check both representations
+    #[derive(Debug)]
+    struct Wide128;
+
+    impl WideBinaryFieldConfig for Wide128 {
+        const DEGREE: usize = 128;
+        const TERMS: &'static [usize] = &[7, 2, 1, 0];
+    }
+
+    #[test]
+    fn test_aes_field_matches_galois_multiplication() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(
+                    AesField::mul(&(a as u128), &(b as u128)),
+                    galois_multiplication(a, b) as u128
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_aes_field_inverse() {
+        // worked example from FIPS-197, {53} * {ca} = {01}
+        assert_eq!(AesField::inverse(&0x53), Some(0xca));
+        assert_eq!(AesField::inverse(&0), None);
+
+        for a in 1..=255u128 {
+            assert_eq!(AesField::mul(&a, &AesField::inverse(&a).unwrap()), 1);
+        }
+    }
+
+    #[test]
+    fn test_ghash_field_axioms() {
+        for _ in 0..20 {
+            let a = FieldElement::<GhashField>::random();
+            let b = FieldElement::<GhashField>::random();
+            let c = FieldElement::<GhashField>::random();
+
+            assert!(&a * &(&b + &c) == &(&a * &b) + &(&a * &c));
+            assert!(&(&a * &b) * &c == &a * &(&b * &c));
+            assert!((&a + &a).is_zero());
+            assert!((&a * &a.inverse().unwrap()).is_one());
+        }
+    }
+
+    #[test]
+    fn test_ghash_reduction() {
+        // x^127 * x = x^128 = x^7 + x^2 + x + 1
+        assert_eq!(GhashField::mul(&(1 << 127), &2), 0x87);
+    }
+
+    #[test]
+    fn test_from_u64_reduces_all_bits() {
+        // x^7 + ... + 1 = x^3 + x^2 + 1 modulo x^4 + x + 1
+        type F16 = BinaryField<4, 0x3>;
+        assert_eq!(F16::from_u64(0xff), 0xd);
+        // x^15 = 1, so of x^0 .. x^63 only x^0 .. x^3 occur an odd number of times
+        assert_eq!(F16::from_u64(u64::MAX), 0xf);
+
+        // agrees with Horner's rule at x for every byte
+        for a in 0..=255u64 {
+            let x = FieldElement::<F16>::new(2);
+            let expected = (0..8).rev().fold(FieldElement::<F16>::zero(), |acc, i| {
+                &(&acc * &x) + &FieldElement::new(((a >> i) & 1) as u128)
+            });
+            assert_eq!(F16::from_u64(a), expected.value);
+        }
+        assert_eq!(AesField::from_u64(0x11b), 0);
+    }
+
+    #[test]
+    fn test_frobenius_has_order_n() {
+        let a = FieldElement::<BinaryField<13, 0x1B>>::random();
+
+        assert!(a.frobenius_map(13) == a);
+        assert!(a.frobenius_map(1) == a.square());
+    }
+
+    #[test]
+    fn test_wide_field_matches_ghash_field() {
+        for _ in 0..20 {
+            let a = GhashField::random();
+            let b = GhashField::random();
+            let wide_a = vec![a as u64, (a >> 64) as u64];
+            let wide_b = vec![b as u64, (b >> 64) as u64];
+
+            let expected = GhashField::mul(&a, &b);
+            let product = WideBinaryField::<Wide128>::mul(&wide_a, &wide_b);
+
+            assert_eq!(product, vec![expected as u64, (expected >> 64) as u64]);
+        }
+    }
+
+    #[test]
+    fn test_wide_field_axioms() {
+        for _ in 0..5 {
+            let a = FieldElement::<WideBinaryField<B163>>::random();
+            let b = FieldElement::<WideBinaryField<B163>>::random();
+            let c = FieldElement::<WideBinaryField<B163>>::random();
+
+            assert!(&a * &(&b + &c) == &(&a * &b) + &(&a * &c));
+            assert!(&(&a * &b) * &c == &a * &(&b * &c));
+            assert!((&a * &a.inverse().unwrap()).is_one());
+            assert!(a.frobenius_map(163) == a);
+        }
+    }
+
+    #[test]
+    fn test_tables_agree_with_field() {
+        let tables = BinaryFieldTables::<8, 0x1D>::new().unwrap();
+
+        assert_eq!(tables.generator(), 2);
+        for a in 0..=255u128 {
+            for b in 0..=255u128 {
+                assert_eq!(tables.mul(a, b), ReedSolomonField::mul(&a, &b));
+            }
+            assert_eq!(tables.inverse(a), ReedSolomonField::inverse(&a));
+            assert_eq!(tables.pow(a, 5), ReedSolomonField::pow(&a, &5));
+        }
+    }
+
+    #[test]
+    fn test_tables_log_antilog() {
+        let tables = BinaryFieldTables::<16, 0x100B>::new().unwrap();
+
+        for i in [0, 1, 2, 1000, 65534] {
+            assert_eq!(tables.log(tables.antilog(i)), Some(i));
+        }
+        assert_eq!(tables.log(0), None);
+        assert!(BinaryFieldTables::<20, 0x9>::new().is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::fields::binary::BinaryField;
+use crate::fields::element::FieldElement;
+use crate::fields::u64_field::U64Field;
+use cryptography::Field;
+
+// canonical fixed-length encoding of field elements
+pub trait FieldBytes: Field {
+    // length of a serialized element
+    const ELEMENT_BYTES: usize;
+
+    // big-endian, ELEMENT_BYTES long
+    fn to_bytes(a: &Self::BaseType) -> Vec<u8>;
+
+    // None unless bytes is the canonical encoding of an element
+    fn from_bytes(bytes: &[u8]) -> Option<Self::BaseType>;
+}
+
+impl<const MODULUS: u64> FieldBytes for U64Field<MODULUS> {
+    const ELEMENT_BYTES: usize = 8;
+
+    fn to_bytes(a: &u64) -> Vec<u8> {
+        a.to_be_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<u64> {
+        let value = u64::from_be_bytes(bytes.try_into().ok()?);
+        (value < MODULUS).then_some(value)
+    }
+}
+
+impl<const N: u32, const POLYNOMIAL: u128> FieldBytes for BinaryField<N, POLYNOMIAL> {
+    const ELEMENT_BYTES: usize = N.div_ceil(8) as usize;
+
+    fn to_bytes(a: &u128) -> Vec<u8> {
+        a.to_be_bytes()[16 - Self::ELEMENT_BYTES..].to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<u128> {
+        if bytes.len() != Self::ELEMENT_BYTES {
+            return None;
+        }
+        let value = bytes
+            .iter()
+            .fold(0u128, |acc, byte| (acc << 8) | *byte as u128);
+        (N == 128 || value >> N == 0).then_some(value)
+    }
+}
+
+// concatenated encodings
+pub fn elements_to_bytes<F: FieldBytes>(elements: &[FieldElement<F>]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(elements.len() * F::ELEMENT_BYTES);
+    for element in elements {
+        bytes.extend(F::to_bytes(&element.value));
+    }
+    bytes
+}
+
+pub fn elements_from_bytes<F: FieldBytes>(
+    bytes: &[u8],
+) -> Result<Vec<FieldElement<F>>, &'static str> {
+    if !bytes.len().is_multiple_of(F::ELEMENT_BYTES) {
+        return Err("length is not a multiple of the element size");
+    }
+    bytes
+        .chunks(F::ELEMENT_BYTES)
+        .map(|chunk| {
+            F::from_bytes(chunk)
+                .map(FieldElement::<F>::new)
+                .ok_or("invalid field element encoding")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{elements_from_bytes, elements_to_bytes, FieldBytes};
+    use crate::fields::binary::BinaryField;
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type GhashField = BinaryField<128, 0x87>;
+
+    #[test]
+    fn test_round_trip() {
+        let bytes = Goldilocks::to_bytes(&0x0102030405060708);
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(Goldilocks::from_bytes(&bytes), Some(0x0102030405060708));
+        assert_eq!(Goldilocks::from_bytes(&[0xff; 8]), None);
+        assert_eq!(Goldilocks::from_bytes(&[0; 7]), None);
+
+        let value = u128::MAX - 5;
+        assert_eq!(
+            GhashField::from_bytes(&GhashField::to_bytes(&value)),
+            Some(value)
+        );
+        assert_eq!(BinaryField::<4, 0x3>::from_bytes(&[0x10]), None);
+        assert_eq!(BinaryField::<4, 0x3>::from_bytes(&[0x0f]), Some(0x0f));
+    }
+
+    #[test]
+    fn test_element_vectors() {
+        let elements: Vec<FieldElement<Goldilocks>> =
+            (0..5).map(|_| FieldElement::random()).collect();
+        let bytes = elements_to_bytes(&elements);
+
+        assert_eq!(bytes.len(), 40);
+        assert!(elements_from_bytes::<Goldilocks>(&bytes).unwrap() == elements);
+        assert!(elements_from_bytes::<Goldilocks>(&bytes[1..]).is_err());
+        assert!(elements_from_bytes::<Goldilocks>(&[0xff; 8]).is_err());
+    }
+}
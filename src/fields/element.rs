@@ -1,4 +1,4 @@
-use cryptography::Field;
+use cryptography::{Field, ModularArithmetic};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 #[derive(Debug)]
@@ -6,6 +6,17 @@ pub struct FieldElement<F: Field> {
     pub value: F::BaseType,
 }
 
+impl<F> Clone for FieldElement<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+        }
+    }
+}
+
 impl<F> Add<&FieldElement<F>> for &FieldElement<F>
 where
     F: Field,
@@ -126,6 +137,69 @@ where
 impl<F> FieldElement<F>
 where
     F: Field,
+{
+    pub fn new(value: F::BaseType) -> FieldElement<F> {
+        FieldElement::<F> { value }
+    }
+
+    pub fn zero() -> FieldElement<F> {
+        FieldElement::<F> { value: F::zero() }
+    }
+
+    pub fn one() -> FieldElement<F> {
+        FieldElement::<F> { value: F::one() }
+    }
+
+    pub fn random() -> FieldElement<F> {
+        FieldElement::<F> { value: F::random() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        F::eq(&self.value, &F::zero())
+    }
+
+    pub fn is_one(&self) -> bool {
+        F::eq(&self.value, &F::one())
+    }
+
+    pub fn double(&self) -> FieldElement<F> {
+        FieldElement::<F> {
+            value: F::double(&self.value),
+        }
+    }
+
+    pub fn square(&self) -> FieldElement<F> {
+        FieldElement::<F> {
+            value: F::square(&self.value),
+        }
+    }
+
+    // multiplicative inverse, None for zero
+    pub fn inverse(&self) -> Option<FieldElement<F>> {
+        F::inverse(&self.value).map(|value| FieldElement::<F> { value })
+    }
+
+    // exponent given as little-endian u64 limbs
+    pub fn pow_limbs(&self, exp: &[u64]) -> FieldElement<F> {
+        FieldElement::<F> {
+            value: F::pow_limbs(&self.value, exp),
+        }
+    }
+
+    pub fn pow_u64(&self, exp: u64) -> FieldElement<F> {
+        self.pow_limbs(&[exp])
+    }
+
+    pub fn frobenius_map(&self, power: usize) -> FieldElement<F> {
+        FieldElement::<F> {
+            value: F::frobenius_map(&self.value, power),
+        }
+    }
+}
+
+impl<F> FieldElement<F>
+where
+    F: ModularArithmetic,
 {
     pub fn pow(&self, exp: &F::BaseType) -> FieldElement<F> {
         FieldElement::<F> {
@@ -204,6 +278,36 @@ mod tests {
         assert!(c.value == 1);
     }
 
+    #[test]
+    fn test_neg_zero_u64_field_element() {
+        let a: FieldElement<U64TestField> = FieldElement::<U64TestField>::from(0);
+
+        let c: FieldElement<U64TestField> = -&a;
+
+        assert!(c == FieldElement::<U64TestField>::from(0));
+        assert!(c.value == 0);
+    }
+
+    #[test]
+    fn test_pow_u64_field_element() {
+        let a: FieldElement<U64TestField> = FieldElement::<U64TestField>::from(3);
+
+        let c: FieldElement<U64TestField> = a.pow(&4);
+
+        assert!(c == FieldElement::<U64TestField>::from(81));
+        assert!(c.value == 81);
+    }
+
+    #[test]
+    fn test_inverse_u64_field_element() {
+        let a: FieldElement<U64TestField> = FieldElement::<U64TestField>::from(14);
+
+        let c: FieldElement<U64TestField> = &a * &a.inverse().unwrap();
+
+        assert!(c == FieldElement::<U64TestField>::from(1));
+        assert!(FieldElement::<U64TestField>::from(0).inverse().is_none());
+    }
+
     #[test]
     fn test_mul_u64_field_element() {
         let a: FieldElement<U64TestField> = FieldElement::<U64TestField>::from(14);
@@ -0,0 +1,523 @@
+use cryptography::Field;
+use num_bigint::BigUint;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Mutex, OnceLock};
+
+pub trait QuadraticExtensionConfig: 'static {
+    type BaseField: Field;
+
+    // non-residue β of the base field, the extension is built as u^2 = β
+    fn non_residue() -> <Self::BaseField as Field>::BaseType;
+
+    // returns β * a
+    fn mul_by_non_residue(
+        a: &<Self::BaseField as Field>::BaseType,
+    ) -> <Self::BaseField as Field>::BaseType {
+        Self::BaseField::mul(&Self::non_residue(), a)
+    }
+}
+
+pub trait CubicExtensionConfig: 'static {
+    type BaseField: Field;
+
+    // cubic non-residue ξ of the base field, the extension is built as v^3 = ξ
+    fn non_residue() -> <Self::BaseField as Field>::BaseType;
+
+    // returns ξ * a
+    fn mul_by_non_residue(
+        a: &<Self::BaseField as Field>::BaseType,
+    ) -> <Self::BaseField as Field>::BaseType {
+        Self::BaseField::mul(&Self::non_residue(), a)
+    }
+}
+
+type QuadraticBase<C> = <<C as QuadraticExtensionConfig>::BaseField as Field>::BaseType;
+type CubicBase<C> = <<C as CubicExtensionConfig>::BaseField as Field>::BaseType;
+
+// returns (p ^ power) as an exponent, p being the characteristic of F
+fn frobenius_exponent<F: Field>(power: usize) -> BigUint {
+    F::characteristic().pow(power as u32)
+}
+
+// returns a ^ e for an arbitrary size exponent
+fn pow_biguint<F: Field>(a: &F::BaseType, e: &BigUint) -> F::BaseType {
+    F::pow_limbs(a, &e.to_u64_digits())
+}
+
+type FrobeniusCache = Mutex<HashMap<(TypeId, usize), Box<dyn Any + Send + Sync>>>;
+
+// returns the Frobenius coefficients of the extension E for p^power, computed on the
+// first call and shared by every later one
+fn frobenius_coefficients<E, V>(power: usize, compute: impl FnOnce() -> V) -> V
+where
+    E: 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    static CACHE: OnceLock<FrobeniusCache> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    let key = (TypeId::of::<E>(), power);
+    if let Some(coefficients) = cache.lock().unwrap().get(&key) {
+        return coefficients.downcast_ref::<V>().unwrap().clone();
+    }
+    // computed outside the lock, a racing thread at worst repeats the work
+    let coefficients = compute();
+    cache
+        .lock()
+        .unwrap()
+        .insert(key, Box::new(coefficients.clone()));
+    coefficients
+}
+
+// Elements are a0 + a1 * u stored as [a0, a1]
+#[derive(Debug)]
+pub struct QuadraticExtension<C>(PhantomData<C>);
+
+impl<C> QuadraticExtension<C>
+where
+    C: QuadraticExtensionConfig,
+{
+    // returns a0 - a1 * u
+    pub fn conjugate(a: &[QuadraticBase<C>; 2]) -> [QuadraticBase<C>; 2] {
+        [a[0].clone(), C::BaseField::neg(&a[1])]
+    }
+
+    // returns (a0 + a1 * u) * b for b in the base field
+    pub fn mul_by_base(a: &[QuadraticBase<C>; 2], b: &QuadraticBase<C>) -> [QuadraticBase<C>; 2] {
+        [C::BaseField::mul(&a[0], b), C::BaseField::mul(&a[1], b)]
+    }
+
+    // returns a0^2 - β * a1^2, the norm down to the base field
+    pub fn norm(a: &[QuadraticBase<C>; 2]) -> QuadraticBase<C> {
+        let t0 = C::BaseField::square(&a[0]);
+        let t1 = C::mul_by_non_residue(&C::BaseField::square(&a[1]));
+        C::BaseField::sub(&t0, &t1)
+    }
+}
+
+impl<C> Field for QuadraticExtension<C>
+where
+    C: QuadraticExtensionConfig,
+{
+    type BaseType = [QuadraticBase<C>; 2];
+
+    fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [
+            C::BaseField::add(&a[0], &b[0]),
+            C::BaseField::add(&a[1], &b[1]),
+        ]
+    }
+
+    fn sub(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [
+            C::BaseField::sub(&a[0], &b[0]),
+            C::BaseField::sub(&a[1], &b[1]),
+        ]
+    }
+
+    // Karatsuba, three base field multiplications
+    fn mul(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        let v0 = C::BaseField::mul(&a[0], &b[0]);
+        let v1 = C::BaseField::mul(&a[1], &b[1]);
+        let a01 = C::BaseField::add(&a[0], &a[1]);
+        let b01 = C::BaseField::add(&b[0], &b[1]);
+
+        let c0 = C::BaseField::add(&v0, &C::mul_by_non_residue(&v1));
+        let c1 = C::BaseField::sub(&C::BaseField::sub(&C::BaseField::mul(&a01, &b01), &v0), &v1);
+
+        [c0, c1]
+    }
+
+    fn neg(a: &Self::BaseType) -> Self::BaseType {
+        [C::BaseField::neg(&a[0]), C::BaseField::neg(&a[1])]
+    }
+
+    fn from_u64(a: u64) -> Self::BaseType {
+        [C::BaseField::from_u64(a), C::BaseField::zero()]
+    }
+
+    fn eq(a: &Self::BaseType, b: &Self::BaseType) -> bool {
+        C::BaseField::eq(&a[0], &b[0]) && C::BaseField::eq(&a[1], &b[1])
+    }
+
+    // (a0 + a1 * u)^-1 = (a0 - a1 * u) / (a0^2 - β * a1^2)
+    fn inverse(a: &Self::BaseType) -> Option<Self::BaseType> {
+        let t = C::BaseField::inverse(&Self::norm(a))?;
+        Some([
+            C::BaseField::mul(&a[0], &t),
+            C::BaseField::neg(&C::BaseField::mul(&a[1], &t)),
+        ])
+    }
+
+    fn characteristic() -> BigUint {
+        C::BaseField::characteristic()
+    }
+
+    fn random() -> Self::BaseType {
+        [C::BaseField::random(), C::BaseField::random()]
+    }
+
+    fn zero() -> Self::BaseType {
+        [C::BaseField::zero(), C::BaseField::zero()]
+    }
+
+    fn one() -> Self::BaseType {
+        [C::BaseField::one(), C::BaseField::zero()]
+    }
+
+    // complex squaring, two base field multiplications
+    fn square(a: &Self::BaseType) -> Self::BaseType {
+        let v0 = C::BaseField::mul(&a[0], &a[1]);
+        let t0 = C::BaseField::add(&a[0], &a[1]);
+        let t1 = C::BaseField::add(&a[0], &C::mul_by_non_residue(&a[1]));
+
+        let c0 = C::BaseField::sub(
+            &C::BaseField::sub(&C::BaseField::mul(&t0, &t1), &v0),
+            &C::mul_by_non_residue(&v0),
+        );
+        let c1 = C::BaseField::double(&v0);
+
+        [c0, c1]
+    }
+
+    // (a0 + a1 * u)^q = a0^q + a1^q * u^q with u^q = β^(q / 2) * u^(q % 2)
+    fn frobenius_map(a: &Self::BaseType, power: usize) -> Self::BaseType {
+        let (coefficient, odd) = frobenius_coefficients::<Self, _>(power, || {
+            let q = frobenius_exponent::<C::BaseField>(power);
+            let coefficient = pow_biguint::<C::BaseField>(&C::non_residue(), &(&q >> 1u32));
+            (coefficient, q.bit(0))
+        });
+
+        let c0 = C::BaseField::frobenius_map(&a[0], power);
+        let c1 = C::BaseField::mul(&C::BaseField::frobenius_map(&a[1], power), &coefficient);
+
+        if odd {
+            [c0, c1]
+        } else {
+            [C::BaseField::add(&c0, &c1), C::BaseField::zero()]
+        }
+    }
+}
+
+// Elements are a0 + a1 * v + a2 * v^2 stored as [a0, a1, a2]
+#[derive(Debug)]
+pub struct CubicExtension<C>(PhantomData<C>);
+
+impl<C> CubicExtension<C>
+where
+    C: CubicExtensionConfig,
+{
+    // returns (a0 + a1 * v + a2 * v^2) * b for b in the base field
+    pub fn mul_by_base(a: &[CubicBase<C>; 3], b: &CubicBase<C>) -> [CubicBase<C>; 3] {
+        [
+            C::BaseField::mul(&a[0], b),
+            C::BaseField::mul(&a[1], b),
+            C::BaseField::mul(&a[2], b),
+        ]
+    }
+
+    // returns (a0 + a1 * v + a2 * v^2) * v = ξ * a2 + a0 * v + a1 * v^2
+    pub fn mul_by_v(a: &[CubicBase<C>; 3]) -> [CubicBase<C>; 3] {
+        [C::mul_by_non_residue(&a[2]), a[0].clone(), a[1].clone()]
+    }
+}
+
+impl<C> Field for CubicExtension<C>
+where
+    C: CubicExtensionConfig,
+{
+    type BaseType = [CubicBase<C>; 3];
+
+    fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [
+            C::BaseField::add(&a[0], &b[0]),
+            C::BaseField::add(&a[1], &b[1]),
+            C::BaseField::add(&a[2], &b[2]),
+        ]
+    }
+
+    fn sub(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        [
+            C::BaseField::sub(&a[0], &b[0]),
+            C::BaseField::sub(&a[1], &b[1]),
+            C::BaseField::sub(&a[2], &b[2]),
+        ]
+    }
+
+    // Karatsuba, six base field multiplications
+    fn mul(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType {
+        let v0 = C::BaseField::mul(&a[0], &b[0]);
+        let v1 = C::BaseField::mul(&a[1], &b[1]);
+        let v2 = C::BaseField::mul(&a[2], &b[2]);
+
+        let t12 = C::BaseField::mul(
+            &C::BaseField::add(&a[1], &a[2]),
+            &C::BaseField::add(&b[1], &b[2]),
+        );
+        let t01 = C::BaseField::mul(
+            &C::BaseField::add(&a[0], &a[1]),
+            &C::BaseField::add(&b[0], &b[1]),
+        );
+        let t02 = C::BaseField::mul(
+            &C::BaseField::add(&a[0], &a[2]),
+            &C::BaseField::add(&b[0], &b[2]),
+        );
+
+        let c0 = C::BaseField::add(
+            &v0,
+            &C::mul_by_non_residue(&C::BaseField::sub(&C::BaseField::sub(&t12, &v1), &v2)),
+        );
+        let c1 = C::BaseField::add(
+            &C::BaseField::sub(&C::BaseField::sub(&t01, &v0), &v1),
+            &C::mul_by_non_residue(&v2),
+        );
+        let c2 = C::BaseField::sub(&C::BaseField::add(&C::BaseField::sub(&t02, &v0), &v1), &v2);
+
+        [c0, c1, c2]
+    }
+
+    fn neg(a: &Self::BaseType) -> Self::BaseType {
+        [
+            C::BaseField::neg(&a[0]),
+            C::BaseField::neg(&a[1]),
+            C::BaseField::neg(&a[2]),
+        ]
+    }
+
+    fn from_u64(a: u64) -> Self::BaseType {
+        [
+            C::BaseField::from_u64(a),
+            C::BaseField::zero(),
+            C::BaseField::zero(),
+        ]
+    }
+
+    fn eq(a: &Self::BaseType, b: &Self::BaseType) -> bool {
+        C::BaseField::eq(&a[0], &b[0])
+            && C::BaseField::eq(&a[1], &b[1])
+            && C::BaseField::eq(&a[2], &b[2])
+    }
+
+    fn inverse(a: &Self::BaseType) -> Option<Self::BaseType> {
+        let t0 = C::BaseField::sub(
+            &C::BaseField::square(&a[0]),
+            &C::mul_by_non_residue(&C::BaseField::mul(&a[1], &a[2])),
+        );
+        let t1 = C::BaseField::sub(
+            &C::mul_by_non_residue(&C::BaseField::square(&a[2])),
+            &C::BaseField::mul(&a[0], &a[1]),
+        );
+        let t2 = C::BaseField::sub(
+            &C::BaseField::square(&a[1]),
+            &C::BaseField::mul(&a[0], &a[2]),
+        );
+
+        let norm = C::BaseField::add(
+            &C::BaseField::mul(&a[0], &t0),
+            &C::mul_by_non_residue(&C::BaseField::add(
+                &C::BaseField::mul(&a[2], &t1),
+                &C::BaseField::mul(&a[1], &t2),
+            )),
+        );
+        let t = C::BaseField::inverse(&norm)?;
+
+        Some([
+            C::BaseField::mul(&t0, &t),
+            C::BaseField::mul(&t1, &t),
+            C::BaseField::mul(&t2, &t),
+        ])
+    }
+
+    fn characteristic() -> BigUint {
+        C::BaseField::characteristic()
+    }
+
+    fn random() -> Self::BaseType {
+        [
+            C::BaseField::random(),
+            C::BaseField::random(),
+            C::BaseField::random(),
+        ]
+    }
+
+    fn zero() -> Self::BaseType {
+        [
+            C::BaseField::zero(),
+            C::BaseField::zero(),
+            C::BaseField::zero(),
+        ]
+    }
+
+    fn one() -> Self::BaseType {
+        [
+            C::BaseField::one(),
+            C::BaseField::zero(),
+            C::BaseField::zero(),
+        ]
+    }
+
+    // Chung-Hasan SQR2, two squarings and three multiplications
+    fn square(a: &Self::BaseType) -> Self::BaseType {
+        let s0 = C::BaseField::square(&a[0]);
+        let s1 = C::BaseField::double(&C::BaseField::mul(&a[0], &a[1]));
+        let s2 = C::BaseField::square(&C::BaseField::add(&C::BaseField::sub(&a[0], &a[1]), &a[2]));
+        let s3 = C::BaseField::double(&C::BaseField::mul(&a[1], &a[2]));
+        let s4 = C::BaseField::square(&a[2]);
+
+        let c0 = C::BaseField::add(&s0, &C::mul_by_non_residue(&s3));
+        let c1 = C::BaseField::add(&s1, &C::mul_by_non_residue(&s4));
+        let c2 = C::BaseField::sub(
+            &C::BaseField::sub(&C::BaseField::add(&C::BaseField::add(&s1, &s2), &s3), &s0),
+            &s4,
+        );
+
+        [c0, c1, c2]
+    }
+
+    // a_i^q * v^(i * q) with v^(i * q) = ξ^(i * q / 3) * v^(i * q % 3)
+    fn frobenius_map(a: &Self::BaseType, power: usize) -> Self::BaseType {
+        // (i * q % 3, ξ^(i * q / 3)) for each i
+        let coefficients = frobenius_coefficients::<Self, _>(power, || {
+            let q = frobenius_exponent::<C::BaseField>(power);
+            let three = BigUint::from(3u64);
+            (0..3u64)
+                .map(|i| {
+                    let exponent = &q * i;
+                    let shift = (&exponent % &three)
+                        .to_u64_digits()
+                        .first()
+                        .copied()
+                        .unwrap_or(0);
+                    let factor =
+                        pow_biguint::<C::BaseField>(&C::non_residue(), &(&exponent / &three));
+                    (shift as usize, factor)
+                })
+                .collect::<Vec<_>>()
+        });
+        let mut result = Self::zero();
+
+        for (coefficient, (shift, factor)) in a.iter().zip(coefficients.iter()) {
+            let term = C::BaseField::mul(&C::BaseField::frobenius_map(coefficient, power), factor);
+            result[*shift] = C::BaseField::add(&result[*shift], &term);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CubicExtension, CubicExtensionConfig, QuadraticExtension, QuadraticExtensionConfig,
+    };
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+    use cryptography::Field;
+
+    // p = 3 mod 4 so -1 is a quadratic non-residue, p = 1 mod 3 and 2 is a cubic non-residue
+    const P: u64 = 4611686018427387847;
+    type TestField = U64Field<P>;
+
+    #[derive(Debug)]
+    struct TestQuadraticConfig;
+
+    impl QuadraticExtensionConfig for TestQuadraticConfig {
+        type BaseField = TestField;
+
+        fn non_residue() -> u64 {
+            P - 1
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestCubicConfig;
+
+    impl CubicExtensionConfig for TestCubicConfig {
+        type BaseField = TestField;
+
+        fn non_residue() -> u64 {
+            2
+        }
+    }
+
+    type Quadratic = QuadraticExtension<TestQuadraticConfig>;
+    type Cubic = CubicExtension<TestCubicConfig>;
+
+    #[test]
+    fn test_quadratic_u_squared_is_non_residue() {
+        let u = FieldElement::<Quadratic>::new([0, 1]);
+
+        assert!(u.square() == FieldElement::<Quadratic>::new([P - 1, 0]));
+    }
+
+    #[test]
+    fn test_quadratic_mul_and_square() {
+        let a = FieldElement::<Quadratic>::new([3, 4]);
+        let b = FieldElement::<Quadratic>::new([5, 6]);
+
+        // (3 + 4u)(5 + 6u) = 15 - 24 + (18 + 20)u
+        assert!(&a * &b == FieldElement::<Quadratic>::new([P - 9, 38]));
+        assert!(a.square() == &a * &a);
+    }
+
+    #[test]
+    fn test_quadratic_inverse() {
+        for _ in 0..10 {
+            let a = FieldElement::<Quadratic>::random();
+            let inverse = a.inverse().unwrap();
+
+            assert!((&a * &inverse).is_one());
+        }
+        assert!(FieldElement::<Quadratic>::zero().inverse().is_none());
+    }
+
+    #[test]
+    fn test_quadratic_frobenius_is_conjugation() {
+        let a = FieldElement::<Quadratic>::random();
+
+        assert!(
+            a.frobenius_map(1) == FieldElement::<Quadratic>::new(Quadratic::conjugate(&a.value))
+        );
+        assert!(a.frobenius_map(1) == a.pow_limbs(&[P]));
+        assert!(a.frobenius_map(2) == a);
+    }
+
+    #[test]
+    fn test_cubic_v_cubed_is_non_residue() {
+        let v = FieldElement::<Cubic>::new([0, 1, 0]);
+
+        assert!(&v.square() * &v == FieldElement::<Cubic>::from(2));
+        assert!(FieldElement::<Cubic>::new(Cubic::mul_by_v(&v.value)) == v.square());
+    }
+
+    #[test]
+    fn test_cubic_mul_square_inverse() {
+        for _ in 0..10 {
+            let a = FieldElement::<Cubic>::random();
+            let b = FieldElement::<Cubic>::random();
+
+            let ab = &a * &b;
+
+            assert!(&ab * &b.inverse().unwrap() == a);
+            assert!(a.square() == &a * &a);
+            assert!((&a * &a.inverse().unwrap()).is_one());
+        }
+        assert!(FieldElement::<Cubic>::zero().inverse().is_none());
+    }
+
+    #[test]
+    fn test_cubic_frobenius() {
+        let a = FieldElement::<Cubic>::random();
+
+        assert!(a.frobenius_map(1) == a.pow_limbs(&[P]));
+        assert!(a.frobenius_map(1).frobenius_map(2) == a);
+        assert!(a.frobenius_map(3) == a);
+    }
+
+    #[test]
+    fn test_characteristic_is_inherited() {
+        assert_eq!(Quadratic::characteristic(), TestField::characteristic());
+        assert_eq!(Cubic::characteristic(), TestField::characteristic());
+    }
+}
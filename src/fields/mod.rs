@@ -1,2 +1,9 @@
+pub mod batch;
+pub mod binary;
+pub mod bytes;
 pub mod element;
+pub mod extension;
+pub mod montgomery;
+pub mod sqrt;
+pub mod tower;
 pub mod u64_field;
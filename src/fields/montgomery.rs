@@ -0,0 +1,460 @@
+use crate::fields::bytes::FieldBytes;
+use cryptography::{FftField, Field};
+use num_bigint::BigUint;
+use std::marker::PhantomData;
+
+// parameters of a prime field with an N-limb modulus
+pub trait MontgomeryConfig<const N: usize> {
+    // odd prime modulus as little-endian u64 limbs
+    const MODULUS: [u64; N];
+
+    // generator of the multiplicative group
+    const GENERATOR: u64;
+}
+
+// Elements are kept in Montgomery form a * R mod p with R = 2^(64 * N), so
+// products reduce without division
+#[derive(Debug)]
+pub struct MontgomeryField<C, const N: usize>(PhantomData<C>);
+
+// returns a + b * c + carry as (low, high)
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 * c as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+// returns a + b + carry as (sum, carry)
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+// returns a - b - borrow as (difference, borrow)
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+const fn geq<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+const fn sub_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut result = [0u64; N];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < N {
+        let (d, b) = sbb(a[i], b[i], borrow);
+        result[i] = d;
+        borrow = b;
+        i += 1;
+    }
+    (result, borrow)
+}
+
+// returns 2a mod m for a < m
+const fn double_mod<const N: usize>(a: &[u64; N], m: &[u64; N]) -> [u64; N] {
+    let mut result = [0u64; N];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < N {
+        result[i] = (a[i] << 1) | carry;
+        carry = a[i] >> 63;
+        i += 1;
+    }
+    if carry == 1 || geq(&result, m) {
+        sub_limbs(&result, m).0
+    } else {
+        result
+    }
+}
+
+// returns 2^bits mod m
+const fn power_of_two<const N: usize>(bits: usize, m: &[u64; N]) -> [u64; N] {
+    let mut result = [0u64; N];
+    result[0] = 1;
+    let mut i = 0;
+    while i < bits {
+        result = double_mod(&result, m);
+        i += 1;
+    }
+    result
+}
+
+// returns -m^-1 mod 2^64 by Newton iteration
+const fn neg_inverse(m: u64) -> u64 {
+    let mut inverse = 1u64;
+    let mut i = 0;
+    while i < 6 {
+        inverse = inverse.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(inverse)));
+        i += 1;
+    }
+    inverse.wrapping_neg()
+}
+
+impl<C, const N: usize> MontgomeryField<C, N>
+where
+    C: MontgomeryConfig<N>,
+{
+    const INV: u64 = neg_inverse(C::MODULUS[0]);
+    const R: [u64; N] = power_of_two(64 * N, &C::MODULUS);
+    const R2: [u64; N] = power_of_two(128 * N, &C::MODULUS);
+
+    // coarsely integrated operand scanning, returns a * b / R mod p
+    fn montgomery_mul(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let modulus = &C::MODULUS;
+        let mut t = [0u64; N];
+        let mut top = 0u64;
+
+        for b_i in b.iter() {
+            let mut carry = 0;
+            for (t_j, a_j) in t.iter_mut().zip(a.iter()) {
+                (*t_j, carry) = mac(*t_j, *a_j, *b_i, carry);
+            }
+            let (sum, overflow) = adc(top, carry, 0);
+
+            let m = t[0].wrapping_mul(Self::INV);
+            let (_, mut carry) = mac(t[0], m, modulus[0], 0);
+            for j in 1..N {
+                (t[j - 1], carry) = mac(t[j], m, modulus[j], carry);
+            }
+            let (sum, c) = adc(sum, carry, 0);
+            t[N - 1] = sum;
+            top = overflow + c;
+        }
+
+        if top != 0 || geq(&t, modulus) {
+            sub_limbs(&t, modulus).0
+        } else {
+            t
+        }
+    }
+
+    // canonical little-endian limbs of a
+    pub fn to_limbs(a: &[u64; N]) -> [u64; N] {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        Self::montgomery_mul(a, &one)
+    }
+
+    // None unless limbs < p
+    pub fn from_limbs(limbs: &[u64; N]) -> Option<[u64; N]> {
+        if geq(limbs, &C::MODULUS) {
+            return None;
+        }
+        Some(Self::montgomery_mul(limbs, &Self::R2))
+    }
+
+    // reduces a modulo p
+    pub fn from_biguint(a: &BigUint) -> [u64; N] {
+        let reduced = a % Self::characteristic();
+        let mut limbs = [0u64; N];
+        for (limb, digit) in limbs.iter_mut().zip(reduced.to_u64_digits()) {
+            *limb = digit;
+        }
+        Self::from_limbs(&limbs).unwrap()
+    }
+
+    pub fn to_biguint(a: &[u64; N]) -> BigUint {
+        let limbs = Self::to_limbs(a);
+        let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    // parses a decimal constant, panics on malformed input
+    pub fn from_decimal(s: &str) -> [u64; N] {
+        Self::from_biguint(&s.parse::<BigUint>().expect("invalid decimal constant"))
+    }
+
+    // parses a hexadecimal constant, panics on malformed input
+    pub fn from_hex(s: &str) -> [u64; N] {
+        let value = BigUint::parse_bytes(s.as_bytes(), 16).expect("invalid hexadecimal constant");
+        Self::from_biguint(&value)
+    }
+}
+
+impl<C, const N: usize> Field for MontgomeryField<C, N>
+where
+    C: MontgomeryConfig<N>,
+{
+    type BaseType = [u64; N];
+
+    fn add(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let mut result = [0u64; N];
+        let mut carry = 0;
+        for i in 0..N {
+            (result[i], carry) = adc(a[i], b[i], carry);
+        }
+        if carry == 1 || geq(&result, &C::MODULUS) {
+            sub_limbs(&result, &C::MODULUS).0
+        } else {
+            result
+        }
+    }
+
+    fn sub(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let (mut result, borrow) = sub_limbs(a, b);
+        if borrow == 1 {
+            let mut carry = 0;
+            for (r, m) in result.iter_mut().zip(C::MODULUS.iter()) {
+                (*r, carry) = adc(*r, *m, carry);
+            }
+        }
+        result
+    }
+
+    fn mul(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        Self::montgomery_mul(a, b)
+    }
+
+    fn neg(a: &[u64; N]) -> [u64; N] {
+        Self::sub(&[0u64; N], a)
+    }
+
+    fn from_u64(a: u64) -> [u64; N] {
+        let mut limbs = [0u64; N];
+        limbs[0] = a;
+        // a < R, so a single reduction step suffices even when a >= p
+        Self::montgomery_mul(&limbs, &Self::R2)
+    }
+
+    fn eq(a: &[u64; N], b: &[u64; N]) -> bool {
+        a == b
+    }
+
+    // Fermat, a^(p - 2)
+    fn inverse(a: &[u64; N]) -> Option<[u64; N]> {
+        if *a == [0u64; N] {
+            return None;
+        }
+        let mut two = [0u64; N];
+        two[0] = 2;
+        Some(Self::pow_limbs(a, &sub_limbs(&C::MODULUS, &two).0))
+    }
+
+    fn characteristic() -> BigUint {
+        let bytes: Vec<u8> = C::MODULUS
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    // rejection sampling over the bit length of p
+    fn random() -> [u64; N] {
+        let top = C::MODULUS[N - 1];
+        let mask = u64::MAX >> top.leading_zeros();
+        loop {
+            let mut limbs: [u64; N] = std::array::from_fn(|_| rand::random::<u64>());
+            limbs[N - 1] &= mask;
+            if let Some(element) = Self::from_limbs(&limbs) {
+                return element;
+            }
+        }
+    }
+
+    fn zero() -> [u64; N] {
+        [0u64; N]
+    }
+
+    fn one() -> [u64; N] {
+        Self::R
+    }
+}
+
+impl<C, const N: usize> FftField for MontgomeryField<C, N>
+where
+    C: MontgomeryConfig<N>,
+{
+    fn two_adicity() -> u32 {
+        // p is odd, so p - 1 only clears the lowest bit
+        let mut limbs = C::MODULUS;
+        limbs[0] -= 1;
+        let mut count = 0;
+        for limb in limbs.iter() {
+            if *limb != 0 {
+                return count + limb.trailing_zeros();
+            }
+            count += 64;
+        }
+        count
+    }
+
+    fn two_adic_root_of_unity() -> [u64; N] {
+        let exponent = (Self::characteristic() - 1u64) >> Self::two_adicity();
+        Self::pow_limbs(&Self::multiplicative_generator(), &exponent.to_u64_digits())
+    }
+
+    fn multiplicative_generator() -> [u64; N] {
+        Self::from_u64(C::GENERATOR)
+    }
+}
+
+impl<C, const N: usize> FieldBytes for MontgomeryField<C, N>
+where
+    C: MontgomeryConfig<N>,
+{
+    const ELEMENT_BYTES: usize = 8 * N;
+
+    fn to_bytes(a: &[u64; N]) -> Vec<u8> {
+        Self::to_limbs(a)
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<[u64; N]> {
+        if bytes.len() != Self::ELEMENT_BYTES {
+            return None;
+        }
+        let mut limbs = [0u64; N];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        Self::from_limbs(&limbs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MontgomeryConfig, MontgomeryField};
+    use crate::fields::bytes::FieldBytes;
+    use crate::fields::element::FieldElement;
+    use cryptography::{FftField, Field};
+    use num_bigint::BigUint;
+
+    // 2^255 - 19, the top limb leaves a single spare bit
+    #[derive(Debug)]
+    struct Config25519;
+
+    impl MontgomeryConfig<4> for Config25519 {
+        const MODULUS: [u64; 4] = [
+            0xffffffffffffffed,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0x7fffffffffffffff,
+        ];
+        const GENERATOR: u64 = 2;
+    }
+
+    // Goldilocks in a single limb, to compare against U64Field
+    #[derive(Debug)]
+    struct ConfigGoldilocks;
+
+    impl MontgomeryConfig<1> for ConfigGoldilocks {
+        const MODULUS: [u64; 1] = [18446744069414584321];
+        const GENERATOR: u64 = 7;
+    }
+
+    // the NIST P-256 prime uses every bit of the top limb
+    #[derive(Debug)]
+    struct ConfigP256;
+
+    impl MontgomeryConfig<4> for ConfigP256 {
+        const MODULUS: [u64; 4] = [
+            0xffffffffffffffff,
+            0x00000000ffffffff,
+            0x0000000000000000,
+            0xffffffff00000001,
+        ];
+        const GENERATOR: u64 = 6;
+    }
+
+    type F25519 = MontgomeryField<Config25519, 4>;
+    type Goldilocks = MontgomeryField<ConfigGoldilocks, 1>;
+    type P256 = MontgomeryField<ConfigP256, 4>;
+
+    fn check_against_biguint<F: Field<BaseType = [u64; 4]>>(to_biguint: fn(&[u64; 4]) -> BigUint) {
+        let p = F::characteristic();
+        for _ in 0..20 {
+            let a = F::random();
+            let b = F::random();
+            let (x, y) = (to_biguint(&a), to_biguint(&b));
+
+            assert!(x < p && y < p);
+            assert_eq!(to_biguint(&F::add(&a, &b)), (&x + &y) % &p);
+            assert_eq!(to_biguint(&F::sub(&a, &b)), (&x + &p - &y) % &p);
+            assert_eq!(to_biguint(&F::mul(&a, &b)), (&x * &y) % &p);
+            assert_eq!(to_biguint(&F::neg(&a)), (&p - &x) % &p);
+            let inverse = F::inverse(&a).unwrap();
+            assert_eq!(to_biguint(&F::mul(&a, &inverse)), BigUint::from(1u64));
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_matches_biguint() {
+        check_against_biguint::<F25519>(F25519::to_biguint);
+        check_against_biguint::<P256>(P256::to_biguint);
+    }
+
+    #[test]
+    fn test_edge_values() {
+        let p = P256::characteristic();
+        let minus_one = P256::from_biguint(&(&p - 1u64));
+
+        assert_eq!(
+            P256::to_biguint(&P256::mul(&minus_one, &minus_one)),
+            BigUint::from(1u64)
+        );
+        assert_eq!(P256::add(&minus_one, &P256::one()), P256::zero());
+        assert_eq!(P256::from_biguint(&p), P256::zero());
+        assert_eq!(
+            P256::to_biguint(&P256::from_u64(u64::MAX)),
+            BigUint::from(u64::MAX)
+        );
+        assert!(P256::inverse(&P256::zero()).is_none());
+        assert_eq!(
+            F25519::to_biguint(&F25519::from_decimal("19")),
+            BigUint::from(19u64)
+        );
+    }
+
+    #[test]
+    fn test_single_limb_matches_u64_field() {
+        use crate::fields::u64_field::U64Field;
+        type Reference = U64Field<18446744069414584321>;
+
+        for _ in 0..20 {
+            let a = rand::random::<u64>() % 18446744069414584321;
+            let b = rand::random::<u64>() % 18446744069414584321;
+            let x = Goldilocks::from_limbs(&[a]).unwrap();
+            let y = Goldilocks::from_limbs(&[b]).unwrap();
+
+            assert_eq!(
+                Goldilocks::to_limbs(&Goldilocks::mul(&x, &y)),
+                [Reference::mul(&a, &b)]
+            );
+            assert_eq!(
+                Goldilocks::to_limbs(&Goldilocks::sub(&x, &y)),
+                [Reference::sub(&a, &b)]
+            );
+        }
+        assert_eq!(Goldilocks::two_adicity(), 32);
+        assert_eq!(
+            Goldilocks::to_limbs(&Goldilocks::root_of_unity(8).unwrap()),
+            [Reference::root_of_unity(8).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let a = FieldElement::<F25519>::random();
+        let bytes = F25519::to_bytes(&a.value);
+
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(F25519::from_bytes(&bytes), Some(a.value));
+        assert_eq!(F25519::from_bytes(&[0xff; 32]), None);
+        assert_eq!(
+            F25519::to_bytes(&F25519::from_u64(0x0102)),
+            [vec![0; 30], vec![1, 2]].concat()
+        );
+    }
+}
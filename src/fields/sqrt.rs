@@ -0,0 +1,116 @@
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+use crate::fields::u64_field::U64Field;
+use cryptography::Field;
+use num_bigint::BigUint;
+
+pub trait SquareRoot: Field {
+    // one of the square roots of a, None for non-residues
+    fn sqrt(a: &Self::BaseType) -> Option<Self::BaseType>;
+}
+
+// Tonelli-Shanks in a field of q elements, with q - 1 = 2^s t for odd t
+pub fn tonelli_shanks<F: Field>(a: &F::BaseType, q: &BigUint) -> Option<F::BaseType> {
+    let a = FieldElement::<F>::new(a.clone());
+    if a.is_zero() {
+        return Some(a.value);
+    }
+    let order = q - 1u64;
+    let half = (&order >> 1u32).to_u64_digits();
+    let euler = |x: &FieldElement<F>| x.pow_limbs(&half);
+    if !euler(&a).is_one() {
+        return None;
+    }
+
+    let s = order.trailing_zeros().unwrap_or(0) as usize;
+    let t = &order >> s;
+    // the smallest non-residue, found within a few tries for every prime
+    let mut z = FieldElement::<F>::from(2);
+    while euler(&z).is_one() {
+        z += &FieldElement::<F>::one();
+    }
+
+    let mut m = s;
+    let mut c = z.pow_limbs(&t.to_u64_digits());
+    let mut x = a.pow_limbs(&((&t + 1u64) >> 1u32).to_u64_digits());
+    let mut b = a.pow_limbs(&t.to_u64_digits());
+    while !b.is_one() {
+        // least i with b^(2^i) = 1
+        let mut i = 0;
+        let mut power = b.clone();
+        while !power.is_one() {
+            power = power.square();
+            i += 1;
+        }
+        let mut factor = c.clone();
+        for _ in 0..m - i - 1 {
+            factor = factor.square();
+        }
+        x *= &factor;
+        c = factor.square();
+        b *= &c;
+        m = i;
+    }
+    Some(x.value)
+}
+
+impl<const MODULUS: u64> SquareRoot for U64Field<MODULUS> {
+    fn sqrt(a: &u64) -> Option<u64> {
+        tonelli_shanks::<Self>(a, &Self::characteristic())
+    }
+}
+
+impl<C, const N: usize> SquareRoot for MontgomeryField<C, N>
+where
+    C: MontgomeryConfig<N>,
+{
+    fn sqrt(a: &[u64; N]) -> Option<[u64; N]> {
+        tonelli_shanks::<Self>(a, &Self::characteristic())
+    }
+}
+
+impl<F> FieldElement<F>
+where
+    F: SquareRoot,
+{
+    pub fn sqrt(&self) -> Option<FieldElement<F>> {
+        F::sqrt(&self.value).map(FieldElement::<F>::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curves::bn254::Fq;
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+
+    #[test]
+    fn test_small_field() {
+        // 97 - 1 = 2^5 * 3 exercises the Tonelli-Shanks loop
+        type F97 = U64Field<97>;
+        let squares: Vec<u64> = (0..97).map(|x| x * x % 97).collect();
+        for a in 0..97u64 {
+            let root = FieldElement::<F97>::from(a).sqrt();
+            assert_eq!(root.is_some(), squares.contains(&a));
+            if let Some(root) = root {
+                assert!(root.square() == FieldElement::<F97>::from(a));
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_fields() {
+        for _ in 0..10 {
+            let a = FieldElement::<Goldilocks>::random();
+            assert!(a.square().sqrt().unwrap().square() == a.square());
+
+            let b = FieldElement::<Fq>::random();
+            assert!(b.square().sqrt().unwrap().square() == b.square());
+        }
+        // -1 is a non-residue modulo the BN254 base field prime, which is 3 mod 4
+        assert!((-&FieldElement::<Fq>::one()).sqrt().is_none());
+        assert!(FieldElement::<Goldilocks>::from(7).sqrt().is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::fields::extension::{
+    CubicExtension, CubicExtensionConfig, QuadraticExtension, QuadraticExtensionConfig,
+};
+use cryptography::Field;
+use std::marker::PhantomData;
+
+// Fp2 = Fp[u] / (u^2 - β), Fp6 = Fp2[v] / (v^3 - ξ), Fp12 = Fp6[w] / (w^2 - v)
+pub trait TowerConfig: 'static {
+    type Fp: Field;
+
+    // quadratic non-residue β in Fp
+    fn fp2_non_residue() -> <Self::Fp as Field>::BaseType;
+
+    // ξ in Fp2, must be neither a square nor a cube
+    fn fp6_non_residue() -> [<Self::Fp as Field>::BaseType; 2];
+}
+
+#[derive(Debug)]
+pub struct Fp2Config<T>(PhantomData<T>);
+
+#[derive(Debug)]
+pub struct Fp6Config<T>(PhantomData<T>);
+
+#[derive(Debug)]
+pub struct Fp12Config<T>(PhantomData<T>);
+
+pub type Fp2<T> = QuadraticExtension<Fp2Config<T>>;
+pub type Fp6<T> = CubicExtension<Fp6Config<T>>;
+pub type Fp12<T> = QuadraticExtension<Fp12Config<T>>;
+
+impl<T> QuadraticExtensionConfig for Fp2Config<T>
+where
+    T: TowerConfig,
+{
+    type BaseField = T::Fp;
+
+    fn non_residue() -> <T::Fp as Field>::BaseType {
+        T::fp2_non_residue()
+    }
+}
+
+impl<T> CubicExtensionConfig for Fp6Config<T>
+where
+    T: TowerConfig,
+{
+    type BaseField = Fp2<T>;
+
+    fn non_residue() -> <Fp2<T> as Field>::BaseType {
+        T::fp6_non_residue()
+    }
+}
+
+impl<T> QuadraticExtensionConfig for Fp12Config<T>
+where
+    T: TowerConfig,
+{
+    type BaseField = Fp6<T>;
+
+    // w^2 = v
+    fn non_residue() -> <Fp6<T> as Field>::BaseType {
+        [Fp2::<T>::zero(), Fp2::<T>::one(), Fp2::<T>::zero()]
+    }
+
+    fn mul_by_non_residue(a: &<Fp6<T> as Field>::BaseType) -> <Fp6<T> as Field>::BaseType {
+        Fp6::<T>::mul_by_v(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fp12, Fp2, Fp6, TowerConfig};
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+    use cryptography::Field;
+
+    // p = 3 mod 4 with ξ = 3 + u neither a square nor a cube in Fp2
+    const P: u64 = 4611686018427387847;
+
+    #[derive(Debug)]
+    struct TestTower;
+
+    impl TowerConfig for TestTower {
+        type Fp = U64Field<P>;
+
+        fn fp2_non_residue() -> u64 {
+            P - 1
+        }
+
+        fn fp6_non_residue() -> [u64; 2] {
+            [3, 1]
+        }
+    }
+
+    type TestFp2 = Fp2<TestTower>;
+    type TestFp6 = Fp6<TestTower>;
+    type TestFp12 = Fp12<TestTower>;
+
+    fn check_field_axioms<F: Field + std::fmt::Debug>() {
+        for _ in 0..5 {
+            let a = FieldElement::<F>::random();
+            let b = FieldElement::<F>::random();
+            let c = FieldElement::<F>::random();
+
+            assert!(&a + &b == &b + &a);
+            assert!(&a * &b == &b * &a);
+            assert!(&(&a + &b) + &c == &a + &(&b + &c));
+            assert!(&(&a * &b) * &c == &a * &(&b * &c));
+            assert!(&a * &(&b + &c) == &(&a * &b) + &(&a * &c));
+            assert!(&a + &FieldElement::<F>::zero() == a);
+            assert!(&a * &FieldElement::<F>::one() == a);
+            assert!((&a + &(-&a)).is_zero());
+            assert!(&(&a - &b) + &b == a);
+            assert!(a.square() == &a * &a);
+            assert!(a.double() == &a + &a);
+            assert!((&a * &a.inverse().unwrap()).is_one());
+        }
+    }
+
+    fn check_frobenius<F: Field + std::fmt::Debug>(degree: usize) {
+        let a = FieldElement::<F>::random();
+        let b = FieldElement::<F>::random();
+
+        assert!(a.frobenius_map(1) == a.pow_limbs(&[P]));
+        assert!(a.frobenius_map(degree) == a);
+        assert!((&a * &b).frobenius_map(1) == &a.frobenius_map(1) * &b.frobenius_map(1));
+        assert!((&a + &b).frobenius_map(1) == &a.frobenius_map(1) + &b.frobenius_map(1));
+        for power in 1..degree {
+            let mut expected = a.clone();
+            for _ in 0..power {
+                expected = expected.frobenius_map(1);
+            }
+            assert!(a.frobenius_map(power) == expected);
+        }
+    }
+
+    #[test]
+    fn test_fp2_axioms() {
+        check_field_axioms::<TestFp2>();
+    }
+
+    #[test]
+    fn test_fp6_axioms() {
+        check_field_axioms::<TestFp6>();
+    }
+
+    #[test]
+    fn test_fp12_axioms() {
+        check_field_axioms::<TestFp12>();
+    }
+
+    #[test]
+    fn test_fp2_frobenius() {
+        check_frobenius::<TestFp2>(2);
+    }
+
+    #[test]
+    fn test_fp6_frobenius() {
+        check_frobenius::<TestFp6>(6);
+    }
+
+    #[test]
+    fn test_fp12_frobenius() {
+        check_frobenius::<TestFp12>(12);
+    }
+
+    #[test]
+    fn test_fp12_conjugate_is_frobenius_six() {
+        let a = FieldElement::<TestFp12>::random();
+
+        assert!(FieldElement::<TestFp12>::new(TestFp12::conjugate(&a.value)) == a.frobenius_map(6));
+    }
+
+    #[test]
+    fn test_w_squared_is_v() {
+        let w = FieldElement::<TestFp12>::new([TestFp6::zero(), TestFp6::one()]);
+        let v = FieldElement::<TestFp6>::new([TestFp2::zero(), TestFp2::one(), TestFp2::zero()]);
+
+        assert!(w.square() == FieldElement::<TestFp12>::new([v.value, TestFp6::zero()]));
+        assert!(
+            &v.square() * &v
+                == FieldElement::<TestFp6>::new([[3, 1], TestFp2::zero(), TestFp2::zero()])
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use cryptography::Field;
+use cryptography::{FftField, Field, ModularArithmetic};
+use num_bigint::BigUint;
 
 #[derive(Debug)]
 pub struct U64Field<const MODULUS: u64>;
@@ -15,17 +16,17 @@ impl<const MODULUS: u64> Field for U64Field<MODULUS> {
     }
 
     fn neg(a: &u64) -> u64 {
-        MODULUS - a
+        if *a == 0 {
+            0
+        } else {
+            MODULUS - a
+        }
     }
 
     fn mul(a: &u64, b: &u64) -> u64 {
         ((*a as u128 * *b as u128) % MODULUS as u128) as u64
     }
 
-    fn pow(a: &u64, b: &u64) -> u64 {
-        ((*a as u128 ^ *b as u128) % MODULUS as u128) as u64
-    }
-
     fn from_u64(x: u64) -> u64 {
         x % MODULUS
     }
@@ -34,19 +35,241 @@ impl<const MODULUS: u64> Field for U64Field<MODULUS> {
         *a == *b
     }
 
-    fn is_prime(_a: &Self::BaseType) -> bool {
-        todo!()
+    fn inverse(a: &u64) -> Option<u64> {
+        if a.is_multiple_of(MODULUS) {
+            return None;
+        }
+        Some(Self::pow_limbs(a, &[MODULUS - 2]))
+    }
+
+    fn characteristic() -> BigUint {
+        BigUint::from(MODULUS)
+    }
+
+    fn random() -> u64 {
+        (rand::random::<u128>() % MODULUS as u128) as u64
+    }
+}
+
+impl<const MODULUS: u64> ModularArithmetic for U64Field<MODULUS> {
+    fn pow(a: &u64, b: &u64) -> u64 {
+        Self::pow_limbs(a, &[*b])
+    }
+
+    fn is_prime(a: &u64) -> bool {
+        is_prime_u64(*a)
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        gcd_u64(a, b)
+    }
+
+    fn exp(a: &u64, b: &u64, m: &u64) -> u64 {
+        pow_mod(*a, *b, *m)
+    }
+
+    fn inv(a: &u64, m: &u64) -> Option<u64> {
+        inv_mod(*a, *m)
+    }
+}
+
+impl<const MODULUS: u64> FftField for U64Field<MODULUS> {
+    fn two_adicity() -> u32 {
+        (MODULUS - 1).trailing_zeros()
+    }
+
+    fn two_adic_root_of_unity() -> u64 {
+        Self::pow_limbs(
+            &Self::multiplicative_generator(),
+            &[(MODULUS - 1) >> Self::two_adicity()],
+        )
+    }
+
+    // smallest g with g^((p - 1) / q) != 1 for every prime q dividing p - 1
+    fn multiplicative_generator() -> u64 {
+        let factors = prime_factors(MODULUS - 1);
+        (2..MODULUS)
+            .find(|g| {
+                factors
+                    .iter()
+                    .all(|q| Self::pow_limbs(g, &[(MODULUS - 1) / q]) != 1)
+            })
+            .unwrap()
+    }
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+fn pow_mod(a: u64, e: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = a % m;
+    let mut e = e;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        e >>= 1;
+    }
+    result
+}
+
+// deterministic Miller-Rabin, these bases are sufficient for every u64
+fn is_prime_u64(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let mut d = n - 1;
+    let mut r = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        r += 1;
+    }
+
+    'witness: for a in BASES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 0..r - 1 {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+
+    true
+}
+
+// Pollard's rho with Floyd cycle detection, n must be composite
+fn pollard_rho(n: u64) -> u64 {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+    for c in 1.. {
+        let f = |x: u64| (mul_mod(x, x, n) + c) % n;
+        let mut x = 2;
+        let mut y = 2;
+        let mut d = 1;
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd_u64(x.abs_diff(y), n);
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+fn gcd_u64(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd_u64(b, a % b)
+    }
+}
+
+// extended Euclid keeping the coefficient of a, None unless gcd(a, m) = 1
+fn inv_mod(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    if r0 != 1 {
+        return None;
+    }
+    Some(t0.rem_euclid(m as i128) as u64)
+}
+
+// distinct prime factors in increasing order
+fn prime_factors(n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    let mut stack = vec![n];
+
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime_u64(m) {
+            factors.push(m);
+            continue;
+        }
+        let d = pollard_rho(m);
+        stack.push(d);
+        stack.push(m / d);
     }
 
-    fn gcd(_a: Self::BaseType, _b: Self::BaseType) -> Self::BaseType {
-        todo!()
+    factors.sort();
+    factors.dedup();
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{prime_factors, U64Field};
+    use cryptography::{FftField, Field, ModularArithmetic};
+
+    type Goldilocks = U64Field<18446744069414584321>;
+
+    #[test]
+    fn test_prime_factors() {
+        assert_eq!(
+            prime_factors(18446744069414584320),
+            vec![2, 3, 5, 17, 257, 65537]
+        );
+        assert_eq!(
+            prime_factors(2 * 4611686018427387847),
+            vec![2, 4611686018427387847]
+        );
     }
 
-    fn exp(_a: &Self::BaseType, _b: &Self::BaseType, _m: &Self::BaseType) -> Self::BaseType {
-        todo!()
+    #[test]
+    fn test_modular_arithmetic() {
+        type F = U64Field<97>;
+        assert!(F::is_prime(&18446744069414584321));
+        assert!(!F::is_prime(&4294967297));
+        assert_eq!(F::gcd(84, 36), 12);
+        assert_eq!(F::exp(&3, &200, &1000), 1);
+        assert_eq!(F::inv(&3, &10), Some(7));
+        assert_eq!(F::inv(&4, &10), None);
+        assert_eq!(F::inv(&u64::MAX, &(u64::MAX - 1)), Some(1));
     }
 
-    fn inv(_a: &Self::BaseType, _m: &Self::BaseType) -> Option<Self::BaseType> {
-        todo!()
+    #[test]
+    fn test_goldilocks_roots_of_unity() {
+        assert_eq!(Goldilocks::two_adicity(), 32);
+        assert_eq!(Goldilocks::multiplicative_generator(), 7);
+
+        let root = Goldilocks::two_adic_root_of_unity();
+        assert_eq!(Goldilocks::pow_limbs(&root, &[1 << 32]), 1);
+        assert_ne!(Goldilocks::pow_limbs(&root, &[1 << 31]), 1);
+
+        let root = Goldilocks::root_of_unity(15).unwrap();
+        assert_eq!(Goldilocks::pow_limbs(&root, &[15]), 1);
+        assert_ne!(Goldilocks::pow_limbs(&root, &[5]), 1);
+        assert_ne!(Goldilocks::pow_limbs(&root, &[3]), 1);
+
+        assert!(Goldilocks::root_of_unity(7).is_none());
+        assert!(Goldilocks::root_of_unity(1 << 33).is_none());
     }
 }
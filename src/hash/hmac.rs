@@ -0,0 +1,93 @@
+use crate::hash::md5::MessageDigestAlgorithm;
+use crate::hash::sha2::{Sha256Family, Sha512Family};
+use crate::hash::sha3::Sha3;
+use cryptography::HashFunction;
+
+// hash functions with the input block size HMAC pads keys to
+pub trait BlockHash: HashFunction + Default {
+    const BLOCK_BYTES: usize;
+}
+
+impl BlockHash for MessageDigestAlgorithm {
+    const BLOCK_BYTES: usize = 64;
+}
+
+impl<const OUTPUT: usize> BlockHash for Sha256Family<OUTPUT> {
+    const BLOCK_BYTES: usize = 64;
+}
+
+impl<const OUTPUT: usize> BlockHash for Sha512Family<OUTPUT> {
+    const BLOCK_BYTES: usize = 128;
+}
+
+// the sponge rate
+impl<const OUTPUT: usize> BlockHash for Sha3<OUTPUT> {
+    const BLOCK_BYTES: usize = 200 - 2 * OUTPUT;
+}
+
+// RFC 2104, H((K ^ opad) || H((K ^ ipad) || message)), keys longer than a block
+// are hashed first
+pub fn hmac<H: BlockHash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut key = if key.len() > H::BLOCK_BYTES {
+        H::default().hash(key)
+    } else {
+        key.to_vec()
+    };
+    key.resize(H::BLOCK_BYTES, 0);
+
+    let mut inner: Vec<u8> = key.iter().map(|k| k ^ 0x36).collect();
+    inner.extend(message);
+    let mut outer: Vec<u8> = key.iter().map(|k| k ^ 0x5c).collect();
+    outer.extend(H::default().hash(&inner));
+    H::default().hash(&outer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::sha2::{Sha256, Sha384, Sha512};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // RFC 4231 test cases 1, 2 and 6
+    #[test]
+    fn test_sha2() {
+        let key = [0x0b; 20];
+        assert_eq!(
+            hex(&hmac::<Sha256>(&key, b"Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            hex(&hmac::<Sha512>(b"Jefe", b"what do ya want for nothing?")),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+        assert_eq!(
+            hex(&hmac::<Sha384>(&key, b"Hi There")),
+            "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6"
+        );
+
+        // a key longer than the block is hashed
+        let long_key = [0xaa; 131];
+        assert_eq!(
+            hex(&hmac::<Sha256>(
+                &long_key,
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    // RFC 2202 test case 2
+    #[test]
+    fn test_md5() {
+        assert_eq!(
+            hex(&hmac::<MessageDigestAlgorithm>(
+                b"Jefe",
+                b"what do ya want for nothing?"
+            )),
+            "750c783e6ab0b503eaa86e310a5db738"
+        );
+    }
+}
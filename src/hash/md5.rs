@@ -53,7 +53,6 @@ impl MessageDigestAlgorithm {
     fn round1(a: u32, b: u32, c: u32, d: u32, x: u32, i: u32) -> u32 {
         let t = Self::T[i as usize];
         let s = Self::S[0][(i % 4) as usize];
-        println!("i: {}, s: {}, t: {:x}", i, s, t);
 
         a.wrapping_add(Self::f(b, c, d))
             .wrapping_add(x)
@@ -64,7 +63,6 @@ impl MessageDigestAlgorithm {
     fn round2(a: u32, b: u32, c: u32, d: u32, x: u32, i: u32) -> u32 {
         let t = Self::T[i as usize];
         let s = Self::S[1][(i % 4) as usize];
-        println!("i: {}, s: {}, t: {:x}", i, s, t);
 
         a.wrapping_add(Self::g(b, c, d))
             .wrapping_add(x)
@@ -75,7 +73,6 @@ impl MessageDigestAlgorithm {
     fn round3(a: u32, b: u32, c: u32, d: u32, x: u32, i: u32) -> u32 {
         let t = Self::T[i as usize];
         let s = Self::S[2][(i % 4) as usize];
-        println!("i: {}, s: {}, t: {:x}", i, s, t);
 
         a.wrapping_add(Self::h(b, c, d))
             .wrapping_add(x)
@@ -86,7 +83,6 @@ impl MessageDigestAlgorithm {
     fn round4(a: u32, b: u32, c: u32, d: u32, x: u32, i: u32) -> u32 {
         let t = Self::T[i as usize];
         let s = Self::S[3][(i % 4) as usize];
-        println!("i: {}, s: {}, t: {:x}", i, s, t);
 
         a.wrapping_add(Self::i(b, c, d))
             .wrapping_add(x)
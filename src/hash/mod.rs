@@ -1 +1,4 @@
+pub mod hmac;
 pub mod md5;
+pub mod sha2;
+pub mod sha3;
@@ -0,0 +1,252 @@
+use cryptography::HashFunction;
+
+// FIPS 202
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+// rotation offsets indexed by x + 5y
+const ROTATIONS: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+// Keccak-f[1600] on lanes indexed by x + 5y
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    for round_constant in ROUND_CONSTANTS {
+        // theta
+        let mut c = [0u64; 5];
+        for x in 0..5 {
+            c[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // rho and pi, lane (x, y) moves to (y, 2x + 3y)
+        let mut b = [0u64; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                b[y + 5 * ((2 * x + 3 * y) % 5)] =
+                    state[x + 5 * y].rotate_left(ROTATIONS[x + 5 * y]);
+            }
+        }
+
+        // chi
+        for y in 0..5 {
+            for x in 0..5 {
+                state[x + 5 * y] =
+                    b[x + 5 * y] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+            }
+        }
+
+        // iota
+        state[0] ^= round_constant;
+    }
+}
+
+// sponge over Keccak-f[1600] with the given rate in bytes, absorbing and squeezing can
+// be interleaved: the first squeeze after absorbing pads with the domain byte
+pub struct KeccakSponge {
+    state: [u64; 25],
+    rate: usize,
+    domain: u8,
+    position: usize,
+    squeezing: bool,
+}
+
+impl KeccakSponge {
+    pub fn new(rate: usize, domain: u8) -> Self {
+        assert!(
+            rate > 0 && rate < 200 && rate.is_multiple_of(8),
+            "rate must be a positive multiple of 8 below 200"
+        );
+
+        Self {
+            state: [0; 25],
+            rate,
+            domain,
+            position: 0,
+            squeezing: false,
+        }
+    }
+
+    fn xor_byte(&mut self, i: usize, byte: u8) {
+        self.state[i / 8] ^= (byte as u64) << (8 * (i % 8));
+    }
+
+    fn byte(&self, i: usize) -> u8 {
+        (self.state[i / 8] >> (8 * (i % 8))) as u8
+    }
+
+    pub fn absorb(&mut self, input: &[u8]) {
+        if self.squeezing {
+            keccak_f1600(&mut self.state);
+            self.position = 0;
+            self.squeezing = false;
+        }
+
+        for byte in input {
+            self.xor_byte(self.position, *byte);
+            self.position += 1;
+            if self.position == self.rate {
+                keccak_f1600(&mut self.state);
+                self.position = 0;
+            }
+        }
+    }
+
+    pub fn squeeze(&mut self, length: usize) -> Vec<u8> {
+        if !self.squeezing {
+            // pad10*1 after the domain separation bits
+            self.xor_byte(self.position, self.domain);
+            self.xor_byte(self.rate - 1, 0x80);
+            keccak_f1600(&mut self.state);
+            self.position = 0;
+            self.squeezing = true;
+        }
+
+        let mut output = Vec::with_capacity(length);
+        while output.len() < length {
+            if self.position == self.rate {
+                keccak_f1600(&mut self.state);
+                self.position = 0;
+            }
+            output.push(self.byte(self.position));
+            self.position += 1;
+        }
+        output
+    }
+}
+
+// SHA3 with a digest of OUTPUT bytes, the capacity is twice the digest
+pub struct Sha3<const OUTPUT: usize> {}
+
+pub type Sha3_224 = Sha3<28>;
+pub type Sha3_256 = Sha3<32>;
+pub type Sha3_384 = Sha3<48>;
+pub type Sha3_512 = Sha3<64>;
+
+impl<const OUTPUT: usize> Sha3<OUTPUT> {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<const OUTPUT: usize> Default for Sha3<OUTPUT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const OUTPUT: usize> HashFunction for Sha3<OUTPUT> {
+    fn hash(&mut self, input: &[u8]) -> Vec<u8> {
+        let mut sponge = KeccakSponge::new(200 - 2 * OUTPUT, 0x06);
+        sponge.absorb(input);
+        sponge.squeeze(OUTPUT)
+    }
+}
+
+pub fn shake128(input: &[u8], length: usize) -> Vec<u8> {
+    let mut sponge = KeccakSponge::new(168, 0x1f);
+    sponge.absorb(input);
+    sponge.squeeze(length)
+}
+
+pub fn shake256(input: &[u8], length: usize) -> Vec<u8> {
+    let mut sponge = KeccakSponge::new(136, 0x1f);
+    sponge.absorb(input);
+    sponge.squeeze(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_sha3_256() {
+        assert_eq!(
+            hex(&Sha3_256::new().hash(b"")),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(
+            hex(&Sha3_256::new().hash(b"abc")),
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+        // longer than one 136 byte block
+        assert_eq!(
+            hex(&Sha3_256::new().hash(&[b'a'; 200])),
+            "cce34485baf2bf2aca99b94833892a4f52896d3d153f7b840cc4f9fe695f1387"
+        );
+    }
+
+    #[test]
+    fn test_sha3_other_sizes() {
+        assert_eq!(
+            hex(&Sha3_224::new().hash(b"abc")),
+            "e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"
+        );
+        assert_eq!(
+            hex(&Sha3_384::new().hash(b"abc")),
+            "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"
+        );
+        assert_eq!(
+            hex(&Sha3_512::new().hash(b"abc")),
+            "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"
+        );
+    }
+
+    #[test]
+    fn test_shake() {
+        assert_eq!(
+            hex(&shake128(b"", 32)),
+            "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+        );
+        assert_eq!(
+            hex(&shake256(b"abc", 64)),
+            "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4"
+        );
+    }
+
+    #[test]
+    fn test_incremental_squeeze() {
+        let mut sponge = KeccakSponge::new(168, 0x1f);
+        sponge.absorb(b"a");
+        sponge.absorb(b"bc");
+
+        let mut output = sponge.squeeze(100);
+        output.extend(sponge.squeeze(200));
+
+        assert_eq!(output, shake128(b"abc", 300));
+    }
+}
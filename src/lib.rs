@@ -1,5 +1,7 @@
+use num_bigint::BigUint;
+
 pub trait Field {
-    type BaseType: Clone;
+    type BaseType: Clone + std::fmt::Debug + Send + Sync + 'static;
 
     // returns a + b
     fn add(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType;
@@ -10,9 +12,6 @@ pub trait Field {
     // returns a * b
     fn mul(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType;
 
-    // returns a ^ b
-    fn pow(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType;
-
     // returns -a
     fn neg(a: &Self::BaseType) -> Self::BaseType;
 
@@ -22,19 +21,113 @@ pub trait Field {
     // eq
     fn eq(a: &Self::BaseType, b: &Self::BaseType) -> bool;
 
+    // multiplicative inverse in the field, None for zero
+    fn inverse(a: &Self::BaseType) -> Option<Self::BaseType>;
+
+    // characteristic of the field
+    fn characteristic() -> BigUint;
+
+    // uniformly random element
+    fn random() -> Self::BaseType;
+
+    // additive identity
+    fn zero() -> Self::BaseType {
+        Self::from_u64(0)
+    }
+
+    // multiplicative identity
+    fn one() -> Self::BaseType {
+        Self::from_u64(1)
+    }
+
+    // returns a + a
+    fn double(a: &Self::BaseType) -> Self::BaseType {
+        Self::add(a, a)
+    }
+
+    // returns a * a
+    fn square(a: &Self::BaseType) -> Self::BaseType {
+        Self::mul(a, a)
+    }
+
+    // returns a ^ e, e given as little-endian u64 limbs
+    fn pow_limbs(a: &Self::BaseType, e: &[u64]) -> Self::BaseType {
+        let mut result = Self::one();
+        for limb in e.iter().rev() {
+            for i in (0..64).rev() {
+                result = Self::square(&result);
+                if (limb >> i) & 1 == 1 {
+                    result = Self::mul(&result, a);
+                }
+            }
+        }
+        result
+    }
+
+    // returns a ^ (p ^ power) where p is the characteristic
+    fn frobenius_map(a: &Self::BaseType, _power: usize) -> Self::BaseType {
+        a.clone()
+    }
+}
+
+// integer arithmetic on the representatives of the field elements, only defined for
+// fields whose elements are integers
+pub trait ModularArithmetic: Field {
+    // returns a ^ b, the exponent taken as an integer
+    fn pow(a: &Self::BaseType, b: &Self::BaseType) -> Self::BaseType;
+
     // is prime
     fn is_prime(a: &Self::BaseType) -> bool;
 
     // gcd
     fn gcd(a: Self::BaseType, b: Self::BaseType) -> Self::BaseType;
 
-    // modular exponentiation
+    // modular exponentiation, m must be nonzero
     fn exp(a: &Self::BaseType, b: &Self::BaseType, m: &Self::BaseType) -> Self::BaseType;
 
-    // modular inverse
+    // modular inverse, None when a and m are not coprime
     fn inv(a: &Self::BaseType, m: &Self::BaseType) -> Option<Self::BaseType>;
 }
 
+pub trait FftField: Field {
+    // largest s such that 2^s divides p - 1
+    fn two_adicity() -> u32;
+
+    // primitive (2^two_adicity)-th root of unity
+    fn two_adic_root_of_unity() -> Self::BaseType;
+
+    // generator of the multiplicative group
+    fn multiplicative_generator() -> Self::BaseType;
+
+    // primitive n-th root of unity, None when n does not divide p - 1
+    fn root_of_unity(n: u64) -> Option<Self::BaseType> {
+        if n == 0 {
+            return None;
+        }
+        if n.is_power_of_two() {
+            let log_n = n.trailing_zeros();
+            if log_n > Self::two_adicity() {
+                return None;
+            }
+            let mut root = Self::two_adic_root_of_unity();
+            for _ in log_n..Self::two_adicity() {
+                root = Self::square(&root);
+            }
+            return Some(root);
+        }
+
+        let order = Self::characteristic() - BigUint::from(1u64);
+        if &order % n != BigUint::from(0u64) {
+            return None;
+        }
+        let exponent = order / n;
+        Some(Self::pow_limbs(
+            &Self::multiplicative_generator(),
+            &exponent.to_u64_digits(),
+        ))
+    }
+}
+
 pub trait Cipher {
     fn encrypt(&mut self, input: &[u8]) -> Vec<u8>;
 
@@ -1,9 +1,14 @@
-// pub mod arithmetization;
+pub mod arithmetization;
 pub mod ciphers;
+pub mod coding;
+pub mod commitments;
+pub mod curves;
 pub mod fields;
 pub mod hash;
 pub mod pk;
 pub mod polynomial;
 pub mod primes;
+pub mod protocols;
+pub mod sharing;
 
 fn main() {}
@@ -0,0 +1,295 @@
+use crate::curves::secp256k1::{Affine, Fq, Fr, Point};
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::hash::sha2::Sha256;
+use cryptography::HashFunction;
+use num_bigint::BigUint;
+
+// window for the scalar multiplications
+const WINDOW: usize = 5;
+
+type Scalar = FieldElement<Fr>;
+
+// SHA256(SHA256(tag) || SHA256(tag) || message), a hash per purpose
+pub fn tagged_hash(tag: &str, message: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::new().hash(tag.as_bytes());
+    let data = [tag_hash.as_slice(), &tag_hash, message].concat();
+    Sha256::new().hash(&data).try_into().unwrap()
+}
+
+fn mul(point: &Point, scalar: &Scalar) -> Point {
+    point.mul_wnaf(&Fr::to_limbs(&scalar.value), WINDOW)
+}
+
+// 32 big-endian bytes reduced modulo n
+fn scalar_from_hash(hash: &[u8; 32]) -> Scalar {
+    FieldElement::new(Fr::from_biguint(&BigUint::from_bytes_be(hash)))
+}
+
+fn x_bytes(point: &Affine) -> [u8; 32] {
+    Fq::to_bytes(&point.x.value).try_into().unwrap()
+}
+
+fn has_even_y(point: &Affine) -> bool {
+    Fq::to_bytes(&point.y.value)[31] & 1 == 0
+}
+
+// the point with the given x coordinate and even y, None unless x < p is on the curve
+fn lift_x(bytes: &[u8; 32]) -> Option<Affine> {
+    let x = FieldElement::<Fq>::new(Fq::from_bytes(bytes)?);
+    let y = (&(&x.square() * &x) + &FieldElement::from(7)).sqrt()?;
+    let point = Affine::new(x, y);
+    if has_even_y(&point) {
+        Some(point)
+    } else {
+        Some(-&point)
+    }
+}
+
+// the secret key as a scalar in [1, n - 1]
+fn secret_scalar(secret: &[u8; 32]) -> Result<Scalar, &'static str> {
+    match Fr::from_bytes(secret).map(FieldElement::<Fr>::new) {
+        Some(d) if !d.is_zero() => Ok(d),
+        _ => Err("secret key must be in [1, n - 1]"),
+    }
+}
+
+fn challenge(r: &[u8], public: &[u8; 32], message: &[u8]) -> Scalar {
+    scalar_from_hash(&tagged_hash(
+        "BIP0340/challenge",
+        &[r, public.as_slice(), message].concat(),
+    ))
+}
+
+// the x-only public key, the x coordinate of dG
+pub fn public_key(secret: &[u8; 32]) -> Result<[u8; 32], &'static str> {
+    let d = secret_scalar(secret)?;
+    Ok(x_bytes(&mul(&Point::generator(), &d).to_affine()))
+}
+
+// BIP-340 default signing. The secret is negated as needed so that P has even y,
+// the nonce mixes the secret masked by aux_rand with P and the message, and the
+// signature is checked before it is released.
+pub fn sign(
+    secret: &[u8; 32],
+    message: &[u8],
+    aux_rand: &[u8; 32],
+) -> Result<[u8; 64], &'static str> {
+    let d = secret_scalar(secret)?;
+    let public = mul(&Point::generator(), &d).to_affine();
+    let d = if has_even_y(&public) { d } else { -&d };
+    let public = x_bytes(&public);
+
+    let mask = tagged_hash("BIP0340/aux", aux_rand);
+    let t: Vec<u8> = Fr::to_bytes(&d.value)
+        .iter()
+        .zip(mask.iter())
+        .map(|(a, b)| a ^ b)
+        .collect();
+    let rand = tagged_hash("BIP0340/nonce", &[t.as_slice(), &public, message].concat());
+    let k = scalar_from_hash(&rand);
+    if k.is_zero() {
+        return Err("nonce is zero");
+    }
+
+    let r = mul(&Point::generator(), &k).to_affine();
+    let k = if has_even_y(&r) { k } else { -&k };
+    let r = x_bytes(&r);
+    let e = challenge(&r, &public, message);
+    let s = &k + &(&e * &d);
+
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r);
+    signature[32..].copy_from_slice(&Fr::to_bytes(&s.value));
+    if !verify(&public, message, &signature) {
+        return Err("signature failed to verify");
+    }
+    Ok(signature)
+}
+
+// a signature split into r < p, s < n and the challenge e
+struct Parsed {
+    public: Affine,
+    r: [u8; 32],
+    s: Scalar,
+    e: Scalar,
+}
+
+fn parse(public: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Option<Parsed> {
+    let point = lift_x(public)?;
+    let r: [u8; 32] = signature[..32].try_into().unwrap();
+    Fq::from_bytes(&r)?;
+    let s = FieldElement::new(Fr::from_bytes(&signature[32..])?);
+    let e = challenge(&r, public, message);
+    Some(Parsed {
+        public: point,
+        r,
+        s,
+        e,
+    })
+}
+
+// R = sG - eP must be finite with even y and x(R) = r
+pub fn verify(public: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let parsed = match parse(public, message, signature) {
+        Some(parsed) => parsed,
+        None => return false,
+    };
+    let r = &mul(&Point::generator(), &parsed.s) - &mul(&parsed.public.to_jacobian(), &parsed.e);
+    if r.is_identity() {
+        return false;
+    }
+    let r = r.to_affine();
+    has_even_y(&r) && x_bytes(&r) == parsed.r
+}
+
+// BIP-340 batch verification with a_1 = 1 and random a_i:
+// [s_1 + a_2 s_2 + ...]G = R_1 + [a_2]R_2 + ... + [e_1]P_1 + [a_2 e_2]P_2 + ...
+// where R_i is lifted from r_i
+pub fn verify_batch(items: &[(&[u8; 32], &[u8], &[u8; 64])]) -> bool {
+    let mut s_sum = Scalar::zero();
+    let mut sum = Point::identity();
+    for (i, (public, message, signature)) in items.iter().enumerate() {
+        let parsed = match parse(public, message, signature) {
+            Some(parsed) => parsed,
+            None => return false,
+        };
+        let r = match lift_x(&parsed.r) {
+            Some(r) => r,
+            None => return false,
+        };
+        let a = if i == 0 {
+            Scalar::one()
+        } else {
+            Scalar::random()
+        };
+        s_sum += &(&a * &parsed.s);
+        sum = &sum + &mul(&r.to_jacobian(), &a);
+        sum = &sum + &mul(&parsed.public.to_jacobian(), &(&a * &parsed.e));
+    }
+    mul(&Point::generator(), &s_sum) == sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cryptography::Field;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    struct Vector {
+        index: usize,
+        secret: Option<[u8; 32]>,
+        public: Vec<u8>,
+        aux_rand: Option<[u8; 32]>,
+        message: Vec<u8>,
+        signature: [u8; 64],
+        valid: bool,
+    }
+
+    // the test vectors published with BIP-340
+    fn vectors() -> Vec<Vector> {
+        include_str!("../../testdata/bip340/test-vectors.csv")
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let fields: Vec<&str> = line.trim_end().split(',').collect();
+                let optional = |s: &str| (!s.is_empty()).then(|| from_hex(s).try_into().unwrap());
+                Vector {
+                    index: fields[0].parse().unwrap(),
+                    secret: optional(fields[1]),
+                    public: from_hex(fields[2]),
+                    aux_rand: optional(fields[3]),
+                    message: from_hex(fields[4]),
+                    signature: from_hex(fields[5]).try_into().unwrap(),
+                    valid: fields[6] == "TRUE",
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_vectors() {
+        let vectors = vectors();
+        assert_eq!(vectors.len(), 19);
+        for vector in &vectors {
+            let public: [u8; 32] = vector.public.clone().try_into().unwrap();
+            if let (Some(secret), Some(aux_rand)) = (&vector.secret, &vector.aux_rand) {
+                assert_eq!(
+                    public_key(secret).unwrap(),
+                    public,
+                    "vector {}",
+                    vector.index
+                );
+                assert_eq!(
+                    sign(secret, &vector.message, aux_rand).unwrap(),
+                    vector.signature,
+                    "vector {}",
+                    vector.index
+                );
+            }
+            assert_eq!(
+                verify(&public, &vector.message, &vector.signature),
+                vector.valid,
+                "vector {}",
+                vector.index
+            );
+            assert_eq!(
+                verify_batch(&[(&public, &vector.message, &vector.signature)]),
+                vector.valid,
+                "vector {}",
+                vector.index
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch() {
+        let vectors = vectors();
+        let publics: Vec<[u8; 32]> = vectors
+            .iter()
+            .map(|v| v.public.clone().try_into().unwrap())
+            .collect();
+        let mut items: Vec<(&[u8; 32], &[u8], &[u8; 64])> = vectors
+            .iter()
+            .zip(publics.iter())
+            .filter(|(v, _)| v.valid)
+            .map(|(v, public)| (public, v.message.as_slice(), &v.signature))
+            .collect();
+        assert!(verify_batch(&items));
+        assert!(verify_batch(&[]));
+
+        // any invalid entry fails the whole batch
+        for (v, public) in vectors.iter().zip(publics.iter()).filter(|(v, _)| !v.valid) {
+            items.push((public, v.message.as_slice(), &v.signature));
+            assert!(!verify_batch(&items), "vector {}", v.index);
+            items.pop();
+        }
+    }
+
+    #[test]
+    fn test_keys() {
+        assert!(public_key(&[0; 32]).is_err());
+        // n itself is out of range
+        let n: [u8; 32] = Fr::characteristic().to_bytes_be().try_into().unwrap();
+        assert!(public_key(&n).is_err());
+        assert!(sign(&n, b"message", &[0; 32]).is_err());
+
+        let secret: [u8; 32] = std::array::from_fn(|i| i as u8 + 1);
+        let public = public_key(&secret).unwrap();
+        let signature = sign(&secret, b"message", &[7; 32]).unwrap();
+        assert!(verify(&public, b"message", &signature));
+        assert!(!verify(&public, b"massage", &signature));
+
+        // aux_rand changes the nonce but not the validity
+        let other = sign(&secret, b"message", &[8; 32]).unwrap();
+        assert_ne!(signature, other);
+        assert!(verify(&public, b"message", &other));
+    }
+}
@@ -0,0 +1,681 @@
+use crate::curves::weierstrass::{AffinePoint, JacobianPoint, WeierstrassConfig};
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::fields::sqrt::SquareRoot;
+use crate::hash::hmac::{hmac, BlockHash};
+use cryptography::{Field, HashFunction};
+use num_bigint::BigUint;
+use std::marker::PhantomData;
+
+// window for the fixed and variable base multiplications
+const WINDOW: usize = 5;
+
+type Scalar<C> = FieldElement<<C as WeierstrassConfig>::ScalarField>;
+
+fn scalar_to_biguint<C: WeierstrassConfig>(a: &Scalar<C>) -> BigUint
+where
+    C::ScalarField: FieldBytes,
+{
+    BigUint::from_bytes_be(&C::ScalarField::to_bytes(&a.value))
+}
+
+// reduces a modulo the order
+fn scalar_from_biguint<C: WeierstrassConfig>(a: &BigUint) -> Scalar<C>
+where
+    C::ScalarField: FieldBytes,
+{
+    let reduced = (a % C::order()).to_bytes_be();
+    let mut bytes = vec![0u8; C::ScalarField::ELEMENT_BYTES - reduced.len()];
+    bytes.extend(reduced);
+    FieldElement::new(C::ScalarField::from_bytes(&bytes).unwrap())
+}
+
+fn mul<C: WeierstrassConfig>(point: &JacobianPoint<C>, scalar: &Scalar<C>) -> JacobianPoint<C>
+where
+    C::ScalarField: FieldBytes,
+{
+    point.mul_wnaf(&scalar_to_biguint::<C>(scalar).to_u64_digits(), WINDOW)
+}
+
+fn base_to_biguint<C: WeierstrassConfig>(a: &FieldElement<C::BaseField>) -> BigUint
+where
+    C::BaseField: FieldBytes,
+{
+    BigUint::from_bytes_be(&C::BaseField::to_bytes(&a.value))
+}
+
+// byte length of the order
+fn order_bytes<C: WeierstrassConfig>() -> usize {
+    (C::order().bits() as usize).div_ceil(8)
+}
+
+// big-endian, left padded to length
+fn int_to_octets(a: &BigUint, length: usize) -> Vec<u8> {
+    let bytes = a.to_bytes_be();
+    let mut padded = vec![0u8; length.saturating_sub(bytes.len())];
+    padded.extend(bytes);
+    padded
+}
+
+// the leftmost bits of the digest, as many as the order has
+fn bits_to_int<C: WeierstrassConfig>(digest: &[u8]) -> BigUint {
+    let value = BigUint::from_bytes_be(digest);
+    let bits = 8 * digest.len() as u64;
+    let qlen = C::order().bits();
+    if bits > qlen {
+        value >> (bits - qlen)
+    } else {
+        value
+    }
+}
+
+// RFC 6979 section 3.2, a stream of nonces determined by the key and the digest
+struct Rfc6979<C: WeierstrassConfig, H: BlockHash> {
+    k: Vec<u8>,
+    v: Vec<u8>,
+    started: bool,
+    _marker: PhantomData<(C, H)>,
+}
+
+impl<C, H> Rfc6979<C, H>
+where
+    C: WeierstrassConfig,
+    C::ScalarField: FieldBytes,
+    H: BlockHash,
+{
+    fn new(secret: &Scalar<C>, digest: &[u8]) -> Self {
+        let length = order_bytes::<C>();
+        let x = int_to_octets(&scalar_to_biguint::<C>(secret), length);
+        let h = int_to_octets(&(bits_to_int::<C>(digest) % C::order()), length);
+
+        let hlen = H::default().hash(&[]).len();
+        let mut v = vec![1u8; hlen];
+        let mut k = vec![0u8; hlen];
+        for separator in [0u8, 1] {
+            let mut data = v.clone();
+            data.push(separator);
+            data.extend(&x);
+            data.extend(&h);
+            k = hmac::<H>(&k, &data);
+            v = hmac::<H>(&k, &v);
+        }
+        Self {
+            k,
+            v,
+            started: false,
+            _marker: PhantomData,
+        }
+    }
+
+    fn reseed(&mut self) {
+        let mut data = self.v.clone();
+        data.push(0);
+        self.k = hmac::<H>(&self.k, &data);
+        self.v = hmac::<H>(&self.k, &self.v);
+    }
+
+    fn next(&mut self) -> Scalar<C> {
+        if self.started {
+            self.reseed();
+        }
+        self.started = true;
+
+        let length = order_bytes::<C>();
+        loop {
+            let mut t = Vec::new();
+            while t.len() < length {
+                self.v = hmac::<H>(&self.k, &self.v);
+                t.extend(&self.v);
+            }
+            let k = bits_to_int::<C>(&t);
+            if k.bits() > 0 && k < C::order() {
+                return scalar_from_biguint::<C>(&k);
+            }
+            self.reseed();
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Signature<C: WeierstrassConfig> {
+    pub r: FieldElement<C::ScalarField>,
+    pub s: FieldElement<C::ScalarField>,
+}
+
+impl<C> Clone for Signature<C>
+where
+    C: WeierstrassConfig,
+{
+    fn clone(&self) -> Self {
+        Self {
+            r: self.r.clone(),
+            s: self.s.clone(),
+        }
+    }
+}
+
+impl<C> PartialEq for Signature<C>
+where
+    C: WeierstrassConfig,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.r == other.r && self.s == other.s
+    }
+}
+
+// DER length, short form below 128
+fn der_length(length: usize) -> Vec<u8> {
+    if length < 128 {
+        vec![length as u8]
+    } else {
+        let bytes: Vec<u8> = length
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|b| *b == 0)
+            .collect();
+        let mut encoded = vec![0x80 | bytes.len() as u8];
+        encoded.extend(bytes);
+        encoded
+    }
+}
+
+// minimal big-endian with a zero byte in front of a set top bit
+fn der_integer(a: &BigUint) -> Vec<u8> {
+    let mut bytes = a.to_bytes_be();
+    if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+    let mut encoded = vec![0x02];
+    encoded.extend(der_length(bytes.len()));
+    encoded.extend(bytes);
+    encoded
+}
+
+// reads a tag and a minimally encoded length, returning the contents and the rest
+fn der_read(bytes: &[u8], tag: u8) -> Result<(&[u8], &[u8]), &'static str> {
+    if bytes.len() < 2 || bytes[0] != tag {
+        return Err("unexpected DER tag");
+    }
+    let (length, header) = match bytes[1] {
+        length if length < 0x80 => (length as usize, 2),
+        0x81 => match bytes.get(2) {
+            Some(length) if *length >= 0x80 => (*length as usize, 3),
+            _ => return Err("non-minimal DER length"),
+        },
+        _ => return Err("unsupported DER length"),
+    };
+    if bytes.len() < header + length {
+        return Err("truncated DER");
+    }
+    Ok((&bytes[header..header + length], &bytes[header + length..]))
+}
+
+impl<C> Signature<C>
+where
+    C: WeierstrassConfig,
+    C::ScalarField: FieldBytes,
+{
+    // r || s, each as long as the order
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let length = order_bytes::<C>();
+        let mut bytes = int_to_octets(&scalar_to_biguint::<C>(&self.r), length);
+        bytes.extend(int_to_octets(&scalar_to_biguint::<C>(&self.s), length));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let length = order_bytes::<C>();
+        if bytes.len() != 2 * length {
+            return Err("invalid signature length");
+        }
+        Self::from_integers(
+            &BigUint::from_bytes_be(&bytes[..length]),
+            &BigUint::from_bytes_be(&bytes[length..]),
+        )
+    }
+
+    // SEQUENCE { INTEGER r, INTEGER s }
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut contents = der_integer(&scalar_to_biguint::<C>(&self.r));
+        contents.extend(der_integer(&scalar_to_biguint::<C>(&self.s)));
+        let mut encoded = vec![0x30];
+        encoded.extend(der_length(contents.len()));
+        encoded.extend(contents);
+        encoded
+    }
+
+    // strict DER, rejects alternative encodings of the same signature
+    pub fn from_der(bytes: &[u8]) -> Result<Self, &'static str> {
+        let (contents, rest) = der_read(bytes, 0x30)?;
+        if !rest.is_empty() {
+            return Err("trailing bytes after DER signature");
+        }
+        let (r, contents) = der_read(contents, 0x02)?;
+        let (s, contents) = der_read(contents, 0x02)?;
+        if !contents.is_empty() {
+            return Err("trailing bytes in DER sequence");
+        }
+
+        let integer = |bytes: &[u8]| match bytes {
+            [] => Err("empty DER integer"),
+            [first, ..] if first & 0x80 != 0 => Err("negative DER integer"),
+            [0, second, ..] if second & 0x80 == 0 => Err("non-minimal DER integer"),
+            _ => Ok(BigUint::from_bytes_be(bytes)),
+        };
+        Self::from_integers(&integer(r)?, &integer(s)?)
+    }
+
+    // both in [1, n)
+    fn from_integers(r: &BigUint, s: &BigUint) -> Result<Self, &'static str> {
+        let n = C::order();
+        if r.bits() == 0 || s.bits() == 0 || r >= &n || s >= &n {
+            return Err("signature values out of range");
+        }
+        Ok(Self {
+            r: scalar_from_biguint::<C>(r),
+            s: scalar_from_biguint::<C>(s),
+        })
+    }
+
+    // s <= n / 2, the form that rules out malleating s into n - s
+    pub fn is_low_s(&self) -> bool {
+        scalar_to_biguint::<C>(&self.s) <= C::order() >> 1
+    }
+
+    pub fn normalize_s(&self) -> Self {
+        if self.is_low_s() {
+            self.clone()
+        } else {
+            Self {
+                r: self.r.clone(),
+                s: -&self.s,
+            }
+        }
+    }
+}
+
+pub struct SigningKey<C: WeierstrassConfig> {
+    secret: Scalar<C>,
+    public: AffinePoint<C>,
+}
+
+impl<C> SigningKey<C>
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes,
+    C::ScalarField: FieldBytes,
+{
+    pub fn new(secret: Scalar<C>) -> Result<Self, &'static str> {
+        if secret.is_zero() {
+            return Err("secret key must be non-zero");
+        }
+        let public = mul(&JacobianPoint::<C>::generator(), &secret).to_affine();
+        Ok(Self { secret, public })
+    }
+
+    pub fn random() -> Self {
+        loop {
+            if let Ok(key) = Self::new(FieldElement::random()) {
+                return key;
+            }
+        }
+    }
+
+    // big-endian, as long as the order
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != order_bytes::<C>() {
+            return Err("invalid secret key length");
+        }
+        let secret = BigUint::from_bytes_be(bytes);
+        if secret >= C::order() {
+            return Err("secret key out of range");
+        }
+        Self::new(scalar_from_biguint::<C>(&secret))
+    }
+
+    pub fn public_key(&self) -> &AffinePoint<C> {
+        &self.public
+    }
+
+    pub fn sign<H: BlockHash>(&self, message: &[u8]) -> Signature<C> {
+        self.sign_prehashed::<H>(&H::default().hash(message))
+    }
+
+    // deterministic nonces from HMAC with H as in RFC 6979
+    pub fn sign_prehashed<H: BlockHash>(&self, digest: &[u8]) -> Signature<C> {
+        self.sign_with_recovery_id::<H>(digest).0
+    }
+
+    // low-s signature and the recovery id: the parity of the y coordinate of kG,
+    // plus 2 when its x coordinate is at least n
+    pub fn sign_recoverable<H: BlockHash>(&self, digest: &[u8]) -> (Signature<C>, u8) {
+        let (signature, recovery_id) = self.sign_with_recovery_id::<H>(digest);
+        if signature.is_low_s() {
+            (signature, recovery_id)
+        } else {
+            (signature.normalize_s(), recovery_id ^ 1)
+        }
+    }
+
+    fn sign_with_recovery_id<H: BlockHash>(&self, digest: &[u8]) -> (Signature<C>, u8) {
+        let e = scalar_from_biguint::<C>(&bits_to_int::<C>(digest));
+        let mut nonces = Rfc6979::<C, H>::new(&self.secret, digest);
+        loop {
+            let k = nonces.next();
+            let point = mul(&JacobianPoint::<C>::generator(), &k).to_affine();
+            let x = base_to_biguint::<C>(&point.x);
+            let r = scalar_from_biguint::<C>(&x);
+            if r.is_zero() {
+                continue;
+            }
+            let s = &k.inverse().unwrap() * &(&e + &(&r * &self.secret));
+            if s.is_zero() {
+                continue;
+            }
+
+            let parity = C::BaseField::to_bytes(&point.y.value).last().unwrap() & 1;
+            let overflow = u8::from(x >= C::order()) << 1;
+            return (Signature { r, s }, parity | overflow);
+        }
+    }
+}
+
+pub fn verify<C, H>(public: &AffinePoint<C>, message: &[u8], signature: &Signature<C>) -> bool
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes,
+    C::ScalarField: FieldBytes,
+    H: HashFunction + Default,
+{
+    verify_prehashed(public, &H::default().hash(message), signature)
+}
+
+// x([e / s]G + [r / s]Q) = r mod n
+pub fn verify_prehashed<C>(public: &AffinePoint<C>, digest: &[u8], signature: &Signature<C>) -> bool
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes,
+    C::ScalarField: FieldBytes,
+{
+    if public.is_identity() || !public.is_on_curve() {
+        return false;
+    }
+    let s_inv = match signature.s.inverse() {
+        Some(s_inv) => s_inv,
+        None => return false,
+    };
+    if signature.r.is_zero() {
+        return false;
+    }
+
+    let e = scalar_from_biguint::<C>(&bits_to_int::<C>(digest));
+    let u1 = &e * &s_inv;
+    let u2 = &signature.r * &s_inv;
+    let point = &mul(&JacobianPoint::<C>::generator(), &u1) + &mul(&public.to_jacobian(), &u2);
+    if point.is_identity() {
+        return false;
+    }
+    scalar_from_biguint::<C>(&base_to_biguint::<C>(&point.to_affine().x)) == signature.r
+}
+
+// the public key that produced a signature with the given recovery id,
+// Q = [1 / r](sR - eG)
+pub fn recover<C>(
+    digest: &[u8],
+    signature: &Signature<C>,
+    recovery_id: u8,
+) -> Result<AffinePoint<C>, &'static str>
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes + SquareRoot,
+    C::ScalarField: FieldBytes,
+{
+    if recovery_id > 3 {
+        return Err("invalid recovery id");
+    }
+    let mut x = scalar_to_biguint::<C>(&signature.r);
+    if recovery_id & 2 != 0 {
+        x += C::order();
+    }
+    if x >= C::BaseField::characteristic() {
+        return Err("invalid recovery id");
+    }
+
+    let mut encoded = vec![2 + (recovery_id & 1)];
+    encoded.extend(int_to_octets(&x, C::BaseField::ELEMENT_BYTES));
+    let point = AffinePoint::<C>::from_bytes(&encoded)?.to_jacobian();
+
+    let r_inv = signature.r.inverse().ok_or("invalid signature")?;
+    let e = scalar_from_biguint::<C>(&bits_to_int::<C>(digest));
+    let public = &mul(&point, &signature.s) - &mul(&JacobianPoint::<C>::generator(), &e);
+    let public = mul(&public, &r_inv);
+    if public.is_identity() {
+        return Err("recovered the identity");
+    }
+    Ok(public.to_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::p256::P256;
+    use crate::curves::p384::P384;
+    use crate::curves::secp256k1::Secp256k1;
+    use crate::hash::sha2::{Sha256, Sha384, Sha512};
+    use std::path::PathBuf;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn key<C>(secret: &str) -> SigningKey<C>
+    where
+        C: WeierstrassConfig,
+        C::BaseField: FieldBytes,
+        C::ScalarField: FieldBytes,
+    {
+        SigningKey::from_bytes(&from_hex(secret)).unwrap()
+    }
+
+    // RFC 6979 A.2.5
+    #[test]
+    fn test_rfc6979_p256() {
+        let key = key::<P256>("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+        assert_eq!(
+            key.public_key().to_uncompressed(),
+            from_hex(
+                "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6\
+                 7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+            )
+        );
+
+        let signature = key.sign::<Sha256>(b"sample");
+        assert_eq!(
+            signature.to_bytes(),
+            from_hex(
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+                 f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+            )
+        );
+        assert!(verify::<_, Sha256>(key.public_key(), b"sample", &signature));
+        assert!(!verify::<_, Sha256>(
+            key.public_key(),
+            b"samplf",
+            &signature
+        ));
+
+        let signature = key.sign::<Sha256>(b"test");
+        assert_eq!(
+            signature.to_bytes(),
+            from_hex(
+                "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367\
+                 019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"
+            )
+        );
+    }
+
+    // RFC 6979 A.2.6
+    #[test]
+    fn test_rfc6979_p384() {
+        let key = key::<P384>(
+            "6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d8\
+             96d5724e4c70a825f872c9ea60d2edf5",
+        );
+        let signature = key.sign::<Sha384>(b"sample");
+        assert_eq!(
+            signature.to_bytes(),
+            from_hex(
+                "94edbb92a5ecb8aad4736e56c691916b3f88140666ce9fa73d64c4ea95ad133c\
+                 81a648152e44acf96e36dd1e80fabe46\
+                 99ef4aeb15f178cea1fe40db2603138f130e740a19624526203b6351d0a3a94f\
+                 a329c145786e679e7b82c71a38628ac8"
+            )
+        );
+        assert!(verify::<_, Sha384>(key.public_key(), b"sample", &signature));
+    }
+
+    #[test]
+    fn test_secp256k1_low_s_and_recovery() {
+        let key =
+            key::<Secp256k1>("0000000000000000000000000000000000000000000000000000000000000001");
+        let digest = Sha256::new().hash(b"Satoshi Nakamoto");
+
+        // the raw signature has a high s
+        let signature = key.sign_prehashed::<Sha256>(&digest);
+        assert!(!signature.is_low_s());
+        let (low, recovery_id) = key.sign_recoverable::<Sha256>(&digest);
+        assert!(low.is_low_s() && low == signature.normalize_s());
+        assert_eq!(
+            low.to_bytes(),
+            from_hex(
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+                 2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"
+            )
+        );
+        assert!(verify_prehashed(key.public_key(), &digest, &low));
+        assert!(verify_prehashed(key.public_key(), &digest, &signature));
+
+        assert!(recover(&digest, &low, recovery_id).unwrap() == *key.public_key());
+        assert!(recover(&digest, &low, recovery_id ^ 1).unwrap() != *key.public_key());
+
+        for i in 0..5u64 {
+            let key = SigningKey::<Secp256k1>::random();
+            let digest = Sha256::new().hash(&i.to_be_bytes());
+            let (signature, recovery_id) = key.sign_recoverable::<Sha256>(&digest);
+            assert!(recover(&digest, &signature, recovery_id).unwrap() == *key.public_key());
+        }
+    }
+
+    #[test]
+    fn test_encodings() {
+        let key = SigningKey::<P256>::random();
+        let signature = key.sign::<Sha256>(b"message");
+
+        let der = signature.to_der();
+        assert!(Signature::<P256>::from_der(&der).unwrap() == signature);
+        assert!(Signature::<P256>::from_bytes(&signature.to_bytes()).unwrap() == signature);
+
+        // trailing data, a wrong length and a padded integer are all rejected
+        let mut trailing = der.clone();
+        trailing.push(0);
+        assert!(Signature::<P256>::from_der(&trailing).is_err());
+        let mut long = der.clone();
+        long[1] += 1;
+        assert!(Signature::<P256>::from_der(&long).is_err());
+        let padded = [&[0x30, der[1] + 1, 0x02, der[3] + 1, 0][..], &der[4..]].concat();
+        assert!(Signature::<P256>::from_der(&padded).is_err());
+
+        // r = 0 and s = n are out of range
+        let n = P256::order().to_bytes_be();
+        let zero = [vec![0u8; 32], n.clone()].concat();
+        assert!(Signature::<P256>::from_bytes(&zero).is_err());
+        let high = [n.clone(), vec![1u8; 32]].concat();
+        assert!(Signature::<P256>::from_bytes(&high).is_err());
+
+        assert!(SigningKey::<P256>::from_bytes(&n).is_err());
+        assert!(SigningKey::<P256>::from_bytes(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_random_keys() {
+        let key = SigningKey::<P384>::random();
+        let signature = key.sign::<Sha512>(b"message");
+        assert!(verify::<_, Sha512>(
+            key.public_key(),
+            b"message",
+            &signature
+        ));
+        assert!(!verify::<_, Sha384>(
+            key.public_key(),
+            b"message",
+            &signature
+        ));
+        let other = SigningKey::<P384>::random();
+        assert!(!verify::<_, Sha512>(
+            other.public_key(),
+            b"message",
+            &signature
+        ));
+    }
+
+    // Wycheproof test vectors from testdata/wycheproof, or WYCHEPROOF_DIR when set
+    fn wycheproof<C, H>(file: &str)
+    where
+        C: WeierstrassConfig,
+        C::BaseField: FieldBytes + SquareRoot,
+        C::ScalarField: FieldBytes,
+        H: HashFunction + Default,
+    {
+        let directory = std::env::var("WYCHEPROOF_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata/wycheproof")
+            });
+        let path = directory.join(file);
+        let contents = std::fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("cannot read {}: {}", path.display(), error));
+        let json: serde_json::Value = serde_json::from_str(&contents).unwrap();
+
+        let mut checked = 0;
+        for group in json["testGroups"].as_array().unwrap() {
+            // the older layout keeps the key under "key", the newer under "publicKey"
+            let key = if group["publicKey"].is_object() {
+                &group["publicKey"]
+            } else {
+                &group["key"]
+            };
+            let public =
+                AffinePoint::<C>::from_bytes(&from_hex(key["uncompressed"].as_str().unwrap()))
+                    .unwrap();
+
+            for test in group["tests"].as_array().unwrap() {
+                let message = from_hex(test["msg"].as_str().unwrap());
+                let valid = Signature::<C>::from_der(&from_hex(test["sig"].as_str().unwrap()))
+                    .is_ok_and(|signature| verify::<C, H>(&public, &message, &signature));
+                match test["result"].as_str().unwrap() {
+                    "valid" => assert!(valid, "test case {}", test["tcId"]),
+                    "invalid" => assert!(!valid, "test case {}", test["tcId"]),
+                    _ => {}
+                }
+                checked += 1;
+            }
+        }
+        assert!(checked > 0, "no test cases in {}", path.display());
+    }
+
+    // the vector files are not committed. Fetch ecdsa_secp256k1_sha256_test.json,
+    // ecdsa_secp256r1_sha256_test.json and ecdsa_secp384r1_sha384_test.json from the
+    // testvectors_v1 directory of https://github.com/C2SP/wycheproof into testdata/wycheproof
+    // or WYCHEPROOF_DIR, then run cargo test test_wycheproof -- --ignored. A missing file
+    // fails the test instead of skipping it.
+    #[test]
+    #[ignore = "needs the Wycheproof vector files, see above"]
+    fn test_wycheproof() {
+        wycheproof::<Secp256k1, Sha256>("ecdsa_secp256k1_sha256_test.json");
+        wycheproof::<P256, Sha256>("ecdsa_secp256r1_sha256_test.json");
+        wycheproof::<P384, Sha384>("ecdsa_secp384r1_sha384_test.json");
+    }
+}
@@ -0,0 +1,309 @@
+use crate::curves::curve25519::{EdwardsPoint, Fr};
+use crate::fields::element::FieldElement;
+use crate::hash::sha2::Sha512;
+use cryptography::{Field, HashFunction};
+use num_bigint::BigUint;
+
+type Scalar = FieldElement<Fr>;
+
+// RFC 8032 dom2 prefix for Ed25519ph, plain Ed25519 uses none
+const DOM2: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
+fn sha512(parts: &[&[u8]]) -> Vec<u8> {
+    Sha512::new().hash(&parts.concat())
+}
+
+// little-endian bytes reduced modulo l
+fn scalar_from_le_bytes(bytes: &[u8]) -> Scalar {
+    FieldElement::new(Fr::from_biguint(&BigUint::from_bytes_le(bytes)))
+}
+
+fn scalar_to_le_bytes(a: &Scalar) -> [u8; 32] {
+    let mut bytes: [u8; 32] = [0; 32];
+    for (chunk, limb) in bytes.chunks_mut(8).zip(Fr::to_limbs(&a.value)) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    bytes
+}
+
+// None unless the bytes encode an integer below l
+fn canonical_scalar(bytes: &[u8]) -> Option<Scalar> {
+    let value = BigUint::from_bytes_le(bytes);
+    if value >= Fr::characteristic() {
+        return None;
+    }
+    Some(FieldElement::new(Fr::from_biguint(&value)))
+}
+
+fn dom2(context: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if context.len() > 255 {
+        return Err("context longer than 255 bytes");
+    }
+    let mut prefix = DOM2.to_vec();
+    prefix.push(1);
+    prefix.push(context.len() as u8);
+    prefix.extend(context);
+    Ok(prefix)
+}
+
+pub struct SigningKey {
+    scalar: Scalar,
+    prefix: Vec<u8>,
+    public: VerifyingKey,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifyingKey {
+    point: EdwardsPoint,
+    bytes: [u8; 32],
+}
+
+impl SigningKey {
+    // RFC 8032 section 5.1.5, the scalar is the clamped first half of SHA-512(seed)
+    // and the second half keys the nonces
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let h = sha512(&[seed]);
+        let mut a: [u8; 32] = h[..32].try_into().unwrap();
+        a[0] &= 248;
+        a[31] &= 127;
+        a[31] |= 64;
+
+        let scalar = scalar_from_le_bytes(&a);
+        let point = EdwardsPoint::generator().mul_scalar(&scalar);
+        Self {
+            scalar,
+            prefix: h[32..].to_vec(),
+            public: VerifyingKey {
+                bytes: point.compress(),
+                point,
+            },
+        }
+    }
+
+    pub fn random() -> Self {
+        Self::from_seed(&std::array::from_fn(|_| rand::random::<u8>()))
+    }
+
+    pub fn verifying_key(&self) -> &VerifyingKey {
+        &self.public
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.sign_with_prefix(&[], message)
+    }
+
+    // Ed25519ph, signs SHA-512(message) under a context of at most 255 bytes
+    pub fn sign_prehashed(&self, message: &[u8], context: &[u8]) -> Result<[u8; 64], &'static str> {
+        Ok(self.sign_with_prefix(&dom2(context)?, &sha512(&[message])))
+    }
+
+    // R = [r]B with r = H(dom || prefix || M), S = r + H(dom || R || A || M) a
+    fn sign_with_prefix(&self, dom: &[u8], message: &[u8]) -> [u8; 64] {
+        let r = scalar_from_le_bytes(&sha512(&[dom, &self.prefix, message]));
+        let big_r = EdwardsPoint::generator().mul_scalar(&r).compress();
+        let k = scalar_from_le_bytes(&sha512(&[dom, &big_r, &self.public.bytes, message]));
+        let s = &r + &(&k * &self.scalar);
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&big_r);
+        signature[32..].copy_from_slice(&scalar_to_le_bytes(&s));
+        signature
+    }
+}
+
+// a signature split into R, S and the challenge k
+struct Parsed {
+    r: EdwardsPoint,
+    s: Scalar,
+    k: Scalar,
+}
+
+impl VerifyingKey {
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, &'static str> {
+        Ok(Self {
+            point: EdwardsPoint::decompress(bytes)?,
+            bytes: *bytes,
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    fn parse(&self, dom: &[u8], message: &[u8], signature: &[u8; 64]) -> Option<Parsed> {
+        let r_bytes: [u8; 32] = signature[..32].try_into().unwrap();
+        let r = EdwardsPoint::decompress(&r_bytes).ok()?;
+        let s = canonical_scalar(&signature[32..])?;
+        let k = scalar_from_le_bytes(&sha512(&[dom, &r_bytes, &self.bytes, message]));
+        Some(Parsed { r, s, k })
+    }
+
+    // the cofactored equation [8][S]B = [8]R + [8][k]A, the same one batches check
+    fn verify_with_prefix(&self, dom: &[u8], message: &[u8], signature: &[u8; 64]) -> bool {
+        let parsed = match self.parse(dom, message, signature) {
+            Some(parsed) => parsed,
+            None => return false,
+        };
+        let lhs = EdwardsPoint::generator().mul_scalar(&parsed.s);
+        let rhs = &parsed.r + &self.point.mul_scalar(&parsed.k);
+        (&lhs - &rhs).mul_by_cofactor().is_identity()
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8; 64]) -> bool {
+        self.verify_with_prefix(&[], message, signature)
+    }
+
+    pub fn verify_prehashed(&self, message: &[u8], context: &[u8], signature: &[u8; 64]) -> bool {
+        match dom2(context) {
+            Ok(dom) => self.verify_with_prefix(&dom, &sha512(&[message]), signature),
+            Err(_) => false,
+        }
+    }
+}
+
+// checks every (key, message, signature) at once with random 128-bit weights z_i:
+// [8]([Σ z_i S_i]B - Σ [z_i]R_i - Σ [z_i k_i]A_i) = O
+pub fn verify_batch(items: &[(&VerifyingKey, &[u8], &[u8; 64])]) -> bool {
+    let mut s_sum = Scalar::zero();
+    let mut sum = EdwardsPoint::identity();
+    for (key, message, signature) in items {
+        let parsed = match key.parse(&[], message, signature) {
+            Some(parsed) => parsed,
+            None => return false,
+        };
+        let z = scalar_from_le_bytes(&rand::random::<u128>().to_le_bytes());
+        s_sum += &(&z * &parsed.s);
+        sum = &sum + &parsed.r.mul_scalar(&z);
+        sum = &sum + &key.point.mul_scalar(&(&z * &parsed.k));
+    }
+    (&EdwardsPoint::generator().mul_scalar(&s_sum) - &sum)
+        .mul_by_cofactor()
+        .is_identity()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn signature(s: &str) -> [u8; 64] {
+        from_hex(s).try_into().unwrap()
+    }
+
+    // RFC 8032 section 7.1, tests 1 to 3
+    #[test]
+    fn test_vectors() {
+        let vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+                 5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                 085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "af82",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+                 18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+        ];
+        for (seed, public, message, expected) in vectors {
+            let key = SigningKey::from_seed(&from_hex(seed).try_into().unwrap());
+            let message = from_hex(message);
+            assert_eq!(key.verifying_key().to_bytes().to_vec(), from_hex(public));
+
+            let sig = key.sign(&message);
+            assert_eq!(sig, signature(expected));
+            assert!(key.verifying_key().verify(&message, &sig));
+            assert!(!key.verifying_key().verify(b"other", &sig));
+        }
+    }
+
+    // RFC 8032 section 7.3
+    #[test]
+    fn test_prehashed() {
+        let key = SigningKey::from_seed(
+            &from_hex("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42")
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(
+            key.verifying_key().to_bytes().to_vec(),
+            from_hex("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf")
+        );
+
+        let sig = key.sign_prehashed(b"abc", b"").unwrap();
+        assert_eq!(
+            sig,
+            signature(
+                "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+                 31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406"
+            )
+        );
+        assert!(key.verifying_key().verify_prehashed(b"abc", b"", &sig));
+
+        // the domain separation keeps plain and prehashed signatures apart
+        assert!(!key.verifying_key().verify(b"abc", &sig));
+        assert!(!key
+            .verifying_key()
+            .verify_prehashed(b"abc", b"context", &sig));
+        assert!(key.sign_prehashed(b"abc", &[0; 256]).is_err());
+    }
+
+    #[test]
+    fn test_malleability() {
+        let key = SigningKey::random();
+        let sig = key.sign(b"message");
+
+        // S + l encodes the same scalar but is rejected as non-canonical
+        let s = BigUint::from_bytes_le(&sig[32..]) + Fr::characteristic();
+        let mut malleated = sig;
+        let mut s_bytes = s.to_bytes_le();
+        s_bytes.resize(32, 0);
+        malleated[32..].copy_from_slice(&s_bytes);
+        assert!(!key.verifying_key().verify(b"message", &malleated));
+
+        let mut tampered = sig;
+        tampered[0] ^= 1;
+        assert!(!key.verifying_key().verify(b"message", &tampered));
+    }
+
+    #[test]
+    fn test_batch() {
+        let keys: Vec<SigningKey> = (0..4).map(|_| SigningKey::random()).collect();
+        let messages: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; i as usize + 1]).collect();
+        let signatures: Vec<[u8; 64]> = keys
+            .iter()
+            .zip(messages.iter())
+            .map(|(key, message)| key.sign(message))
+            .collect();
+
+        let mut items: Vec<(&VerifyingKey, &[u8], &[u8; 64])> = keys
+            .iter()
+            .zip(messages.iter())
+            .zip(signatures.iter())
+            .map(|((key, message), sig)| (key.verifying_key(), message.as_slice(), sig))
+            .collect();
+        assert!(verify_batch(&items));
+        assert!(verify_batch(&[]));
+
+        // one signature over the wrong message fails the batch
+        items[2].1 = b"wrong";
+        assert!(!verify_batch(&items));
+    }
+}
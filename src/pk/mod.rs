@@ -1 +1,6 @@
+pub mod bip340;
+pub mod ecdsa;
+pub mod ed25519;
 pub mod rsa;
+pub mod schnorr;
+pub mod x25519;
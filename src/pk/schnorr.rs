@@ -0,0 +1,218 @@
+use crate::curves::weierstrass::{JacobianPoint, WeierstrassConfig};
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::protocols::transcript::{GroupEncoding, Transcript};
+use num_bigint::BigUint;
+
+// window for the scalar multiplications
+const WINDOW: usize = 5;
+
+// a group of prime order written additively, with scalars modulo the order
+pub trait PrimeOrderGroup: Clone + PartialEq + GroupEncoding {
+    type Scalar: FieldBytes;
+
+    fn generator() -> Self;
+
+    fn identity() -> Self;
+
+    fn add(&self, other: &Self) -> Self;
+
+    fn mul(&self, scalar: &FieldElement<Self::Scalar>) -> Self;
+
+    // whether an element received from outside lies in the group
+    fn is_valid(&self) -> bool;
+}
+
+// the prime order subgroup of a Weierstrass curve
+impl<C> PrimeOrderGroup for JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes,
+    C::ScalarField: FieldBytes,
+{
+    type Scalar = C::ScalarField;
+
+    fn generator() -> Self {
+        JacobianPoint::generator()
+    }
+
+    fn identity() -> Self {
+        JacobianPoint::identity()
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn mul(&self, scalar: &FieldElement<C::ScalarField>) -> Self {
+        let scalar = BigUint::from_bytes_be(&C::ScalarField::to_bytes(&scalar.value));
+        self.mul_wnaf(&scalar.to_u64_digits(), WINDOW)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.to_affine().is_on_curve() && self.is_in_subgroup()
+    }
+}
+
+// (R, s) with sG = R + eP. Keeping R instead of e lets signatures be verified in
+// batches.
+pub struct Signature<G: PrimeOrderGroup> {
+    pub r: G,
+    pub s: FieldElement<G::Scalar>,
+}
+
+pub struct SigningKey<G: PrimeOrderGroup> {
+    secret: FieldElement<G::Scalar>,
+    public: G,
+}
+
+// e = H(R, P, m), bound to the encodings of both group elements
+fn challenge<G: PrimeOrderGroup>(r: &G, public: &G, message: &[u8]) -> FieldElement<G::Scalar> {
+    let mut transcript = Transcript::keccak(b"schnorr signature");
+    transcript.append_group_element(b"R", r);
+    transcript.append_group_element(b"public key", public);
+    transcript.append_message(b"message", message);
+    transcript.challenge_field_element(b"challenge")
+}
+
+impl<G: PrimeOrderGroup> SigningKey<G> {
+    pub fn new(secret: FieldElement<G::Scalar>) -> Result<Self, &'static str> {
+        if secret.is_zero() {
+            return Err("secret key must be nonzero");
+        }
+        let public = G::generator().mul(&secret);
+        Ok(Self { secret, public })
+    }
+
+    pub fn random() -> Self {
+        loop {
+            if let Ok(key) = Self::new(FieldElement::random()) {
+                return key;
+            }
+        }
+    }
+
+    pub fn public_key(&self) -> &G {
+        &self.public
+    }
+
+    // hedged nonce from the secret, the message and fresh randomness, so neither a
+    // repeated message nor a broken random number generator alone repeats it
+    fn nonce(&self, message: &[u8]) -> FieldElement<G::Scalar> {
+        let mut transcript = Transcript::keccak(b"schnorr nonce");
+        transcript.append_field_element(b"secret key", &self.secret);
+        transcript.append_message(b"message", message);
+        transcript.append_message(b"randomness", &rand::random::<[u8; 32]>());
+        transcript.challenge_field_element(b"nonce")
+    }
+
+    // R = kG, s = k + e x
+    pub fn sign(&self, message: &[u8]) -> Signature<G> {
+        let k = self.nonce(message);
+        let r = G::generator().mul(&k);
+        let e = challenge(&r, &self.public, message);
+        let s = &k + &(&e * &self.secret);
+        Signature { r, s }
+    }
+}
+
+pub fn verify<G: PrimeOrderGroup>(public: &G, message: &[u8], signature: &Signature<G>) -> bool {
+    if *public == G::identity() || !public.is_valid() || !signature.r.is_valid() {
+        return false;
+    }
+    let e = challenge(&signature.r, public, message);
+    G::generator().mul(&signature.s) == signature.r.add(&public.mul(&e))
+}
+
+// checks every (key, message, signature) at once with random weights z_i:
+// [Σ z_i s_i]G = Σ [z_i]R_i + Σ [z_i e_i]P_i
+pub fn verify_batch<G: PrimeOrderGroup>(items: &[(&G, &[u8], &Signature<G>)]) -> bool {
+    let mut s_sum = FieldElement::<G::Scalar>::zero();
+    let mut sum = G::identity();
+    for (public, message, signature) in items {
+        if **public == G::identity() || !public.is_valid() || !signature.r.is_valid() {
+            return false;
+        }
+        let e = challenge(&signature.r, public, message);
+        let z = FieldElement::<G::Scalar>::random();
+        s_sum += &(&z * &signature.s);
+        sum = sum.add(&signature.r.mul(&z));
+        sum = sum.add(&public.mul(&(&z * &e)));
+    }
+    G::generator().mul(&s_sum) == sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::{bn254, p256, p384, secp256k1};
+
+    fn round_trip<G: PrimeOrderGroup>() {
+        let key = SigningKey::<G>::random();
+        let signature = key.sign(b"message");
+        assert!(verify(key.public_key(), b"message", &signature));
+        assert!(!verify(key.public_key(), b"other", &signature));
+
+        let other = SigningKey::<G>::random();
+        assert!(!verify(other.public_key(), b"message", &signature));
+
+        let tampered = Signature {
+            r: signature.r.clone(),
+            s: &signature.s + &FieldElement::one(),
+        };
+        assert!(!verify(key.public_key(), b"message", &tampered));
+
+        // hedged nonces give fresh signatures for the same message
+        let again = key.sign(b"message");
+        assert!(again.r != signature.r);
+        assert!(verify(key.public_key(), b"message", &again));
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        round_trip::<secp256k1::Point>();
+        round_trip::<p256::Point>();
+        round_trip::<p384::Point>();
+        round_trip::<bn254::G1>();
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        assert!(SigningKey::<secp256k1::Point>::new(FieldElement::zero()).is_err());
+
+        let key = SigningKey::<secp256k1::Point>::random();
+        let signature = key.sign(b"message");
+        let identity = secp256k1::Point::identity();
+        assert!(!verify(&identity, b"message", &signature));
+
+        // R = O with s = 0 would pass sG = R + eP for the identity key
+        let forged = Signature {
+            r: identity.clone(),
+            s: FieldElement::zero(),
+        };
+        assert!(!verify(&identity, b"message", &forged));
+    }
+
+    #[test]
+    fn test_batch() {
+        let keys: Vec<SigningKey<p256::Point>> = (0..4).map(|_| SigningKey::random()).collect();
+        let messages: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; i as usize + 1]).collect();
+        let signatures: Vec<Signature<p256::Point>> = keys
+            .iter()
+            .zip(messages.iter())
+            .map(|(key, message)| key.sign(message))
+            .collect();
+
+        let mut items: Vec<(&p256::Point, &[u8], &Signature<p256::Point>)> = keys
+            .iter()
+            .zip(messages.iter())
+            .zip(signatures.iter())
+            .map(|((key, message), sig)| (key.public_key(), message.as_slice(), sig))
+            .collect();
+        assert!(verify_batch(&items));
+        assert!(verify_batch::<p256::Point>(&[]));
+
+        items[1].1 = b"wrong";
+        assert!(!verify_batch(&items));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::curves::curve25519::{montgomery_ladder, to_le_bytes, Fq};
+use crate::fields::element::FieldElement;
+use num_bigint::BigUint;
+
+// x coordinate of the base point
+pub const BASE_POINT: [u8; 32] = {
+    let mut u = [0u8; 32];
+    u[0] = 9;
+    u
+};
+
+// clears the three low bits and the top bit, sets bit 254
+fn clamp(scalar: &[u8; 32]) -> [u8; 32] {
+    let mut k = *scalar;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    k
+}
+
+// RFC 7748 section 5. The top bit of u is ignored and non-canonical values are
+// reduced modulo p.
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut masked = *u;
+    masked[31] &= 127;
+    let u = FieldElement::new(Fq::from_biguint(&BigUint::from_bytes_le(&masked)));
+    to_le_bytes(&montgomery_ladder(&clamp(scalar), &u))
+}
+
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    x25519(secret, &BASE_POINT)
+}
+
+pub fn random_secret() -> [u8; 32] {
+    std::array::from_fn(|_| rand::random::<u8>())
+}
+
+// rejects the all-zero output of small order public keys
+pub fn shared_secret(secret: &[u8; 32], public: &[u8; 32]) -> Result<[u8; 32], &'static str> {
+    let shared = x25519(secret, public);
+    if shared == [0u8; 32] {
+        return Err("public key has small order");
+    }
+    Ok(shared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(s: &str) -> [u8; 32] {
+        let bytes: Vec<u8> = (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect();
+        bytes.try_into().unwrap()
+    }
+
+    // RFC 7748 section 5.2
+    #[test]
+    fn test_vectors() {
+        assert_eq!(
+            x25519(
+                &from_hex("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                &from_hex("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c")
+            ),
+            from_hex("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+        // the top bit of u is set and ignored
+        assert_eq!(
+            x25519(
+                &from_hex("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d"),
+                &from_hex("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493")
+            ),
+            from_hex("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957")
+        );
+    }
+
+    fn iterate(iterations: usize) -> [u8; 32] {
+        let mut k = BASE_POINT;
+        let mut u = BASE_POINT;
+        for _ in 0..iterations {
+            let next = x25519(&k, &u);
+            u = k;
+            k = next;
+        }
+        k
+    }
+
+    #[test]
+    fn test_iterated() {
+        assert_eq!(
+            iterate(1),
+            from_hex("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+        assert_eq!(
+            iterate(1000),
+            from_hex("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+    }
+
+    #[test]
+    #[ignore = "a million ladders take minutes"]
+    fn test_iterated_million() {
+        assert_eq!(
+            iterate(1_000_000),
+            from_hex("7c3911e0ab2586fd864497297e575e6f3bc601c0883c30df5f4dd2d24f665424")
+        );
+    }
+
+    // RFC 7748 section 6.1
+    #[test]
+    fn test_diffie_hellman() {
+        let alice = from_hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob = from_hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        assert_eq!(
+            public_key(&alice),
+            from_hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            public_key(&bob),
+            from_hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+
+        let shared = from_hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(shared_secret(&alice, &public_key(&bob)).unwrap(), shared);
+        assert_eq!(shared_secret(&bob, &public_key(&alice)).unwrap(), shared);
+
+        // u = 0 and u = 1 have small order
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        assert!(shared_secret(&alice, &[0u8; 32]).is_err());
+        assert!(shared_secret(&alice, &one).is_err());
+
+        // u = p + 9 is reduced to the base point
+        let mut non_canonical = [0xff; 32];
+        non_canonical[0] = 0xf6;
+        non_canonical[31] = 0x7f;
+        assert_eq!(x25519(&alice, &non_canonical), public_key(&alice));
+
+        let (a, b) = (random_secret(), random_secret());
+        assert_eq!(
+            shared_secret(&a, &public_key(&b)),
+            shared_secret(&b, &public_key(&a))
+        );
+    }
+}
@@ -0,0 +1,537 @@
+use crate::fields::element::FieldElement;
+use crate::polynomial::domain::EvaluationDomain;
+use cryptography::{FftField, Field};
+use std::ops::{Add, Mul, Neg, Sub};
+
+// below this many coefficients Karatsuba falls back to schoolbook multiplication
+const KARATSUBA_THRESHOLD: usize = 32;
+
+// below this divisor degree or quotient length long division is faster than Newton iteration
+const FAST_DIVISION_THRESHOLD: usize = 64;
+
+// below this many coefficients in either operand mul_ntt uses Karatsuba instead
+const NTT_THRESHOLD: usize = 64;
+
+// coefficient form, coefficients[i] multiplies x^i and there are no trailing zeros
+#[derive(Debug)]
+pub struct Polynomial<F: Field> {
+    pub coefficients: Vec<FieldElement<F>>,
+}
+
+impl<F> Clone for Polynomial<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            coefficients: self.coefficients.clone(),
+        }
+    }
+}
+
+impl<F> PartialEq for Polynomial<F>
+where
+    F: Field,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients == other.coefficients
+    }
+}
+
+impl<F> Polynomial<F>
+where
+    F: Field,
+{
+    pub fn new(coefficients: Vec<FieldElement<F>>) -> Self {
+        let mut polynomial = Self { coefficients };
+        polynomial.normalize();
+        polynomial
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            coefficients: Vec::new(),
+        }
+    }
+
+    pub fn constant(c: FieldElement<F>) -> Self {
+        Self::new(vec![c])
+    }
+
+    // c * x^degree
+    pub fn monomial(c: FieldElement<F>, degree: usize) -> Self {
+        let mut coefficients = vec![FieldElement::<F>::zero(); degree];
+        coefficients.push(c);
+        Self::new(coefficients)
+    }
+
+    // x - root
+    pub fn linear(root: &FieldElement<F>) -> Self {
+        Self::new(vec![-root, FieldElement::<F>::one()])
+    }
+
+    fn normalize(&mut self) {
+        while self.coefficients.last().is_some_and(|c| c.is_zero()) {
+            self.coefficients.pop();
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn leading_coefficient(&self) -> FieldElement<F> {
+        self.coefficients
+            .last()
+            .cloned()
+            .unwrap_or_else(FieldElement::<F>::zero)
+    }
+
+    // coefficient of x^i, zero past the degree
+    pub fn coefficient(&self, i: usize) -> FieldElement<F> {
+        self.coefficients
+            .get(i)
+            .cloned()
+            .unwrap_or_else(FieldElement::<F>::zero)
+    }
+
+    // Horner's rule
+    pub fn evaluate(&self, x: &FieldElement<F>) -> FieldElement<F> {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(FieldElement::<F>::zero(), |acc, c| &(&acc * x) + c)
+    }
+
+    pub fn scale(&self, factor: &FieldElement<F>) -> Self {
+        Self::new(self.coefficients.iter().map(|c| c * factor).collect())
+    }
+
+    // divides by the leading coefficient, the zero polynomial stays zero
+    pub fn monic(&self) -> Self {
+        match self.leading_coefficient().inverse() {
+            Some(inverse) => self.scale(&inverse),
+            None => Self::zero(),
+        }
+    }
+
+    pub fn derivative(&self) -> Self {
+        // i is built as 1 + 1 + ... so that it reduces by the characteristic,
+        // from(i) is not the integer i in binary fields
+        let mut factor = FieldElement::<F>::zero();
+        Self::new(
+            self.coefficients
+                .iter()
+                .skip(1)
+                .map(|c| {
+                    factor += &FieldElement::<F>::one();
+                    c * &factor
+                })
+                .collect(),
+        )
+    }
+
+    // self mod x^n
+    pub fn truncate(&self, n: usize) -> Self {
+        Self::new(self.coefficients.iter().take(n).cloned().collect())
+    }
+
+    // x^(n - 1) * self(1 / x), self must have fewer than n coefficients
+    pub fn reverse(&self, n: usize) -> Self {
+        assert!(self.coefficients.len() <= n, "polynomial is longer than n");
+
+        let mut coefficients = self.coefficients.clone();
+        coefficients.resize(n, FieldElement::<F>::zero());
+        coefficients.reverse();
+        Self::new(coefficients)
+    }
+
+    // self(other(x))
+    pub fn compose(&self, other: &Self) -> Self {
+        self.coefficients.iter().rev().fold(Self::zero(), |acc, c| {
+            &(&acc * other) + &Self::constant(c.clone())
+        })
+    }
+
+    pub fn mul_schoolbook(&self, other: &Self) -> Self {
+        Self::new(schoolbook(&self.coefficients, &other.coefficients))
+    }
+
+    pub fn mul_karatsuba(&self, other: &Self) -> Self {
+        Self::new(karatsuba(&self.coefficients, &other.coefficients))
+    }
+
+    // returns (quotient, remainder) with deg remainder < deg divisor
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), &'static str> {
+        let divisor_degree = divisor.degree().ok_or("division by the zero polynomial")?;
+        let lead_inv = divisor.leading_coefficient().inverse().unwrap();
+
+        let mut remainder = self.coefficients.clone();
+        if remainder.len() <= divisor_degree {
+            return Ok((Self::zero(), self.clone()));
+        }
+
+        let mut quotient = vec![FieldElement::<F>::zero(); remainder.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let factor = &remainder[i + divisor_degree] * &lead_inv;
+            for (j, d) in divisor.coefficients.iter().enumerate() {
+                remainder[i + j] -= &(&factor * d);
+            }
+            quotient[i] = factor;
+        }
+        remainder.truncate(divisor_degree);
+
+        Ok((Self::new(quotient), Self::new(remainder)))
+    }
+
+    // monic greatest common divisor, zero only when both inputs are zero
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b).unwrap();
+            a = b;
+            b = remainder;
+        }
+        a.monic()
+    }
+}
+
+impl<F> Polynomial<F>
+where
+    F: FftField,
+{
+    // multiplication through evaluations on a power of two domain, falls back to
+    // Karatsuba for short operands or when the field has no root of unity of the needed order
+    pub fn mul_ntt(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        if self.coefficients.len() < NTT_THRESHOLD || other.coefficients.len() < NTT_THRESHOLD {
+            return self.mul_karatsuba(other);
+        }
+
+        let size = (self.coefficients.len() + other.coefficients.len() - 1).next_power_of_two();
+        let domain = match EvaluationDomain::<F>::new(size) {
+            Ok(domain) => domain,
+            Err(_) => return self.mul_karatsuba(other),
+        };
+
+        let a = domain.fft(&self.coefficients);
+        let b = domain.fft(&other.coefficients);
+        let product: Vec<FieldElement<F>> = a.iter().zip(b.iter()).map(|(x, y)| x * y).collect();
+
+        Self::new(domain.ifft(&product))
+    }
+
+    // 1 / self mod x^n by Newton iteration g = g (2 - self g), doubling the precision each step
+    pub fn inverse_series(&self, n: usize) -> Result<Self, &'static str> {
+        let c0 = self
+            .coefficient(0)
+            .inverse()
+            .ok_or("constant term must be non-zero")?;
+        let two = Self::constant(FieldElement::<F>::from(2));
+
+        let mut g = Self::constant(c0);
+        let mut precision = 1;
+        while precision < n {
+            precision = (2 * precision).min(n);
+            let fg = self.truncate(precision).mul_ntt(&g).truncate(precision);
+            g = g.mul_ntt(&(&two - &fg)).truncate(precision);
+        }
+
+        Ok(g.truncate(n))
+    }
+
+    // div_rem through reversed polynomials and a power series inverse, O(M(n)) instead of O(n^2)
+    pub fn div_rem_fast(&self, divisor: &Self) -> Result<(Self, Self), &'static str> {
+        let divisor_degree = divisor.degree().ok_or("division by the zero polynomial")?;
+        let degree = match self.degree() {
+            Some(degree) if degree >= divisor_degree => degree,
+            _ => return Ok((Self::zero(), self.clone())),
+        };
+
+        let quotient_length = degree - divisor_degree + 1;
+        if divisor_degree < FAST_DIVISION_THRESHOLD || quotient_length < FAST_DIVISION_THRESHOLD {
+            return self.div_rem(divisor);
+        }
+
+        // rev(q) = rev(self) / rev(divisor) mod x^(deg q + 1)
+        let divisor_inverse = divisor
+            .reverse(divisor_degree + 1)
+            .inverse_series(quotient_length)?;
+        let quotient_reversed = self
+            .reverse(degree + 1)
+            .truncate(quotient_length)
+            .mul_ntt(&divisor_inverse)
+            .truncate(quotient_length);
+        let quotient = quotient_reversed.reverse(quotient_length);
+        let remainder = self - &quotient.mul_ntt(divisor);
+
+        Ok((quotient, remainder))
+    }
+}
+
+fn schoolbook<F: Field>(a: &[FieldElement<F>], b: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = vec![FieldElement::<F>::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += &(x * y);
+        }
+    }
+    result
+}
+
+fn add_into<F: Field>(target: &mut [FieldElement<F>], values: &[FieldElement<F>]) {
+    for (t, v) in target.iter_mut().zip(values.iter()) {
+        *t += v;
+    }
+}
+
+fn sub_into<F: Field>(target: &mut [FieldElement<F>], values: &[FieldElement<F>]) {
+    for (t, v) in target.iter_mut().zip(values.iter()) {
+        *t -= v;
+    }
+}
+
+fn sum<F: Field>(a: &[FieldElement<F>], b: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+    let mut result = vec![FieldElement::<F>::zero(); a.len().max(b.len())];
+    add_into(&mut result, a);
+    add_into(&mut result, b);
+    result
+}
+
+// (a0 + a1 x^m)(b0 + b1 x^m) = z0 + ((a0 + a1)(b0 + b1) - z0 - z2) x^m + z2 x^2m
+fn karatsuba<F: Field>(a: &[FieldElement<F>], b: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+    if a.len() < KARATSUBA_THRESHOLD || b.len() < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+
+    let m = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(m.min(a.len()));
+    let (b0, b1) = b.split_at(m.min(b.len()));
+
+    let z0 = karatsuba(a0, b0);
+    let z2 = karatsuba(a1, b1);
+    let mut z1 = karatsuba(&sum(a0, a1), &sum(b0, b1));
+    sub_into(&mut z1, &z0);
+    sub_into(&mut z1, &z2);
+
+    let mut result = vec![FieldElement::<F>::zero(); a.len() + b.len() - 1];
+    add_into(&mut result, &z0);
+    add_into(&mut result[m..], &z1);
+    add_into(&mut result[2 * m..], &z2);
+    result
+}
+
+impl<F> Add<&Polynomial<F>> for &Polynomial<F>
+where
+    F: Field,
+{
+    type Output = Polynomial<F>;
+
+    fn add(self, other: &Polynomial<F>) -> Self::Output {
+        Polynomial::new(sum(&self.coefficients, &other.coefficients))
+    }
+}
+
+impl<F> Sub<&Polynomial<F>> for &Polynomial<F>
+where
+    F: Field,
+{
+    type Output = Polynomial<F>;
+
+    fn sub(self, other: &Polynomial<F>) -> Self::Output {
+        let mut result =
+            vec![FieldElement::<F>::zero(); self.coefficients.len().max(other.coefficients.len())];
+        add_into(&mut result, &self.coefficients);
+        sub_into(&mut result, &other.coefficients);
+        Polynomial::new(result)
+    }
+}
+
+impl<F> Mul<&Polynomial<F>> for &Polynomial<F>
+where
+    F: Field,
+{
+    type Output = Polynomial<F>;
+
+    fn mul(self, other: &Polynomial<F>) -> Self::Output {
+        self.mul_karatsuba(other)
+    }
+}
+
+impl<F> Neg for &Polynomial<F>
+where
+    F: Field,
+{
+    type Output = Polynomial<F>;
+
+    fn neg(self) -> Self::Output {
+        Polynomial::new(self.coefficients.iter().map(|c| -c).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Polynomial;
+    use crate::fields::binary::AesField;
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn polynomial(coefficients: &[u64]) -> Polynomial<Goldilocks> {
+        Polynomial::new(coefficients.iter().map(|c| Element::from(*c)).collect())
+    }
+
+    fn random_polynomial(length: usize) -> Polynomial<Goldilocks> {
+        Polynomial::new((0..length).map(|_| Element::random()).collect())
+    }
+
+    #[test]
+    fn test_normalize_and_degree() {
+        assert_eq!(polynomial(&[1, 2, 0, 0]).degree(), Some(1));
+        assert_eq!(polynomial(&[0, 0]).degree(), None);
+        assert!(polynomial(&[0]).is_zero());
+        assert!(polynomial(&[1, 2, 3]).leading_coefficient() == Element::from(3));
+    }
+
+    #[test]
+    fn test_add_sub_neg() {
+        let a = polynomial(&[1, 2, 3]);
+        let b = polynomial(&[4, 5]);
+
+        assert!(&a + &b == polynomial(&[5, 7, 3]));
+        assert!(&a - &a == Polynomial::zero());
+        assert!(&(&a - &b) + &b == a);
+        assert!(&a + &(-&a) == Polynomial::zero());
+    }
+
+    #[test]
+    fn test_mul() {
+        // (1 + x)(1 - x) = 1 - x^2
+        let a = polynomial(&[1, 1]);
+        let b = Polynomial::new(vec![Element::from(1), -&Element::from(1)]);
+
+        assert!(
+            &a * &b == Polynomial::new(vec![Element::from(1), Element::zero(), -&Element::from(1)])
+        );
+        assert!(&a * &Polynomial::zero() == Polynomial::zero());
+    }
+
+    #[test]
+    fn test_karatsuba_and_ntt_match_schoolbook() {
+        for (n, m) in [(1, 1), (31, 40), (64, 64), (100, 37), (200, 129)] {
+            let a = random_polynomial(n);
+            let b = random_polynomial(m);
+            let expected = a.mul_schoolbook(&b);
+
+            assert!(a.mul_karatsuba(&b) == expected);
+            assert!(a.mul_ntt(&b) == expected);
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        // 1 + 2x + 3x^2 at x = 2
+        assert!(polynomial(&[1, 2, 3]).evaluate(&Element::from(2)) == Element::from(17));
+        assert!(Polynomial::<Goldilocks>::zero()
+            .evaluate(&Element::from(2))
+            .is_zero());
+    }
+
+    #[test]
+    fn test_div_rem() {
+        for (n, m) in [(10, 3), (50, 50), (3, 10), (64, 1)] {
+            let a = random_polynomial(n);
+            let b = random_polynomial(m);
+
+            let (q, r) = a.div_rem(&b).unwrap();
+
+            assert!(&(&q * &b) + &r == a);
+            assert!(r.degree() < b.degree() || r.is_zero());
+        }
+        assert!(polynomial(&[1]).div_rem(&Polynomial::zero()).is_err());
+    }
+
+    #[test]
+    fn test_inverse_series() {
+        let a = random_polynomial(100);
+
+        let inverse = a.inverse_series(77).unwrap();
+
+        assert_eq!(inverse.degree(), Some(76));
+        assert!(a.mul_ntt(&inverse).truncate(77) == polynomial(&[1]));
+        assert!(polynomial(&[0, 1]).inverse_series(4).is_err());
+    }
+
+    #[test]
+    fn test_div_rem_fast_matches_div_rem() {
+        for (n, m) in [
+            (10, 3),
+            (3, 10),
+            (300, 100),
+            (513, 70),
+            (1000, 999),
+            (700, 1),
+        ] {
+            let a = random_polynomial(n);
+            let b = random_polynomial(m);
+
+            let (q, r) = a.div_rem_fast(&b).unwrap();
+
+            assert!((q.clone(), r.clone()) == a.div_rem(&b).unwrap());
+            assert!(&(&q * &b) + &r == a);
+        }
+        assert!(polynomial(&[1]).div_rem_fast(&Polynomial::zero()).is_err());
+    }
+
+    #[test]
+    fn test_derivative() {
+        // d/dx (1 + 2x + 3x^2 + 4x^3) = 2 + 6x + 12x^2
+        assert!(polynomial(&[1, 2, 3, 4]).derivative() == polynomial(&[2, 6, 12]));
+        assert!(polynomial(&[5]).derivative().is_zero());
+
+        // in characteristic two d/dx (x^3 + x^2) = 3x^2 + 2x = x^2
+        let one = FieldElement::<AesField>::one();
+        let zero = FieldElement::<AesField>::zero();
+        let cubic = Polynomial::new(vec![zero.clone(), zero.clone(), one.clone(), one.clone()]);
+        assert!(cubic.derivative() == Polynomial::new(vec![zero.clone(), zero, one]));
+    }
+
+    #[test]
+    fn test_compose() {
+        let a = random_polynomial(5);
+        let b = random_polynomial(4);
+        let x = Element::random();
+
+        let composed = a.compose(&b);
+
+        assert_eq!(composed.degree(), Some(12));
+        assert!(composed.evaluate(&x) == a.evaluate(&b.evaluate(&x)));
+    }
+
+    #[test]
+    fn test_gcd() {
+        let one = Element::one();
+        let x_minus = |r: u64| Polynomial::linear(&Element::from(r));
+
+        let a = &(&x_minus(1) * &x_minus(2)) * &Polynomial::constant(Element::from(5));
+        let b = &x_minus(1) * &x_minus(3);
+
+        assert!(a.gcd(&b) == x_minus(1));
+        assert!(x_minus(2).gcd(&x_minus(3)) == Polynomial::constant(one));
+        assert!(a.gcd(&Polynomial::zero()) == a.monic());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::fields::element::FieldElement;
+use crate::polynomial::ntt::{bit_reverse_permutation, ntt_dif, ntt_mixed_radix, twiddles};
+use cryptography::FftField;
+
+// multiplicative subgroup {ω^0, ..., ω^(n - 1)} and its coset offset * H,
+// with the twiddle factors precomputed for power of two sizes
+pub struct EvaluationDomain<F: FftField> {
+    size: usize,
+    generator: FieldElement<F>,
+    generator_inv: FieldElement<F>,
+    size_inv: FieldElement<F>,
+    offset: FieldElement<F>,
+    offset_inv: FieldElement<F>,
+    twiddles: Vec<FieldElement<F>>,
+    inverse_twiddles: Vec<FieldElement<F>>,
+}
+
+impl<F> EvaluationDomain<F>
+where
+    F: FftField,
+{
+    // subgroup of the given size with the multiplicative generator as coset offset
+    pub fn new(size: usize) -> Result<Self, &'static str> {
+        Self::with_offset(size, FieldElement::<F>::new(F::multiplicative_generator()))
+    }
+
+    pub fn with_offset(size: usize, offset: FieldElement<F>) -> Result<Self, &'static str> {
+        if size == 0 {
+            return Err("domain size must be positive");
+        }
+        let generator = FieldElement::<F>::new(
+            F::root_of_unity(size as u64).ok_or("no root of unity of this order in the field")?,
+        );
+        let generator_inv = generator.inverse().unwrap();
+        let size_inv = FieldElement::<F>::from(size as u64)
+            .inverse()
+            .ok_or("domain size is not invertible in the field")?;
+        let offset_inv = offset.inverse().ok_or("coset offset must be non-zero")?;
+
+        let (twiddles, inverse_twiddles) = if size.is_power_of_two() {
+            (twiddles(&generator, size), twiddles(&generator_inv, size))
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        Ok(Self {
+            size,
+            generator,
+            generator_inv,
+            size_inv,
+            offset,
+            offset_inv,
+            twiddles,
+            inverse_twiddles,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn generator(&self) -> &FieldElement<F> {
+        &self.generator
+    }
+
+    pub fn offset(&self) -> &FieldElement<F> {
+        &self.offset
+    }
+
+    // ω^i
+    pub fn element(&self, i: usize) -> FieldElement<F> {
+        self.generator.pow_u64((i % self.size) as u64)
+    }
+
+    pub fn elements(&self) -> Vec<FieldElement<F>> {
+        let mut result = Vec::with_capacity(self.size);
+        let mut current = FieldElement::<F>::one();
+        for _ in 0..self.size {
+            result.push(current.clone());
+            current *= &self.generator;
+        }
+        result
+    }
+
+    pub fn coset_elements(&self) -> Vec<FieldElement<F>> {
+        self.elements()
+            .iter()
+            .map(|element| element * &self.offset)
+            .collect()
+    }
+
+    // Z_H(x) = x^n - 1
+    pub fn evaluate_vanishing_polynomial(&self, x: &FieldElement<F>) -> FieldElement<F> {
+        &x.pow_u64(self.size as u64) - &FieldElement::<F>::one()
+    }
+
+    fn transform(
+        &self,
+        values: &mut Vec<FieldElement<F>>,
+        root: &FieldElement<F>,
+        twiddles: &[FieldElement<F>],
+    ) {
+        if self.size.is_power_of_two() {
+            ntt_dif(values, twiddles);
+            bit_reverse_permutation(values);
+        } else {
+            *values = ntt_mixed_radix(values, root);
+        }
+    }
+
+    // evaluations over the subgroup of a polynomial with at most size coefficients
+    pub fn fft(&self, coefficients: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        assert!(
+            coefficients.len() <= self.size,
+            "polynomial does not fit in the domain"
+        );
+
+        let mut values = coefficients.to_vec();
+        values.resize(self.size, FieldElement::<F>::zero());
+        self.transform(&mut values, &self.generator, &self.twiddles);
+        values
+    }
+
+    // coefficients of the polynomial taking the given values over the subgroup
+    pub fn ifft(&self, evaluations: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        assert_eq!(
+            evaluations.len(),
+            self.size,
+            "one evaluation per domain element is required"
+        );
+
+        let mut values = evaluations.to_vec();
+        self.transform(&mut values, &self.generator_inv, &self.inverse_twiddles);
+        for value in values.iter_mut() {
+            *value *= &self.size_inv;
+        }
+        values
+    }
+
+    // evaluations over offset * H, coefficient i is scaled by offset^i first
+    pub fn coset_fft(&self, coefficients: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        self.fft(&Self::scale(coefficients, &self.offset))
+    }
+
+    pub fn coset_ifft(&self, evaluations: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+        Self::scale(&self.ifft(evaluations), &self.offset_inv)
+    }
+
+    fn scale(coefficients: &[FieldElement<F>], factor: &FieldElement<F>) -> Vec<FieldElement<F>> {
+        let mut power = FieldElement::<F>::one();
+        coefficients
+            .iter()
+            .map(|coefficient| {
+                let scaled = coefficient * &power;
+                power *= factor;
+                scaled
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EvaluationDomain;
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+
+    fn random_values(n: usize) -> Vec<FieldElement<Goldilocks>> {
+        (0..n)
+            .map(|_| FieldElement::<Goldilocks>::random())
+            .collect()
+    }
+
+    fn horner(
+        coefficients: &[FieldElement<Goldilocks>],
+        x: &FieldElement<Goldilocks>,
+    ) -> FieldElement<Goldilocks> {
+        coefficients
+            .iter()
+            .rev()
+            .fold(FieldElement::<Goldilocks>::zero(), |acc, c| &(&acc * x) + c)
+    }
+
+    #[test]
+    fn test_fft_matches_naive_evaluation() {
+        for size in [1, 2, 16, 12, 15] {
+            let domain = EvaluationDomain::<Goldilocks>::new(size).unwrap();
+            let coefficients = random_values(size);
+
+            let evaluations = domain.fft(&coefficients);
+
+            for (x, y) in domain.elements().iter().zip(evaluations.iter()) {
+                assert!(horner(&coefficients, x) == *y);
+            }
+            assert!(domain.ifft(&evaluations) == coefficients);
+        }
+    }
+
+    #[test]
+    fn test_fft_pads_short_polynomials() {
+        let domain = EvaluationDomain::<Goldilocks>::new(32).unwrap();
+        let coefficients = random_values(5);
+
+        let evaluations = domain.fft(&coefficients);
+        let recovered = domain.ifft(&evaluations);
+
+        assert!(recovered[..5] == coefficients[..]);
+        assert!(recovered[5..].iter().all(|c| c.is_zero()));
+    }
+
+    #[test]
+    fn test_coset_fft_matches_naive_evaluation() {
+        for size in [8, 6] {
+            let domain = EvaluationDomain::<Goldilocks>::new(size).unwrap();
+            let coefficients = random_values(size);
+
+            let evaluations = domain.coset_fft(&coefficients);
+
+            for (x, y) in domain.coset_elements().iter().zip(evaluations.iter()) {
+                assert!(horner(&coefficients, x) == *y);
+                assert!(!domain.evaluate_vanishing_polynomial(x).is_zero());
+            }
+            assert!(domain.coset_ifft(&evaluations) == coefficients);
+        }
+    }
+
+    #[test]
+    fn test_vanishing_polynomial() {
+        let domain = EvaluationDomain::<Goldilocks>::new(16).unwrap();
+
+        for x in domain.elements() {
+            assert!(domain.evaluate_vanishing_polynomial(&x).is_zero());
+        }
+        assert!(domain.element(17) == domain.element(1));
+    }
+
+    #[test]
+    fn test_unsupported_sizes() {
+        assert!(EvaluationDomain::<Goldilocks>::new(0).is_err());
+        assert!(EvaluationDomain::<Goldilocks>::new(7).is_err());
+        assert!(EvaluationDomain::<Goldilocks>::new(1 << 33).is_err());
+    }
+}
@@ -1,3 +1,7 @@
+use crate::fields::batch::batch_inverse;
+use crate::fields::element::FieldElement;
+use crate::polynomial::dense::Polynomial;
+use cryptography::Field;
 use cryptography::Number;
 use cryptography::Point;
 
@@ -9,7 +13,7 @@ impl LagrangeInterpolation {
     }
 
     pub fn interpolate(&mut self, points: Vec<Point>, x: Number) -> Number {
-        let mut result = Number::from(0 as f64);
+        let mut result = Number::from(0_f64);
 
         for i in 0..points.len() {
             let mut term = points[i].y;
@@ -25,6 +29,113 @@ impl LagrangeInterpolation {
     }
 }
 
+impl LagrangeInterpolation {
+    // coefficient form of the unique polynomial of degree < n through (xs[i], ys[i])
+    pub fn interpolate_polynomial<F: Field>(
+        xs: &[FieldElement<F>],
+        ys: &[FieldElement<F>],
+    ) -> Result<Polynomial<F>, &'static str> {
+        if xs.len() != ys.len() {
+            return Err("number of x and y coordinates differ");
+        }
+        let weights = barycentric_weights(xs)?;
+
+        // M(x) = (x - x_0)(x - x_1)...(x - x_{n-1})
+        let mut master = vec![FieldElement::<F>::one()];
+        for x in xs {
+            master.insert(0, FieldElement::<F>::zero());
+            for i in 0..master.len() - 1 {
+                let term = x * &master[i + 1];
+                master[i] -= &term;
+            }
+        }
+
+        let mut result = vec![FieldElement::<F>::zero(); xs.len()];
+        for ((x, y), w) in xs.iter().zip(ys.iter()).zip(weights.iter()) {
+            // M(x) / (x - x_i) by synthetic division, scaled by y_i * w_i
+            let factor = y * w;
+            let mut carry = FieldElement::<F>::zero();
+            for k in (0..xs.len()).rev() {
+                carry = &(&carry * x) + &master[k + 1];
+                result[k] += &(&carry * &factor);
+            }
+        }
+
+        Ok(Polynomial::new(result))
+    }
+}
+
+// w_i = 1 / prod_{j != i} (x_i - x_j)
+fn barycentric_weights<F: Field>(
+    xs: &[FieldElement<F>],
+) -> Result<Vec<FieldElement<F>>, &'static str> {
+    let mut denominators = Vec::with_capacity(xs.len());
+    for (i, xi) in xs.iter().enumerate() {
+        let mut product = FieldElement::<F>::one();
+        for (j, xj) in xs.iter().enumerate() {
+            if i != j {
+                product *= &(xi - xj);
+            }
+        }
+        if product.is_zero() {
+            return Err("duplicate x-coordinates");
+        }
+        denominators.push(product);
+    }
+    Ok(batch_inverse(&denominators))
+}
+
+// barycentric form with the weights computed once, each query costs O(n)
+pub struct BarycentricInterpolation<F: Field> {
+    xs: Vec<FieldElement<F>>,
+    ys: Vec<FieldElement<F>>,
+    weights: Vec<FieldElement<F>>,
+}
+
+impl<F> BarycentricInterpolation<F>
+where
+    F: Field,
+{
+    pub fn new(xs: Vec<FieldElement<F>>, ys: Vec<FieldElement<F>>) -> Result<Self, &'static str> {
+        if xs.len() != ys.len() {
+            return Err("number of x and y coordinates differ");
+        }
+        if xs.is_empty() {
+            return Err("at least one point is required");
+        }
+        let weights = barycentric_weights(&xs)?;
+
+        Ok(Self { xs, ys, weights })
+    }
+
+    pub fn weights(&self) -> &[FieldElement<F>] {
+        &self.weights
+    }
+
+    // p(x) = sum(w_i y_i / (x - x_i)) / sum(w_i / (x - x_i))
+    pub fn evaluate(&self, x: &FieldElement<F>) -> FieldElement<F> {
+        let differences: Vec<FieldElement<F>> = self.xs.iter().map(|xi| x - xi).collect();
+        if let Some(i) = differences.iter().position(|d| d.is_zero()) {
+            return self.ys[i].clone();
+        }
+
+        let inverses = batch_inverse(&differences);
+        let mut numerator = FieldElement::<F>::zero();
+        let mut denominator = FieldElement::<F>::zero();
+        for ((inverse, w), y) in inverses.iter().zip(self.weights.iter()).zip(self.ys.iter()) {
+            let term = inverse * w;
+            numerator += &(&term * y);
+            denominator += &term;
+        }
+
+        &numerator * &denominator.inverse().unwrap()
+    }
+
+    pub fn to_polynomial(&self) -> Polynomial<F> {
+        LagrangeInterpolation::interpolate_polynomial(&self.xs, &self.ys).unwrap()
+    }
+}
+
 impl Default for LagrangeInterpolation {
     fn default() -> Self {
         Self::new()
@@ -34,44 +145,115 @@ impl Default for LagrangeInterpolation {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    #[test]
+    fn test_interpolate_polynomial_recovers_coefficients() {
+        let expected = Polynomial::new((0..10).map(|_| Element::random()).collect());
+        let xs: Vec<Element> = (0..10).map(|_| Element::random()).collect();
+        let ys: Vec<Element> = xs.iter().map(|x| expected.evaluate(x)).collect();
+
+        let polynomial = LagrangeInterpolation::interpolate_polynomial(&xs, &ys).unwrap();
+
+        assert!(polynomial == expected);
+    }
+
+    #[test]
+    fn test_interpolate_polynomial_exact_line() {
+        // (1, 1), (2, 2), (3, 3) is y = x with no rounding error
+        let xs: Vec<Element> = (1..=3).map(Element::from).collect();
+        let ys = xs.clone();
+
+        let polynomial = LagrangeInterpolation::interpolate_polynomial(&xs, &ys).unwrap();
+
+        assert!(polynomial == Polynomial::new(vec![Element::zero(), Element::one()]));
+        assert!(polynomial.evaluate(&Element::from(4)) == Element::from(4));
+    }
+
+    #[test]
+    fn test_interpolate_polynomial_errors() {
+        let xs = vec![Element::from(1), Element::from(2), Element::from(1)];
+        let ys = vec![Element::from(5), Element::from(6), Element::from(7)];
+
+        assert_eq!(
+            LagrangeInterpolation::interpolate_polynomial(&xs, &ys),
+            Err("duplicate x-coordinates")
+        );
+        assert!(LagrangeInterpolation::interpolate_polynomial(&xs[..2], &ys).is_err());
+        assert!(
+            LagrangeInterpolation::interpolate_polynomial::<Goldilocks>(&[], &[])
+                .unwrap()
+                .is_zero()
+        );
+    }
+
+    #[test]
+    fn test_barycentric_evaluation() {
+        let expected = Polynomial::new((0..16).map(|_| Element::random()).collect());
+        let xs: Vec<Element> = (0..16).map(Element::from).collect();
+        let ys: Vec<Element> = xs.iter().map(|x| expected.evaluate(x)).collect();
+
+        let interpolation = BarycentricInterpolation::new(xs.clone(), ys.clone()).unwrap();
+
+        for _ in 0..10 {
+            let x = Element::random();
+            assert!(interpolation.evaluate(&x) == expected.evaluate(&x));
+        }
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            assert!(interpolation.evaluate(x) == *y);
+        }
+        assert!(interpolation.to_polynomial() == expected);
+    }
+
+    #[test]
+    fn test_barycentric_rejects_duplicates() {
+        let xs = vec![Element::from(3), Element::from(3)];
+        let ys = vec![Element::from(1), Element::from(2)];
+
+        assert!(BarycentricInterpolation::new(xs, ys).is_err());
+    }
+
     #[test]
     fn test_lagrange_float_32() {
         let mut lagrange = LagrangeInterpolation::new();
         let points = vec![
             Point {
-                x: Number::from(1.0 as f32),
-                y: Number::from(1.0 as f32),
+                x: Number::from(1.0_f32),
+                y: Number::from(1.0_f32),
             },
             Point {
-                x: Number::from(2.0 as f32),
-                y: Number::from(2.0 as f32),
+                x: Number::from(2.0_f32),
+                y: Number::from(2.0_f32),
             },
             Point {
-                x: Number::from(3.0 as f32),
-                y: Number::from(3.0 as f32),
+                x: Number::from(3.0_f32),
+                y: Number::from(3.0_f32),
             },
         ];
-        let result = lagrange.interpolate(points, Number::from(4.0 as f32));
-        assert_eq!(result, Number::from(4.0 as f64));
+        let result = lagrange.interpolate(points, Number::from(4.0_f32));
+        assert_eq!(result, Number::from(4.0_f64));
     }
     #[test]
     fn test_lagrange_float_64() {
         let mut lagrange = LagrangeInterpolation::new();
         let points = vec![
             Point {
-                x: Number::from(1.0 as f64),
-                y: Number::from(1.0 as f64),
+                x: Number::from(1.0_f64),
+                y: Number::from(1.0_f64),
             },
             Point {
-                x: Number::from(2.0 as f64),
-                y: Number::from(2.0 as f64),
+                x: Number::from(2.0_f64),
+                y: Number::from(2.0_f64),
             },
             Point {
-                x: Number::from(3.0 as f64),
-                y: Number::from(3.0 as f64),
+                x: Number::from(3.0_f64),
+                y: Number::from(3.0_f64),
             },
         ];
-        let result = lagrange.interpolate(points, Number::from(4.0 as f64));
-        assert_eq!(result, Number::from(4.0 as f64));
+        let result = lagrange.interpolate(points, Number::from(4.0_f64));
+        assert_eq!(result, Number::from(4.0_f64));
     }
 }
@@ -1 +1,8 @@
-pub mod lagrange;
\ No newline at end of file
+pub mod dense;
+pub mod domain;
+pub mod lagrange;
+pub mod multilinear;
+pub mod multipoint;
+pub mod multivariate;
+pub mod newton;
+pub mod ntt;
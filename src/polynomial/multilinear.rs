@@ -0,0 +1,314 @@
+use crate::fields::element::FieldElement;
+use cryptography::Field;
+use std::ops::{Add, Neg, Sub};
+
+// multilinear polynomial in n variables stored by its values on {0, 1}^n,
+// evaluations[i] is the value at the point whose first variable is the
+// most significant bit of i
+#[derive(Debug)]
+pub struct MultilinearPolynomial<F: Field> {
+    num_variables: usize,
+    evaluations: Vec<FieldElement<F>>,
+}
+
+impl<F> Clone for MultilinearPolynomial<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            num_variables: self.num_variables,
+            evaluations: self.evaluations.clone(),
+        }
+    }
+}
+
+impl<F> PartialEq for MultilinearPolynomial<F>
+where
+    F: Field,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.num_variables == other.num_variables && self.evaluations == other.evaluations
+    }
+}
+
+impl<F> MultilinearPolynomial<F>
+where
+    F: Field,
+{
+    pub fn new(evaluations: Vec<FieldElement<F>>) -> Result<Self, &'static str> {
+        if !evaluations.len().is_power_of_two() {
+            return Err("number of evaluations must be a power of two");
+        }
+
+        Ok(Self {
+            num_variables: evaluations.len().trailing_zeros() as usize,
+            evaluations,
+        })
+    }
+
+    pub fn zero(num_variables: usize) -> Self {
+        Self {
+            num_variables,
+            evaluations: vec![FieldElement::<F>::zero(); 1 << num_variables],
+        }
+    }
+
+    pub fn random(num_variables: usize) -> Self {
+        Self {
+            num_variables,
+            evaluations: (0..1 << num_variables)
+                .map(|_| FieldElement::<F>::random())
+                .collect(),
+        }
+    }
+
+    // eq(x, r) = prod r_i x_i + (1 - r_i)(1 - x_i), equal to 1 at x = r on the hypercube
+    pub fn eq(r: &[FieldElement<F>]) -> Self {
+        let mut evaluations = vec![FieldElement::<F>::one()];
+        for r_i in r {
+            // each existing entry splits into (1 - r_i) e followed by r_i e
+            let mut next = Vec::with_capacity(2 * evaluations.len());
+            for e in evaluations.iter() {
+                let high = e * r_i;
+                next.push(e - &high);
+                next.push(high);
+            }
+            evaluations = next;
+        }
+
+        Self {
+            num_variables: r.len(),
+            evaluations,
+        }
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    pub fn evaluations(&self) -> &[FieldElement<F>] {
+        &self.evaluations
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.evaluations.iter().all(|e| e.is_zero())
+    }
+
+    // sum of the values over {0, 1}^n
+    pub fn sum_over_hypercube(&self) -> FieldElement<F> {
+        let mut sum = FieldElement::<F>::zero();
+        for e in self.evaluations.iter() {
+            sum += e;
+        }
+        sum
+    }
+
+    // p(r, x_2, ..., x_n), halves the table
+    pub fn fix_first_variable(&self, r: &FieldElement<F>) -> Self {
+        assert!(self.num_variables > 0, "no variables left to fix");
+
+        let half = self.evaluations.len() / 2;
+        let (low, high) = self.evaluations.split_at(half);
+        let evaluations = low
+            .iter()
+            .zip(high.iter())
+            .map(|(l, h)| l + &(&(h - l) * r))
+            .collect();
+
+        Self {
+            num_variables: self.num_variables - 1,
+            evaluations,
+        }
+    }
+
+    // fixes the leading variables to values in order
+    pub fn fix_variables(&self, values: &[FieldElement<F>]) -> Self {
+        assert!(
+            values.len() <= self.num_variables,
+            "more values than variables"
+        );
+
+        values
+            .iter()
+            .fold(self.clone(), |p, r| p.fix_first_variable(r))
+    }
+
+    pub fn evaluate(&self, point: &[FieldElement<F>]) -> Result<FieldElement<F>, &'static str> {
+        if point.len() != self.num_variables {
+            return Err("point dimension does not match the number of variables");
+        }
+
+        Ok(self.fix_variables(point).evaluations[0].clone())
+    }
+
+    pub fn scale(&self, factor: &FieldElement<F>) -> Self {
+        Self {
+            num_variables: self.num_variables,
+            evaluations: self.evaluations.iter().map(|e| e * factor).collect(),
+        }
+    }
+}
+
+// eq(x, y) for arbitrary points of the same dimension
+pub fn eq_evaluate<F: Field>(x: &[FieldElement<F>], y: &[FieldElement<F>]) -> FieldElement<F> {
+    assert_eq!(x.len(), y.len(), "points must have the same dimension");
+
+    let one = FieldElement::<F>::one();
+    let mut result = FieldElement::<F>::one();
+    for (a, b) in x.iter().zip(y.iter()) {
+        let both = a * b;
+        let neither = &(&one - a) * &(&one - b);
+        result *= &(&both + &neither);
+    }
+    result
+}
+
+impl<F> Add<&MultilinearPolynomial<F>> for &MultilinearPolynomial<F>
+where
+    F: Field,
+{
+    type Output = MultilinearPolynomial<F>;
+
+    fn add(self, other: &MultilinearPolynomial<F>) -> Self::Output {
+        assert_eq!(
+            self.num_variables, other.num_variables,
+            "number of variables differ"
+        );
+
+        MultilinearPolynomial {
+            num_variables: self.num_variables,
+            evaluations: self
+                .evaluations
+                .iter()
+                .zip(other.evaluations.iter())
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+}
+
+impl<F> Sub<&MultilinearPolynomial<F>> for &MultilinearPolynomial<F>
+where
+    F: Field,
+{
+    type Output = MultilinearPolynomial<F>;
+
+    fn sub(self, other: &MultilinearPolynomial<F>) -> Self::Output {
+        self + &(-other)
+    }
+}
+
+impl<F> Neg for &MultilinearPolynomial<F>
+where
+    F: Field,
+{
+    type Output = MultilinearPolynomial<F>;
+
+    fn neg(self) -> Self::Output {
+        MultilinearPolynomial {
+            num_variables: self.num_variables,
+            evaluations: self.evaluations.iter().map(|e| -e).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eq_evaluate, MultilinearPolynomial};
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn hypercube_point(i: usize, n: usize) -> Vec<Element> {
+        (0..n)
+            .map(|j| Element::from(((i >> (n - 1 - j)) & 1) as u64))
+            .collect()
+    }
+
+    fn random_point(n: usize) -> Vec<Element> {
+        (0..n).map(|_| Element::random()).collect()
+    }
+
+    #[test]
+    fn test_evaluate_on_hypercube() {
+        let p = MultilinearPolynomial::<Goldilocks>::random(4);
+
+        for i in 0..16 {
+            assert!(p.evaluate(&hypercube_point(i, 4)).unwrap() == p.evaluations()[i]);
+        }
+        assert!(p.evaluate(&random_point(3)).is_err());
+        assert!(MultilinearPolynomial::<Goldilocks>::new(vec![Element::one(); 3]).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_is_multilinear_extension() {
+        // p(x, y) = 1 + 2x + 3y + 4xy
+        let p = MultilinearPolynomial::new(vec![
+            Element::from(1),
+            Element::from(4),
+            Element::from(3),
+            Element::from(10),
+        ])
+        .unwrap();
+        let (x, y) = (Element::from(5), Element::from(7));
+
+        let expected = &(&(&Element::from(1) + &(&Element::from(2) * &x))
+            + &(&Element::from(3) * &y))
+            + &(&(&Element::from(4) * &x) * &y);
+
+        assert!(p.evaluate(&[x, y]).unwrap() == expected);
+    }
+
+    #[test]
+    fn test_fix_variables() {
+        let p = MultilinearPolynomial::<Goldilocks>::random(5);
+        let point = random_point(5);
+
+        let partial = p.fix_variables(&point[..2]);
+
+        assert_eq!(partial.num_variables(), 3);
+        assert!(partial.evaluate(&point[2..]).unwrap() == p.evaluate(&point).unwrap());
+    }
+
+    #[test]
+    fn test_eq_polynomial() {
+        let r = random_point(4);
+        let eq = MultilinearPolynomial::eq(&r);
+
+        for i in 0..16 {
+            let x = hypercube_point(i, 4);
+            assert!(eq.evaluations()[i] == eq_evaluate(&x, &r));
+        }
+        assert!(eq.sum_over_hypercube().is_one());
+
+        let x = random_point(4);
+        assert!(eq.evaluate(&x).unwrap() == eq_evaluate(&x, &r));
+
+        // sum over the hypercube of p(x) eq(x, r) is p(r)
+        let p = MultilinearPolynomial::<Goldilocks>::random(4);
+        let mut sum = Element::zero();
+        for (a, b) in p.evaluations().iter().zip(eq.evaluations().iter()) {
+            sum += &(a * b);
+        }
+        assert!(sum == p.evaluate(&r).unwrap());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = MultilinearPolynomial::<Goldilocks>::random(3);
+        let b = MultilinearPolynomial::<Goldilocks>::random(3);
+        let point = random_point(3);
+        let two = Element::from(2);
+
+        let sum = (&a + &b).evaluate(&point).unwrap();
+        let difference = (&a - &b).evaluate(&point).unwrap();
+
+        assert!(sum == &a.evaluate(&point).unwrap() + &b.evaluate(&point).unwrap());
+        assert!(difference == &a.evaluate(&point).unwrap() - &b.evaluate(&point).unwrap());
+        assert!(a.scale(&two).evaluate(&point).unwrap() == &two * &a.evaluate(&point).unwrap());
+        assert!((&a - &a).is_zero());
+    }
+}
@@ -0,0 +1,205 @@
+use crate::fields::batch::batch_inverse;
+use crate::fields::element::FieldElement;
+use crate::polynomial::dense::Polynomial;
+use cryptography::FftField;
+
+// nodes at this level cover 2^LEAF_LEVEL points, below it evaluation uses Horner's rule
+const LEAF_LEVEL: usize = 4;
+
+// levels[0] holds x - x_i for every point, node i of level l + 1 is the product of nodes
+// 2i and 2i + 1 of level l, so it vanishes exactly on points [i 2^(l+1), (i + 1) 2^(l+1))
+pub struct SubproductTree<F: FftField> {
+    points: Vec<FieldElement<F>>,
+    levels: Vec<Vec<Polynomial<F>>>,
+}
+
+impl<F> SubproductTree<F>
+where
+    F: FftField,
+{
+    pub fn new(points: &[FieldElement<F>]) -> Self {
+        let mut levels = Vec::new();
+        if !points.is_empty() {
+            let mut level: Vec<Polynomial<F>> = points.iter().map(Polynomial::linear).collect();
+            while level.len() > 1 {
+                let next = level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => left.mul_ntt(right),
+                        _ => pair[0].clone(),
+                    })
+                    .collect();
+                levels.push(level);
+                level = next;
+            }
+            levels.push(level);
+        }
+
+        Self {
+            points: points.to_vec(),
+            levels,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[FieldElement<F>] {
+        &self.points
+    }
+
+    // (x - x_0)(x - x_1)...(x - x_{n-1})
+    pub fn vanishing_polynomial(&self) -> Polynomial<F> {
+        match self.levels.last() {
+            Some(root) => root[0].clone(),
+            None => Polynomial::constant(FieldElement::<F>::one()),
+        }
+    }
+
+    // p(x_i) for every point, reducing p modulo each node on the way down and switching to
+    // Horner's rule once a node covers few enough points
+    pub fn evaluate(&self, polynomial: &Polynomial<F>) -> Vec<FieldElement<F>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        let cutoff = LEAF_LEVEL.min(self.levels.len() - 1);
+        let mut remainders = vec![polynomial.clone()];
+        for level in self.levels[cutoff..].iter().rev() {
+            remainders = level
+                .iter()
+                .enumerate()
+                .map(|(i, node)| remainders[i / 2].div_rem_fast(node).unwrap().1)
+                .collect();
+        }
+
+        self.points
+            .chunks(1 << cutoff)
+            .zip(remainders.iter())
+            .flat_map(|(points, remainder)| points.iter().map(|x| remainder.evaluate(x)))
+            .collect()
+    }
+
+    // polynomial of degree < n taking values[i] at x_i, sum of values[i] / M'(x_i) * M(x) / (x - x_i)
+    pub fn interpolate(&self, values: &[FieldElement<F>]) -> Result<Polynomial<F>, &'static str> {
+        if values.len() != self.points.len() {
+            return Err("number of x and y coordinates differ");
+        }
+        if self.is_empty() {
+            return Ok(Polynomial::zero());
+        }
+
+        let denominators = self.evaluate(&self.vanishing_polynomial().derivative());
+        if denominators.iter().any(|d| d.is_zero()) {
+            return Err("duplicate x-coordinates");
+        }
+        let weights = batch_inverse(&denominators);
+
+        let mut combinations: Vec<Polynomial<F>> = weights
+            .iter()
+            .zip(values.iter())
+            .map(|(w, y)| Polynomial::constant(w * y))
+            .collect();
+        for level in self.levels.iter().take(self.levels.len() - 1) {
+            combinations = combinations
+                .chunks(2)
+                .zip(level.chunks(2))
+                .map(|(combination, nodes)| match (combination, nodes) {
+                    ([left, right], [left_node, right_node]) => {
+                        &left.mul_ntt(right_node) + &right.mul_ntt(left_node)
+                    }
+                    _ => combination[0].clone(),
+                })
+                .collect();
+        }
+
+        Ok(combinations.remove(0))
+    }
+}
+
+// evaluates polynomial at every point in O(n log^2 n)
+pub fn evaluate_many<F: FftField>(
+    polynomial: &Polynomial<F>,
+    points: &[FieldElement<F>],
+) -> Vec<FieldElement<F>> {
+    SubproductTree::new(points).evaluate(polynomial)
+}
+
+// unique polynomial of degree < n through (xs[i], ys[i]) in O(n log^2 n)
+pub fn interpolate<F: FftField>(
+    xs: &[FieldElement<F>],
+    ys: &[FieldElement<F>],
+) -> Result<Polynomial<F>, &'static str> {
+    SubproductTree::new(xs).interpolate(ys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate_many, interpolate, SubproductTree};
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+    use crate::polynomial::dense::Polynomial;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn random_values(n: usize) -> Vec<Element> {
+        (0..n).map(|_| Element::random()).collect()
+    }
+
+    #[test]
+    fn test_evaluate_many_matches_horner() {
+        for (degree, count) in [(0, 1), (10, 3), (100, 37), (300, 300), (65, 513)] {
+            let polynomial = Polynomial::new(random_values(degree + 1));
+            let points = random_values(count);
+
+            let values = evaluate_many(&polynomial, &points);
+
+            assert_eq!(values.len(), count);
+            for (x, y) in points.iter().zip(values.iter()) {
+                assert!(polynomial.evaluate(x) == *y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interpolate_roundtrip() {
+        for count in [1, 2, 7, 64, 201] {
+            let expected = Polynomial::new(random_values(count));
+            let tree = SubproductTree::new(&random_values(count));
+
+            let values = tree.evaluate(&expected);
+
+            assert!(tree.interpolate(&values).unwrap() == expected);
+        }
+    }
+
+    #[test]
+    fn test_vanishing_polynomial() {
+        let points = random_values(13);
+        let tree = SubproductTree::new(&points);
+        let vanishing = tree.vanishing_polynomial();
+
+        assert_eq!(vanishing.degree(), Some(13));
+        assert!(points.iter().all(|x| vanishing.evaluate(x).is_zero()));
+        assert!(SubproductTree::<Goldilocks>::new(&[])
+            .vanishing_polynomial()
+            .evaluate(&Element::random())
+            .is_one());
+    }
+
+    #[test]
+    fn test_interpolate_errors() {
+        let xs = vec![Element::from(1), Element::from(2), Element::from(1)];
+        let ys = random_values(3);
+
+        assert_eq!(interpolate(&xs, &ys), Err("duplicate x-coordinates"));
+        assert!(interpolate(&xs, &ys[..2]).is_err());
+        assert!(interpolate::<Goldilocks>(&[], &[]).unwrap().is_zero());
+    }
+}
@@ -0,0 +1,444 @@
+use crate::fields::element::FieldElement;
+use crate::polynomial::multilinear::MultilinearPolynomial;
+use cryptography::Field;
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonomialOrder {
+    // first differing exponent decides
+    Lex,
+    // total degree first, ties broken by Lex
+    GradedLex,
+    // total degree first, then the smaller last differing exponent is larger
+    GradedReverseLex,
+}
+
+impl MonomialOrder {
+    pub fn compare(&self, a: &[usize], b: &[usize]) -> Ordering {
+        let lex = || {
+            a.iter()
+                .zip(b.iter())
+                .map(|(x, y)| x.cmp(y))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        };
+        let degree = || a.iter().sum::<usize>().cmp(&b.iter().sum::<usize>());
+
+        match self {
+            MonomialOrder::Lex => lex(),
+            MonomialOrder::GradedLex => degree().then_with(lex),
+            MonomialOrder::GradedReverseLex => degree().then_with(|| {
+                a.iter()
+                    .zip(b.iter())
+                    .rev()
+                    .map(|(x, y)| y.cmp(x))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            }),
+        }
+    }
+}
+
+// sum of coefficient * x_0^e_0 ... x_{n-1}^e_{n-1}, terms kept sorted from the leading
+// term down under the chosen order, without zero coefficients or repeated monomials
+#[derive(Debug)]
+pub struct SparsePolynomial<F: Field> {
+    num_variables: usize,
+    order: MonomialOrder,
+    terms: Vec<(Vec<usize>, FieldElement<F>)>,
+}
+
+impl<F> Clone for SparsePolynomial<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            num_variables: self.num_variables,
+            order: self.order,
+            terms: self.terms.clone(),
+        }
+    }
+}
+
+// equal as polynomials, the monomial order is not compared
+impl<F> PartialEq for SparsePolynomial<F>
+where
+    F: Field,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let other = other.with_order(self.order);
+        self.num_variables == other.num_variables && self.terms == other.terms
+    }
+}
+
+impl<F> SparsePolynomial<F>
+where
+    F: Field,
+{
+    pub fn new(
+        num_variables: usize,
+        terms: Vec<(Vec<usize>, FieldElement<F>)>,
+        order: MonomialOrder,
+    ) -> Result<Self, &'static str> {
+        if terms
+            .iter()
+            .any(|(exponents, _)| exponents.len() != num_variables)
+        {
+            return Err("every monomial needs one exponent per variable");
+        }
+
+        let mut polynomial = Self {
+            num_variables,
+            order,
+            terms,
+        };
+        polynomial.normalize();
+        Ok(polynomial)
+    }
+
+    pub fn zero(num_variables: usize, order: MonomialOrder) -> Self {
+        Self {
+            num_variables,
+            order,
+            terms: Vec::new(),
+        }
+    }
+
+    pub fn constant(num_variables: usize, c: FieldElement<F>, order: MonomialOrder) -> Self {
+        Self::new(num_variables, vec![(vec![0; num_variables], c)], order).unwrap()
+    }
+
+    // x_i
+    pub fn variable(i: usize, num_variables: usize, order: MonomialOrder) -> Self {
+        assert!(i < num_variables, "variable index out of range");
+
+        let mut exponents = vec![0; num_variables];
+        exponents[i] = 1;
+        Self::new(
+            num_variables,
+            vec![(exponents, FieldElement::<F>::one())],
+            order,
+        )
+        .unwrap()
+    }
+
+    // monomial coefficients of the multilinear extension by the inverse Moebius transform
+    pub fn from_multilinear(p: &MultilinearPolynomial<F>, order: MonomialOrder) -> Self {
+        let n = p.num_variables();
+        let mut coefficients = p.evaluations().to_vec();
+        for bit in 0..n {
+            for i in 0..coefficients.len() {
+                if i & (1 << bit) != 0 {
+                    let lower = coefficients[i ^ (1 << bit)].clone();
+                    coefficients[i] -= &lower;
+                }
+            }
+        }
+
+        let terms = coefficients
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let exponents = (0..n).map(|j| (i >> (n - 1 - j)) & 1).collect();
+                (exponents, c)
+            })
+            .collect();
+        Self::new(n, terms, order).unwrap()
+    }
+
+    fn normalize(&mut self) {
+        let order = self.order;
+        self.terms.sort_by(|a, b| order.compare(&b.0, &a.0));
+
+        let mut merged: Vec<(Vec<usize>, FieldElement<F>)> = Vec::with_capacity(self.terms.len());
+        for (exponents, coefficient) in self.terms.drain(..) {
+            match merged.last_mut() {
+                Some((last, sum)) if *last == exponents => *sum += &coefficient,
+                _ => merged.push((exponents, coefficient)),
+            }
+        }
+        merged.retain(|(_, coefficient)| !coefficient.is_zero());
+        self.terms = merged;
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    pub fn order(&self) -> MonomialOrder {
+        self.order
+    }
+
+    pub fn terms(&self) -> &[(Vec<usize>, FieldElement<F>)] {
+        &self.terms
+    }
+
+    pub fn with_order(&self, order: MonomialOrder) -> Self {
+        Self::new(self.num_variables, self.terms.clone(), order).unwrap()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // largest monomial under the order, None for the zero polynomial
+    pub fn leading_term(&self) -> Option<&(Vec<usize>, FieldElement<F>)> {
+        self.terms.first()
+    }
+
+    // None for the zero polynomial
+    pub fn total_degree(&self) -> Option<usize> {
+        self.terms
+            .iter()
+            .map(|(exponents, _)| exponents.iter().sum())
+            .max()
+    }
+
+    // largest exponent of x_i, None for the zero polynomial
+    pub fn degree_in(&self, i: usize) -> Option<usize> {
+        self.terms.iter().map(|(exponents, _)| exponents[i]).max()
+    }
+
+    pub fn evaluate(&self, point: &[FieldElement<F>]) -> Result<FieldElement<F>, &'static str> {
+        if point.len() != self.num_variables {
+            return Err("point dimension does not match the number of variables");
+        }
+
+        let mut result = FieldElement::<F>::zero();
+        for (exponents, coefficient) in self.terms.iter() {
+            let mut term = coefficient.clone();
+            for (x, e) in point.iter().zip(exponents.iter()) {
+                term *= &x.pow_u64(*e as u64);
+            }
+            result += &term;
+        }
+        Ok(result)
+    }
+
+    // substitutes x_i = value, the variable count stays the same
+    pub fn partial_evaluate(&self, i: usize, value: &FieldElement<F>) -> Self {
+        assert!(i < self.num_variables, "variable index out of range");
+
+        let terms = self
+            .terms
+            .iter()
+            .map(|(exponents, coefficient)| {
+                let mut exponents = exponents.clone();
+                let power = value.pow_u64(exponents[i] as u64);
+                exponents[i] = 0;
+                (exponents, coefficient * &power)
+            })
+            .collect();
+        Self::new(self.num_variables, terms, self.order).unwrap()
+    }
+
+    pub fn scale(&self, factor: &FieldElement<F>) -> Self {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(exponents, coefficient)| (exponents.clone(), coefficient * factor))
+            .collect();
+        Self::new(self.num_variables, terms, self.order).unwrap()
+    }
+}
+
+impl<F> Add<&SparsePolynomial<F>> for &SparsePolynomial<F>
+where
+    F: Field,
+{
+    type Output = SparsePolynomial<F>;
+
+    fn add(self, other: &SparsePolynomial<F>) -> Self::Output {
+        assert_eq!(
+            self.num_variables, other.num_variables,
+            "number of variables differ"
+        );
+
+        let mut terms = self.terms.clone();
+        terms.extend(other.terms.iter().cloned());
+        SparsePolynomial::new(self.num_variables, terms, self.order).unwrap()
+    }
+}
+
+impl<F> Sub<&SparsePolynomial<F>> for &SparsePolynomial<F>
+where
+    F: Field,
+{
+    type Output = SparsePolynomial<F>;
+
+    fn sub(self, other: &SparsePolynomial<F>) -> Self::Output {
+        self + &(-other)
+    }
+}
+
+impl<F> Mul<&SparsePolynomial<F>> for &SparsePolynomial<F>
+where
+    F: Field,
+{
+    type Output = SparsePolynomial<F>;
+
+    fn mul(self, other: &SparsePolynomial<F>) -> Self::Output {
+        assert_eq!(
+            self.num_variables, other.num_variables,
+            "number of variables differ"
+        );
+
+        let mut terms = Vec::with_capacity(self.terms.len() * other.terms.len());
+        for (a, x) in self.terms.iter() {
+            for (b, y) in other.terms.iter() {
+                let exponents = a.iter().zip(b.iter()).map(|(i, j)| i + j).collect();
+                terms.push((exponents, x * y));
+            }
+        }
+        SparsePolynomial::new(self.num_variables, terms, self.order).unwrap()
+    }
+}
+
+impl<F> Neg for &SparsePolynomial<F>
+where
+    F: Field,
+{
+    type Output = SparsePolynomial<F>;
+
+    fn neg(self) -> Self::Output {
+        SparsePolynomial {
+            num_variables: self.num_variables,
+            order: self.order,
+            terms: self
+                .terms
+                .iter()
+                .map(|(exponents, coefficient)| (exponents.clone(), -coefficient))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MonomialOrder, SparsePolynomial};
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+    use crate::polynomial::multilinear::MultilinearPolynomial;
+    use std::cmp::Ordering;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn polynomial(terms: &[(&[usize], u64)], order: MonomialOrder) -> SparsePolynomial<Goldilocks> {
+        let n = terms[0].0.len();
+        SparsePolynomial::new(
+            n,
+            terms
+                .iter()
+                .map(|(e, c)| (e.to_vec(), Element::from(*c)))
+                .collect(),
+            order,
+        )
+        .unwrap()
+    }
+
+    fn random_point(n: usize) -> Vec<Element> {
+        (0..n).map(|_| Element::random()).collect()
+    }
+
+    #[test]
+    fn test_monomial_orders() {
+        // x y^2 vs z^3 vs x^2
+        let (a, b, c) = ([1, 2, 0], [0, 0, 3], [2, 0, 0]);
+
+        assert_eq!(MonomialOrder::Lex.compare(&a, &b), Ordering::Greater);
+        assert_eq!(MonomialOrder::Lex.compare(&c, &a), Ordering::Greater);
+        assert_eq!(MonomialOrder::GradedLex.compare(&c, &a), Ordering::Less);
+        assert_eq!(MonomialOrder::GradedLex.compare(&a, &b), Ordering::Greater);
+        assert_eq!(
+            MonomialOrder::GradedReverseLex.compare(&a, &b),
+            Ordering::Greater
+        );
+
+        // x y z vs y^3 is where graded lex and graded reverse lex disagree
+        let (d, e) = ([1, 1, 1], [0, 3, 0]);
+        assert_eq!(MonomialOrder::GradedLex.compare(&d, &e), Ordering::Greater);
+        assert_eq!(
+            MonomialOrder::GradedReverseLex.compare(&d, &e),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_normalization_and_leading_term() {
+        let p = polynomial(
+            &[
+                (&[0, 1], 3),
+                (&[2, 0], 1),
+                (&[0, 1], 4),
+                (&[1, 1], 0),
+                (&[0, 3], 2),
+            ],
+            MonomialOrder::Lex,
+        );
+
+        assert_eq!(p.terms().len(), 3);
+        assert_eq!(p.leading_term().unwrap().0, vec![2, 0]);
+        assert_eq!(
+            p.with_order(MonomialOrder::GradedLex)
+                .leading_term()
+                .unwrap()
+                .0,
+            vec![0, 3]
+        );
+        assert_eq!(p.total_degree(), Some(3));
+        assert_eq!(p.degree_in(0), Some(2));
+        assert!(p == p.with_order(MonomialOrder::GradedReverseLex));
+        assert!(SparsePolynomial::<Goldilocks>::new(
+            2,
+            vec![(vec![1], Element::one())],
+            MonomialOrder::Lex
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let order = MonomialOrder::GradedReverseLex;
+        let a = polynomial(&[(&[1, 0, 0], 2), (&[0, 1, 1], 5), (&[0, 0, 0], 1)], order);
+        let b = polynomial(&[(&[2, 0, 1], 3), (&[0, 1, 1], 7)], order);
+        let point = random_point(3);
+
+        let va = a.evaluate(&point).unwrap();
+        let vb = b.evaluate(&point).unwrap();
+
+        assert!((&a + &b).evaluate(&point).unwrap() == &va + &vb);
+        assert!((&a - &b).evaluate(&point).unwrap() == &va - &vb);
+        assert!((&a * &b).evaluate(&point).unwrap() == &va * &vb);
+        assert!((&a - &a).is_zero());
+        assert_eq!((&a * &b).total_degree(), Some(5));
+    }
+
+    #[test]
+    fn test_partial_evaluation() {
+        let order = MonomialOrder::Lex;
+        let x = SparsePolynomial::<Goldilocks>::variable(0, 2, order);
+        let y = SparsePolynomial::<Goldilocks>::variable(1, 2, order);
+        let p =
+            &(&(&x * &x) * &y) + &(&y * &SparsePolynomial::constant(2, Element::from(3), order));
+        let point = random_point(2);
+
+        let partial = p.partial_evaluate(0, &point[0]);
+
+        assert_eq!(partial.degree_in(0), Some(0));
+        assert!(partial.evaluate(&point).unwrap() == p.evaluate(&point).unwrap());
+    }
+
+    #[test]
+    fn test_from_multilinear() {
+        let m = MultilinearPolynomial::<Goldilocks>::random(4);
+        let point = random_point(4);
+
+        let p = SparsePolynomial::from_multilinear(&m, MonomialOrder::GradedLex);
+
+        assert!(p.evaluate(&point).unwrap() == m.evaluate(&point).unwrap());
+        assert!((0..4).all(|i| p.degree_in(i).unwrap() <= 1));
+    }
+}
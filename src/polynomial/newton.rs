@@ -0,0 +1,457 @@
+use crate::fields::element::FieldElement;
+use crate::polynomial::dense::Polynomial;
+use cryptography::Field;
+use cryptography::Number;
+use cryptography::Point;
+
+// arithmetic needed by the divided difference tables, implemented for field elements and Number
+pub trait InterpolationScalar: Clone + PartialEq {
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn add(&self, other: &Self) -> Self;
+
+    fn sub(&self, other: &Self) -> Self;
+
+    fn mul(&self, other: &Self) -> Self;
+
+    // None when other is zero
+    fn div(&self, other: &Self) -> Option<Self>;
+}
+
+impl<F> InterpolationScalar for FieldElement<F>
+where
+    F: Field,
+{
+    fn zero() -> Self {
+        FieldElement::<F>::zero()
+    }
+
+    fn one() -> Self {
+        FieldElement::<F>::one()
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    fn div(&self, other: &Self) -> Option<Self> {
+        other.inverse().map(|inverse| self * &inverse)
+    }
+}
+
+impl InterpolationScalar for Number {
+    fn zero() -> Self {
+        Number::Float32(0.0)
+    }
+
+    fn one() -> Self {
+        Number::Float32(1.0)
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        *self + *other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        *self - *other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        *self * *other
+    }
+
+    fn div(&self, other: &Self) -> Option<Self> {
+        if *other == Number::Float32(0.0) || *other == Number::Float64(0.0) {
+            return None;
+        }
+        Some(*self / *other)
+    }
+}
+
+// p(x) = c_0 + c_1 (x - z_0) + c_2 (x - z_0)(x - z_1) + ...
+// where z are the nodes in insertion order, repeated for derivative data
+pub struct NewtonInterpolation<T: InterpolationScalar> {
+    nodes: Vec<T>,
+    coefficients: Vec<T>,
+    // diagonal[j] is the divided difference over the last j + 1 nodes
+    diagonal: Vec<T>,
+}
+
+impl<T> NewtonInterpolation<T>
+where
+    T: InterpolationScalar,
+{
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            coefficients: Vec::new(),
+            diagonal: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    // divided differences f[z_0], f[z_0, z_1], ..., f[z_0, ..., z_n]
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    // adds one point in O(n) without recomputing the existing table
+    pub fn add_point(&mut self, x: T, y: T) -> Result<(), &'static str> {
+        self.add_point_with_derivatives(x, &[y])
+    }
+
+    // adds x together with f(x), f'(x), f''(x), ... as consecutive repeated nodes
+    pub fn add_point_with_derivatives(&mut self, x: T, values: &[T]) -> Result<(), &'static str> {
+        if values.is_empty() {
+            return Err("at least the value at the point is required");
+        }
+        if self.nodes.contains(&x) {
+            return Err("duplicate x-coordinates");
+        }
+
+        // f^(j)(x) / j!, the divided difference over j + 1 copies of x
+        let mut scaled = Vec::with_capacity(values.len());
+        // j is built as 1 + 1 + ... so that it reduces by the characteristic
+        let mut factorial = T::one();
+        let mut j = T::zero();
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                factorial = factorial.mul(&j);
+            }
+            j = j.add(&T::one());
+            scaled.push(
+                value
+                    .div(&factorial)
+                    .ok_or("derivative order is not invertible in this characteristic")?,
+            );
+        }
+
+        for copies in 0..values.len() {
+            let n = self.nodes.len();
+            let mut diagonal = Vec::with_capacity(n + 1);
+            diagonal.push(values[0].clone());
+            for j in 1..=n {
+                if j <= copies {
+                    diagonal.push(scaled[j].clone());
+                } else {
+                    let difference = diagonal[j - 1].sub(&self.diagonal[j - 1]);
+                    let denominator = x.sub(&self.nodes[n - j]);
+                    diagonal.push(
+                        difference
+                            .div(&denominator)
+                            .ok_or("duplicate x-coordinates")?,
+                    );
+                }
+            }
+
+            self.coefficients.push(diagonal[n].clone());
+            self.diagonal = diagonal;
+            self.nodes.push(x.clone());
+        }
+
+        Ok(())
+    }
+
+    // nested evaluation of the Newton form
+    pub fn evaluate(&self, x: &T) -> T {
+        let mut result = T::zero();
+        for (coefficient, node) in self.coefficients.iter().zip(self.nodes.iter()).rev() {
+            result = result.mul(&x.sub(node)).add(coefficient);
+        }
+        result
+    }
+
+    // monomial coefficients, lowest degree first
+    pub fn to_coefficients(&self) -> Vec<T> {
+        let mut result: Vec<T> = Vec::with_capacity(self.coefficients.len());
+        for (coefficient, node) in self.coefficients.iter().zip(self.nodes.iter()).rev() {
+            // result = result * (x - node) + coefficient
+            let mut next = vec![T::zero(); result.len() + 1];
+            for (i, c) in result.iter().enumerate() {
+                next[i + 1] = next[i + 1].add(c);
+                next[i] = next[i].sub(&c.mul(node));
+            }
+            next[0] = next[0].add(coefficient);
+            result = next;
+        }
+        result
+    }
+}
+
+impl<T> Default for NewtonInterpolation<T>
+where
+    T: InterpolationScalar,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> NewtonInterpolation<FieldElement<F>>
+where
+    F: Field,
+{
+    pub fn to_polynomial(&self) -> Polynomial<F> {
+        Polynomial::new(self.to_coefficients())
+    }
+}
+
+impl NewtonInterpolation<Number> {
+    pub fn from_points(points: &[Point]) -> Result<Self, &'static str> {
+        let mut interpolation = Self::new();
+        for point in points {
+            interpolation.add_point(point.x, point.y)?;
+        }
+        Ok(interpolation)
+    }
+}
+
+// interpolation matching f(x_i), f'(x_i), ... at every node, degree < total number of values
+pub struct HermiteInterpolation<T: InterpolationScalar> {
+    newton: NewtonInterpolation<T>,
+}
+
+impl<T> HermiteInterpolation<T>
+where
+    T: InterpolationScalar,
+{
+    // each entry is a node with its value followed by successive derivatives
+    pub fn new(data: Vec<(T, Vec<T>)>) -> Result<Self, &'static str> {
+        let mut newton = NewtonInterpolation::new();
+        for (x, values) in data {
+            newton.add_point_with_derivatives(x, &values)?;
+        }
+        Ok(Self { newton })
+    }
+
+    pub fn add_point(&mut self, x: T, values: &[T]) -> Result<(), &'static str> {
+        self.newton.add_point_with_derivatives(x, values)
+    }
+
+    pub fn evaluate(&self, x: &T) -> T {
+        self.newton.evaluate(x)
+    }
+
+    pub fn to_coefficients(&self) -> Vec<T> {
+        self.newton.to_coefficients()
+    }
+
+    pub fn newton_form(&self) -> &NewtonInterpolation<T> {
+        &self.newton
+    }
+}
+
+impl<F> HermiteInterpolation<FieldElement<F>>
+where
+    F: Field,
+{
+    pub fn to_polynomial(&self) -> Polynomial<F> {
+        self.newton.to_polynomial()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::binary::AesField;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn assert_close(a: Number, b: f64) {
+        let value = match a {
+            Number::Float32(value) => value as f64,
+            Number::Float64(value) => value,
+        };
+        assert!((value - b).abs() < 1e-6, "{} != {}", value, b);
+    }
+
+    #[test]
+    fn test_newton_recovers_polynomial() {
+        let expected = Polynomial::new((0..12).map(|_| Element::random()).collect());
+        let mut interpolation = NewtonInterpolation::new();
+
+        for _ in 0..12 {
+            let x = Element::random();
+            let y = expected.evaluate(&x);
+            interpolation.add_point(x, y).unwrap();
+        }
+
+        assert_eq!(interpolation.len(), 12);
+        assert!(interpolation.to_polynomial() == expected);
+        let x = Element::random();
+        assert!(interpolation.evaluate(&x) == expected.evaluate(&x));
+    }
+
+    #[test]
+    fn test_newton_incremental_insertion() {
+        let mut interpolation = NewtonInterpolation::<Element>::new();
+        interpolation
+            .add_point(Element::from(1), Element::from(3))
+            .unwrap();
+        interpolation
+            .add_point(Element::from(2), Element::from(5))
+            .unwrap();
+        let coefficients = interpolation.coefficients().to_vec();
+
+        // y = 2x + 1, the third point bends it into x^2 - x + 3
+        assert!(
+            interpolation.to_polynomial()
+                == Polynomial::new(vec![Element::from(1), Element::from(2)])
+        );
+        interpolation
+            .add_point(Element::from(3), Element::from(9))
+            .unwrap();
+
+        assert!(interpolation.coefficients()[..2] == coefficients[..]);
+        assert!(
+            interpolation.to_polynomial()
+                == Polynomial::new(vec![Element::from(3), -&Element::one(), Element::one()])
+        );
+    }
+
+    #[test]
+    fn test_newton_rejects_duplicates() {
+        let mut interpolation = NewtonInterpolation::<Element>::new();
+        interpolation
+            .add_point(Element::from(4), Element::from(1))
+            .unwrap();
+
+        assert_eq!(
+            interpolation.add_point(Element::from(4), Element::from(2)),
+            Err("duplicate x-coordinates")
+        );
+        assert_eq!(interpolation.len(), 1);
+    }
+
+    #[test]
+    fn test_newton_over_numbers() {
+        let points = vec![
+            Point {
+                x: Number::Float64(0.0),
+                y: Number::Float64(1.0),
+            },
+            Point {
+                x: Number::Float64(1.0),
+                y: Number::Float64(3.0),
+            },
+            Point {
+                x: Number::Float64(2.0),
+                y: Number::Float64(7.0),
+            },
+        ];
+
+        // x^2 + x + 1
+        let interpolation = NewtonInterpolation::from_points(&points).unwrap();
+        let coefficients = interpolation.to_coefficients();
+
+        assert_close(interpolation.evaluate(&Number::Float64(3.0)), 13.0);
+        assert_close(coefficients[0], 1.0);
+        assert_close(coefficients[1], 1.0);
+        assert_close(coefficients[2], 1.0);
+    }
+
+    #[test]
+    fn test_hermite_matches_derivatives() {
+        let expected = Polynomial::new((0..9).map(|_| Element::random()).collect());
+        let first = expected.derivative();
+        let second = first.derivative();
+
+        let data: Vec<(Element, Vec<Element>)> = (1..=3)
+            .map(|i| {
+                let x = Element::from(i);
+                let values = vec![
+                    expected.evaluate(&x),
+                    first.evaluate(&x),
+                    second.evaluate(&x),
+                ];
+                (x, values)
+            })
+            .collect();
+
+        let interpolation = HermiteInterpolation::new(data).unwrap();
+
+        assert_eq!(interpolation.newton_form().len(), 9);
+        assert!(interpolation.to_polynomial() == expected);
+    }
+
+    #[test]
+    fn test_hermite_mixed_multiplicities() {
+        let expected = Polynomial::new((0..5).map(|_| Element::random()).collect());
+        let first = expected.derivative();
+        let x0 = Element::from(7);
+        let x1 = Element::from(11);
+        let x2 = Element::from(13);
+
+        let mut interpolation = HermiteInterpolation::new(vec![(
+            x0.clone(),
+            vec![expected.evaluate(&x0), first.evaluate(&x0)],
+        )])
+        .unwrap();
+        interpolation
+            .add_point(x1.clone(), &[expected.evaluate(&x1)])
+            .unwrap();
+        interpolation
+            .add_point(x2.clone(), &[expected.evaluate(&x2), first.evaluate(&x2)])
+            .unwrap();
+
+        assert!(interpolation.to_polynomial() == expected);
+        assert!(interpolation.add_point(x1, &[Element::zero()]).is_err());
+    }
+
+    #[test]
+    fn test_hermite_over_numbers() {
+        // f(x) = x^3, f(0) = 0, f'(0) = 0, f(1) = 1, f'(1) = 3
+        let interpolation = HermiteInterpolation::new(vec![
+            (
+                Number::Float64(0.0),
+                vec![Number::Float64(0.0), Number::Float64(0.0)],
+            ),
+            (
+                Number::Float64(1.0),
+                vec![Number::Float64(1.0), Number::Float64(3.0)],
+            ),
+        ])
+        .unwrap();
+
+        assert_close(interpolation.evaluate(&Number::Float64(2.0)), 8.0);
+        let coefficients = interpolation.to_coefficients();
+        assert_close(coefficients[2], 0.0);
+        assert_close(coefficients[3], 1.0);
+    }
+
+    #[test]
+    fn test_hermite_characteristic_two() {
+        // 2! = 0 in GF(2^8), so second derivatives cannot be used
+        let x = FieldElement::<AesField>::from(3);
+        let values = vec![
+            FieldElement::<AesField>::from(1),
+            FieldElement::<AesField>::from(2),
+            FieldElement::<AesField>::from(5),
+        ];
+
+        assert!(HermiteInterpolation::new(vec![(x, values)]).is_err());
+    }
+}
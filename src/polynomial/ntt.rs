@@ -0,0 +1,261 @@
+use crate::fields::element::FieldElement;
+use cryptography::{FftField, Field};
+
+// reorders values so that index i moves to the bit reversal of i, len must be a power of two
+pub fn bit_reverse_permutation<T>(values: &mut [T]) {
+    let n = values.len();
+    if n <= 2 {
+        return;
+    }
+    assert!(n.is_power_of_two(), "length must be a power of two");
+
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+// omega^0, omega^1, ..., omega^(n / 2 - 1)
+pub fn twiddles<F: Field>(omega: &FieldElement<F>, n: usize) -> Vec<FieldElement<F>> {
+    let mut result = Vec::with_capacity(n / 2);
+    let mut current = FieldElement::<F>::one();
+    for _ in 0..n / 2 {
+        result.push(current.clone());
+        current *= omega;
+    }
+    result
+}
+
+// Gentleman-Sande decimation in frequency, natural order in, bit-reversed order out
+pub fn ntt_dif<F: Field>(values: &mut [FieldElement<F>], twiddles: &[FieldElement<F>]) {
+    let n = values.len();
+    let mut half = n / 2;
+
+    while half >= 1 {
+        let stride = n / (2 * half);
+        for start in (0..n).step_by(2 * half) {
+            for j in 0..half {
+                let a = values[start + j].clone();
+                let b = values[start + j + half].clone();
+                values[start + j] = &a + &b;
+                values[start + j + half] = &(&a - &b) * &twiddles[j * stride];
+            }
+        }
+        half /= 2;
+    }
+}
+
+// Cooley-Tukey decimation in time, bit-reversed order in, natural order out
+pub fn ntt_dit<F: Field>(values: &mut [FieldElement<F>], twiddles: &[FieldElement<F>]) {
+    let n = values.len();
+    let mut half = 1;
+
+    while half < n {
+        let stride = n / (2 * half);
+        for start in (0..n).step_by(2 * half) {
+            for j in 0..half {
+                let a = values[start + j].clone();
+                let b = &values[start + j + half] * &twiddles[j * stride];
+                values[start + j] = &a + &b;
+                values[start + j + half] = &a - &b;
+            }
+        }
+        half *= 2;
+    }
+}
+
+// evaluations at omega^0, ..., omega^(n - 1) of the polynomial with the given coefficients
+pub fn ntt<F: Field>(values: &mut [FieldElement<F>], omega: &FieldElement<F>) {
+    let n = values.len();
+    if n.is_power_of_two() {
+        bit_reverse_permutation(values);
+        ntt_dit(values, &twiddles(omega, n));
+    } else {
+        let result = ntt_mixed_radix(values, omega);
+        values.clone_from_slice(&result);
+    }
+}
+
+// inverse of ntt, omega is the same root used for the forward transform
+pub fn intt<F: Field>(values: &mut [FieldElement<F>], omega: &FieldElement<F>) {
+    let n = values.len();
+    if n == 0 {
+        return;
+    }
+    ntt(
+        values,
+        &omega.inverse().expect("root of unity is never zero"),
+    );
+
+    let n_inv = FieldElement::<F>::from(n as u64)
+        .inverse()
+        .expect("transform size must be invertible in the field");
+    for value in values.iter_mut() {
+        *value *= &n_inv;
+    }
+}
+
+fn smallest_factor(n: usize) -> usize {
+    (2..n)
+        .take_while(|d| d * d <= n)
+        .find(|d| n.is_multiple_of(*d))
+        .unwrap_or(n)
+}
+
+// recursive mixed radix Cooley-Tukey for any n, splitting off the smallest prime factor
+// and falling back to the O(n^2) transform for prime lengths
+pub fn ntt_mixed_radix<F: Field>(
+    values: &[FieldElement<F>],
+    omega: &FieldElement<F>,
+) -> Vec<FieldElement<F>> {
+    let n = values.len();
+    if n <= 1 {
+        return values.to_vec();
+    }
+
+    let radix = smallest_factor(n);
+    if radix == n {
+        return naive_dft(values, omega);
+    }
+
+    let m = n / radix;
+    let omega_radix = omega.pow_u64(radix as u64);
+
+    // sub[j] is the transform of values[j], values[j + radix], values[j + 2 * radix], ...
+    let sub: Vec<Vec<FieldElement<F>>> = (0..radix)
+        .map(|j| {
+            let part: Vec<FieldElement<F>> =
+                values.iter().skip(j).step_by(radix).cloned().collect();
+            ntt_mixed_radix(&part, &omega_radix)
+        })
+        .collect();
+
+    let mut result = Vec::with_capacity(n);
+    let mut omega_k = FieldElement::<F>::one();
+    for k in 0..n {
+        // sum over j of omega^(j * k) * sub[j][k mod m]
+        let mut sum = FieldElement::<F>::zero();
+        let mut factor = FieldElement::<F>::one();
+        for part in sub.iter() {
+            sum += &(&factor * &part[k % m]);
+            factor *= &omega_k;
+        }
+        result.push(sum);
+        omega_k *= omega;
+    }
+
+    result
+}
+
+fn naive_dft<F: Field>(
+    values: &[FieldElement<F>],
+    omega: &FieldElement<F>,
+) -> Vec<FieldElement<F>> {
+    let mut result = Vec::with_capacity(values.len());
+    let mut point = FieldElement::<F>::one();
+    for _ in 0..values.len() {
+        let mut sum = FieldElement::<F>::zero();
+        for coefficient in values.iter().rev() {
+            sum = &(&sum * &point) + coefficient;
+        }
+        result.push(sum);
+        point *= omega;
+    }
+    result
+}
+
+// root of unity matching a transform of size n
+pub fn root_of_unity<F: FftField>(n: usize) -> Option<FieldElement<F>> {
+    F::root_of_unity(n as u64).map(FieldElement::<F>::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bit_reverse_permutation, intt, naive_dft, ntt, ntt_dif, ntt_dit, ntt_mixed_radix,
+        root_of_unity, twiddles,
+    };
+    use crate::fields::element::FieldElement;
+    use crate::fields::u64_field::U64Field;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+
+    fn random_values(n: usize) -> Vec<FieldElement<Goldilocks>> {
+        (0..n)
+            .map(|_| FieldElement::<Goldilocks>::random())
+            .collect()
+    }
+
+    #[test]
+    fn test_bit_reverse_permutation() {
+        let mut values: Vec<usize> = (0..8).collect();
+        bit_reverse_permutation(&mut values);
+        assert_eq!(values, vec![0, 4, 2, 6, 1, 5, 3, 7]);
+
+        bit_reverse_permutation(&mut values);
+        assert_eq!(values, (0..8).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_ntt_matches_naive_evaluation() {
+        for log_n in 0..8 {
+            let n = 1 << log_n;
+            let omega = root_of_unity::<Goldilocks>(n).unwrap();
+            let coefficients = random_values(n);
+
+            let mut values = coefficients.clone();
+            ntt(&mut values, &omega);
+
+            assert!(values == naive_dft(&coefficients, &omega));
+        }
+    }
+
+    #[test]
+    fn test_dif_and_dit_agree() {
+        let n = 64;
+        let omega = root_of_unity::<Goldilocks>(n).unwrap();
+        let twiddles = twiddles(&omega, n);
+        let coefficients = random_values(n);
+
+        let mut dif = coefficients.clone();
+        ntt_dif(&mut dif, &twiddles);
+        bit_reverse_permutation(&mut dif);
+
+        let mut dit = coefficients.clone();
+        bit_reverse_permutation(&mut dit);
+        ntt_dit(&mut dit, &twiddles);
+
+        assert!(dif == dit);
+    }
+
+    #[test]
+    fn test_intt_roundtrip() {
+        let n = 128;
+        let omega = root_of_unity::<Goldilocks>(n).unwrap();
+        let coefficients = random_values(n);
+
+        let mut values = coefficients.clone();
+        ntt(&mut values, &omega);
+        intt(&mut values, &omega);
+
+        assert!(values == coefficients);
+    }
+
+    #[test]
+    fn test_mixed_radix_matches_naive_evaluation() {
+        for n in [3, 5, 6, 12, 15, 17, 20, 51] {
+            let omega = root_of_unity::<Goldilocks>(n).unwrap();
+            let coefficients = random_values(n);
+
+            assert!(ntt_mixed_radix(&coefficients, &omega) == naive_dft(&coefficients, &omega));
+
+            let mut values = coefficients.clone();
+            ntt(&mut values, &omega);
+            intt(&mut values, &omega);
+            assert!(values == coefficients);
+        }
+    }
+}
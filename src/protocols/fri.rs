@@ -0,0 +1,548 @@
+use crate::commitments::merkle::{write_blob, MerkleHasher, MerkleMultiProof, MerkleTree, Reader};
+use crate::fields::batch::batch_inverse;
+use crate::fields::bytes::{elements_from_bytes, elements_to_bytes, FieldBytes};
+use crate::fields::element::FieldElement;
+use crate::polynomial::domain::EvaluationDomain;
+use crate::protocols::transcript::{Duplex, Transcript};
+use cryptography::{FftField, HashFunction};
+use std::marker::PhantomData;
+
+pub struct FriConfig {
+    // values merged per fold, a power of two
+    pub folding_factor: usize,
+    // evaluation domain size over the degree bound, a power of two
+    pub blowup: usize,
+    pub num_queries: usize,
+    // folding stops once the degree bound is at most this, the remaining polynomial is
+    // sent in the clear
+    pub remainder_degree_bound: usize,
+}
+
+impl FriConfig {
+    pub fn new(
+        folding_factor: usize,
+        blowup: usize,
+        num_queries: usize,
+        remainder_degree_bound: usize,
+    ) -> Result<Self, &'static str> {
+        if folding_factor < 2 || !folding_factor.is_power_of_two() {
+            return Err("folding factor must be a power of two of at least 2");
+        }
+        if blowup < 2 || !blowup.is_power_of_two() {
+            return Err("blowup must be a power of two of at least 2");
+        }
+        if num_queries == 0 {
+            return Err("at least one query is required");
+        }
+        if remainder_degree_bound == 0 {
+            return Err("remainder degree bound must be positive");
+        }
+
+        Ok(Self {
+            folding_factor,
+            blowup,
+            num_queries,
+            remainder_degree_bound,
+        })
+    }
+}
+
+// openings of one committed layer: for every distinct leaf hit by a query, the
+// folding_factor values of its coset, in the order of proof.indices
+pub struct FriLayerProof<F: FftField> {
+    pub values: Vec<Vec<FieldElement<F>>>,
+    pub proof: MerkleMultiProof,
+}
+
+pub struct FriProof<F: FftField> {
+    pub roots: Vec<Vec<u8>>,
+    pub layers: Vec<FriLayerProof<F>>,
+    // coefficients of the last folded polynomial
+    pub remainder: Vec<FieldElement<F>>,
+}
+
+// number of roots (u64), each root as a blob, then for every layer the number of
+// opened cosets (u64), each coset's elements as a blob and the Merkle proof as a blob,
+// finally the remainder as a blob. Blobs are a u64 length followed by the bytes.
+impl<F> FriProof<F>
+where
+    F: FftField + FieldBytes,
+{
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.roots.len() as u64).to_le_bytes().to_vec();
+        for root in self.roots.iter() {
+            write_blob(&mut bytes, root);
+        }
+        for layer in self.layers.iter() {
+            bytes.extend_from_slice(&(layer.values.len() as u64).to_le_bytes());
+            for coset in layer.values.iter() {
+                write_blob(&mut bytes, &elements_to_bytes(coset));
+            }
+            write_blob(&mut bytes, &layer.proof.to_bytes());
+        }
+        write_blob(&mut bytes, &elements_to_bytes(&self.remainder));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader::new(bytes);
+        let proof = Self::read(&mut reader)?;
+        reader.finish()?;
+        Ok(proof)
+    }
+
+    pub(crate) fn read(reader: &mut Reader) -> Result<Self, &'static str> {
+        let count = reader.u64()?;
+        let mut roots = Vec::new();
+        for _ in 0..count {
+            roots.push(reader.blob()?.to_vec());
+        }
+
+        let mut layers = Vec::with_capacity(roots.len());
+        for _ in 0..roots.len() {
+            let count = reader.u64()?;
+            let mut values = Vec::new();
+            for _ in 0..count {
+                values.push(elements_from_bytes(reader.blob()?)?);
+            }
+            let proof = MerkleMultiProof::from_bytes(reader.blob()?)?;
+            layers.push(FriLayerProof { values, proof });
+        }
+        let remainder = elements_from_bytes(reader.blob()?)?;
+
+        Ok(Self {
+            roots,
+            layers,
+            remainder,
+        })
+    }
+}
+
+// one committed function: the values on offset * <ω> of size n, leaf i holds the
+// coset {i, i + n/k, ..., i + (k - 1) n/k} of positions, whose points x ζ^t with
+// ζ = ω^(n/k) all map to x^k
+struct Layer<F: FftField, H: HashFunction> {
+    values: Vec<FieldElement<F>>,
+    tree: MerkleTree<H>,
+}
+
+// FRI proximity test over a coset of a two-adic subgroup, the committed function
+// is accepted when it is close to a polynomial of degree below degree_bound
+pub struct Fri<F: FftField + FieldBytes, H: HashFunction + Default> {
+    config: FriConfig,
+    degree_bound: usize,
+    // (domain, degree bound) of every folded layer and finally of the remainder
+    domains: Vec<(EvaluationDomain<F>, usize)>,
+    hasher: PhantomData<H>,
+}
+
+impl<F, H> Fri<F, H>
+where
+    F: FftField + FieldBytes,
+    H: HashFunction + Default,
+{
+    pub fn new(config: FriConfig, degree_bound: usize) -> Result<Self, &'static str> {
+        if degree_bound == 0 || !degree_bound.is_power_of_two() {
+            return Err("degree bound must be a power of two");
+        }
+
+        let k = config.folding_factor;
+        let mut offset = FieldElement::<F>::new(F::multiplicative_generator());
+        let mut size = degree_bound * config.blowup;
+        let mut bound = degree_bound;
+        let mut domains = Vec::new();
+        loop {
+            domains.push((EvaluationDomain::with_offset(size, offset.clone())?, bound));
+            if bound <= config.remainder_degree_bound {
+                break;
+            }
+            offset = offset.pow_u64(k as u64);
+            size /= k;
+            bound = bound.div_ceil(k);
+        }
+
+        Ok(Self {
+            config,
+            degree_bound,
+            domains,
+            hasher: PhantomData,
+        })
+    }
+
+    pub fn degree_bound(&self) -> usize {
+        self.degree_bound
+    }
+
+    // the domain the committed function is evaluated on
+    pub fn domain(&self) -> &EvaluationDomain<F> {
+        &self.domains[0].0
+    }
+
+    fn num_layers(&self) -> usize {
+        self.domains.len() - 1
+    }
+
+    // f(X) = sum_j X^j f_j(X^k), the fold at y = x^k is sum_j β^j f_j(y), computed
+    // from the values v_t = f(x ζ^t) by an inverse DFT of size k
+    fn fold_coset(
+        values: &[FieldElement<F>],
+        x_inv: &FieldElement<F>,
+        zeta_inv: &FieldElement<F>,
+        beta: &FieldElement<F>,
+    ) -> FieldElement<F> {
+        let ratio = beta * x_inv;
+        let mut result = FieldElement::<F>::zero();
+        let mut ratio_power = FieldElement::<F>::one();
+        let mut zeta_power = FieldElement::<F>::one();
+        for _ in 0..values.len() {
+            // k f_j(y) x^j = sum_t v_t ζ^(-tj)
+            let mut coefficient = FieldElement::<F>::zero();
+            let mut twiddle = FieldElement::<F>::one();
+            for value in values {
+                coefficient += &(value * &twiddle);
+                twiddle *= &zeta_power;
+            }
+            result += &(&coefficient * &ratio_power);
+            ratio_power *= &ratio;
+            zeta_power *= zeta_inv;
+        }
+
+        let k_inv = FieldElement::<F>::from(values.len() as u64)
+            .inverse()
+            .unwrap();
+        &result * &k_inv
+    }
+
+    // ζ^-1 and x^-1 for the leaves of a layer
+    fn coset_inverses(
+        domain: &EvaluationDomain<F>,
+        k: usize,
+    ) -> (FieldElement<F>, Vec<FieldElement<F>>) {
+        let leaves = domain.size() / k;
+        let zeta_inv = domain.element(leaves).inverse().unwrap();
+        let xs: Vec<FieldElement<F>> = domain.coset_elements().into_iter().take(leaves).collect();
+        (zeta_inv, batch_inverse(&xs))
+    }
+
+    fn commit(values: Vec<FieldElement<F>>, k: usize) -> Layer<F, H> {
+        let leaves = values.len() / k;
+        let cosets: Vec<Vec<u8>> = (0..leaves)
+            .map(|i| {
+                let coset: Vec<FieldElement<F>> =
+                    (0..k).map(|t| values[i + t * leaves].clone()).collect();
+                elements_to_bytes(&coset)
+            })
+            .collect();
+        let tree = MerkleTree::new(&cosets, 2, H::default()).unwrap();
+
+        Layer { values, tree }
+    }
+
+    // uniform positions in the first domain, its size is a power of two so reducing
+    // 64 random bits is unbiased
+    fn query_positions<D: Duplex>(&self, transcript: &mut Transcript<D>) -> Vec<usize> {
+        let size = self.domain().size() as u64;
+        (0..self.config.num_queries)
+            .map(|_| {
+                let bytes = transcript.challenge_bytes(b"fri query", 8);
+                (u64::from_le_bytes(bytes.try_into().unwrap()) % size) as usize
+            })
+            .collect()
+    }
+
+    // distinct leaves of a layer with n / k leaves hit by the given positions
+    fn leaf_indices(positions: &[usize], leaves: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = positions.iter().map(|p| p % leaves).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+
+    // evaluations of the committed function over domain(), returns the proof and the
+    // query positions
+    pub fn prove<D: Duplex>(
+        &self,
+        evaluations: Vec<FieldElement<F>>,
+        transcript: &mut Transcript<D>,
+    ) -> Result<(FriProof<F>, Vec<usize>), &'static str> {
+        if evaluations.len() != self.domain().size() {
+            return Err("one evaluation per domain point is required");
+        }
+
+        let k = self.config.folding_factor;
+        let mut layers: Vec<Layer<F, H>> = Vec::with_capacity(self.num_layers());
+        let mut current = evaluations;
+        for (domain, _) in self.domains[..self.num_layers()].iter() {
+            let layer = Self::commit(current, k);
+            transcript.append_message(b"fri layer", layer.tree.root());
+            let beta = transcript.challenge_field_element(b"fri folding");
+
+            let leaves = domain.size() / k;
+            let (zeta_inv, x_invs) = Self::coset_inverses(domain, k);
+            current = (0..leaves)
+                .map(|i| {
+                    let coset: Vec<FieldElement<F>> = (0..k)
+                        .map(|t| layer.values[i + t * leaves].clone())
+                        .collect();
+                    Self::fold_coset(&coset, &x_invs[i], &zeta_inv, &beta)
+                })
+                .collect();
+            layers.push(layer);
+        }
+
+        let (last_domain, last_bound) = self.domains.last().unwrap();
+        let mut remainder = last_domain.coset_ifft(&current);
+        remainder.truncate(*last_bound);
+        transcript.append_field_elements(b"fri remainder", &remainder);
+
+        let positions = self.query_positions(transcript);
+        let mut layer_proofs = Vec::with_capacity(layers.len());
+        let mut current_positions = positions.clone();
+        for layer in layers.iter() {
+            let leaves = layer.values.len() / k;
+            let indices = Self::leaf_indices(&current_positions, leaves);
+            let values = indices
+                .iter()
+                .map(|i| {
+                    (0..k)
+                        .map(|t| layer.values[i + t * leaves].clone())
+                        .collect()
+                })
+                .collect();
+            layer_proofs.push(FriLayerProof {
+                values,
+                proof: layer.tree.prove_batch(&indices)?,
+            });
+            current_positions = current_positions.iter().map(|p| p % leaves).collect();
+        }
+
+        let proof = FriProof {
+            roots: layers.iter().map(|l| l.tree.root().to_vec()).collect(),
+            layers: layer_proofs,
+            remainder,
+        };
+        Ok((proof, positions))
+    }
+
+    // replays the transcript and checks every query through all layers, returns the
+    // query positions with the committed function's values there
+    pub fn verify<D: Duplex>(
+        &self,
+        proof: &FriProof<F>,
+        transcript: &mut Transcript<D>,
+    ) -> Result<Vec<(usize, FieldElement<F>)>, &'static str> {
+        let k = self.config.folding_factor;
+        if proof.roots.len() != self.num_layers() || proof.layers.len() != self.num_layers() {
+            return Err("proof has the wrong number of layers");
+        }
+        let (last_domain, last_bound) = self.domains.last().unwrap();
+        if proof.remainder.len() > *last_bound {
+            return Err("remainder degree is too large");
+        }
+
+        let mut betas = Vec::with_capacity(self.num_layers());
+        for root in proof.roots.iter() {
+            transcript.append_message(b"fri layer", root);
+            betas.push(transcript.challenge_field_element::<F>(b"fri folding"));
+        }
+        transcript.append_field_elements(b"fri remainder", &proof.remainder);
+        let positions = self.query_positions(transcript);
+
+        let mut merkle = MerkleHasher::new(H::default(), 2)?;
+        let mut current_positions = positions.clone();
+        // value of the current layer at each query position, unknown for the first
+        let mut expected: Option<Vec<FieldElement<F>>> = None;
+        let mut queried = Vec::new();
+        for (l, (domain, _)) in self.domains[..self.num_layers()].iter().enumerate() {
+            let layer = &proof.layers[l];
+            let leaves = domain.size() / k;
+            let indices = Self::leaf_indices(&current_positions, leaves);
+            if layer.proof.indices != indices || layer.values.len() != indices.len() {
+                return Err("layer opens the wrong positions");
+            }
+            if layer.values.iter().any(|coset| coset.len() != k) {
+                return Err("layer opening has the wrong size");
+            }
+            let leaf_bytes: Vec<Vec<u8>> =
+                layer.values.iter().map(|v| elements_to_bytes(v)).collect();
+            if !merkle.verify_batch(&proof.roots[l], &leaf_bytes, &layer.proof) {
+                return Err("layer opening does not match the commitment");
+            }
+
+            let zeta_inv = domain.element(leaves).inverse().unwrap();
+            let mut folded = Vec::with_capacity(current_positions.len());
+            for (q, position) in current_positions.iter().enumerate() {
+                let leaf = position % leaves;
+                let coset = &layer.values[indices.binary_search(&leaf).unwrap()];
+                let value = &coset[position / leaves];
+                match &expected {
+                    Some(expected) if expected[q] != *value => {
+                        return Err("folding is inconsistent");
+                    }
+                    Some(_) => {}
+                    None => queried.push((*position, value.clone())),
+                }
+
+                let x_inv = (domain.offset() * &domain.element(leaf)).inverse().unwrap();
+                folded.push(Self::fold_coset(coset, &x_inv, &zeta_inv, &betas[l]));
+            }
+
+            expected = Some(folded);
+            current_positions = current_positions.iter().map(|p| p % leaves).collect();
+        }
+
+        for (q, position) in current_positions.iter().enumerate() {
+            let x = last_domain.offset() * &last_domain.element(*position);
+            let value = proof
+                .remainder
+                .iter()
+                .rev()
+                .fold(FieldElement::<F>::zero(), |acc, c| &(&acc * &x) + c);
+            match &expected {
+                Some(expected) if expected[q] != value => {
+                    return Err("remainder does not match the last layer");
+                }
+                Some(_) => {}
+                None => queried.push((*position, value)),
+            }
+        }
+
+        Ok(queried)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::u64_field::U64Field;
+    use crate::hash::md5::MessageDigestAlgorithm;
+    use crate::hash::sha3::Sha3_256;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn random_values(n: usize) -> Vec<Element> {
+        (0..n).map(|_| Element::random()).collect()
+    }
+
+    fn low_degree_evaluations(
+        fri: &Fri<Goldilocks, Sha3_256>,
+        degree_bound: usize,
+    ) -> Vec<Element> {
+        fri.domain().coset_fft(&random_values(degree_bound))
+    }
+
+    #[test]
+    fn test_accepts_low_degree() {
+        for (k, blowup, remainder) in [(2, 4, 1), (4, 2, 4), (8, 8, 2)] {
+            let config = FriConfig::new(k, blowup, 16, remainder).unwrap();
+            let fri = Fri::<Goldilocks, Sha3_256>::new(config, 64).unwrap();
+            let evaluations = low_degree_evaluations(&fri, 64);
+
+            let (proof, positions) = fri
+                .prove(evaluations.clone(), &mut Transcript::keccak(b"test"))
+                .unwrap();
+            let queried = fri
+                .verify(&proof, &mut Transcript::keccak(b"test"))
+                .unwrap();
+
+            assert_eq!(queried.len(), 16);
+            for ((position, value), expected) in queried.iter().zip(positions.iter()) {
+                assert_eq!(position, expected);
+                assert!(*value == evaluations[*position]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_high_degree() {
+        let config = FriConfig::new(4, 4, 16, 2).unwrap();
+        let fri = Fri::<Goldilocks, MessageDigestAlgorithm>::new(config, 32).unwrap();
+
+        // random functions and polynomials of twice the allowed degree
+        let random = random_values(fri.domain().size());
+        let too_high = fri.domain().coset_fft(&random_values(64));
+        for evaluations in [random, too_high] {
+            let (proof, _) = fri
+                .prove(evaluations, &mut Transcript::keccak(b"test"))
+                .unwrap();
+            assert!(fri
+                .verify(&proof, &mut Transcript::keccak(b"test"))
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_rejects_tampering() {
+        let config = FriConfig::new(2, 4, 8, 1).unwrap();
+        let fri = Fri::<Goldilocks, Sha3_256>::new(config, 16).unwrap();
+        let evaluations = low_degree_evaluations(&fri, 16);
+        let prove = || {
+            fri.prove(evaluations.clone(), &mut Transcript::keccak(b"test"))
+                .unwrap()
+                .0
+        };
+
+        let mut proof = prove();
+        proof.layers[1].values[0][1] += &Element::one();
+        assert!(fri
+            .verify(&proof, &mut Transcript::keccak(b"test"))
+            .is_err());
+
+        let mut proof = prove();
+        proof.remainder[0] += &Element::one();
+        assert!(fri
+            .verify(&proof, &mut Transcript::keccak(b"test"))
+            .is_err());
+
+        let mut proof = prove();
+        proof.roots.pop();
+        assert!(fri
+            .verify(&proof, &mut Transcript::keccak(b"test"))
+            .is_err());
+
+        // challenges bound to a different context
+        let proof = prove();
+        assert!(fri
+            .verify(&proof, &mut Transcript::keccak(b"other"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let config = FriConfig::new(4, 4, 8, 2).unwrap();
+        let fri = Fri::<Goldilocks, Sha3_256>::new(config, 32).unwrap();
+        let evaluations = low_degree_evaluations(&fri, 32);
+        let (proof, _) = fri
+            .prove(evaluations, &mut Transcript::keccak(b"test"))
+            .unwrap();
+
+        let bytes = proof.to_bytes();
+        let decoded = FriProof::<Goldilocks>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(fri
+            .verify(&decoded, &mut Transcript::keccak(b"test"))
+            .is_ok());
+
+        assert!(FriProof::<Goldilocks>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(FriProof::<Goldilocks>::from_bytes(&extended).is_err());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(FriConfig::new(3, 4, 8, 1).is_err());
+        assert!(FriConfig::new(2, 1, 8, 1).is_err());
+        assert!(FriConfig::new(2, 4, 0, 1).is_err());
+
+        let config = FriConfig::new(2, 4, 8, 1).unwrap();
+        assert!(Fri::<Goldilocks, Sha3_256>::new(config, 24).is_err());
+
+        let config = FriConfig::new(2, 4, 8, 1).unwrap();
+        let fri = Fri::<Goldilocks, Sha3_256>::new(config, 16).unwrap();
+        assert!(fri
+            .prove(random_values(10), &mut Transcript::keccak(b"test"))
+            .is_err());
+    }
+}
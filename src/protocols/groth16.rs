@@ -0,0 +1,352 @@
+use crate::arithmetization::r1cs::{ConstraintSystem, SparseMatrix};
+use crate::curves::bn254::{multi_pairing, scalar_mul, Fr, G1, G2};
+use crate::curves::weierstrass::{JacobianPoint, WeierstrassConfig};
+use crate::fields::batch::batch_inverse;
+use crate::fields::element::FieldElement;
+use crate::polynomial::domain::EvaluationDomain;
+
+type Scalar = FieldElement<Fr>;
+
+// the toxic waste of the setup, whoever knows it can forge proofs
+pub struct Trapdoor {
+    pub tau: Scalar,
+    pub alpha: Scalar,
+    pub beta: Scalar,
+    pub gamma: Scalar,
+    pub delta: Scalar,
+}
+
+impl Trapdoor {
+    pub fn random() -> Self {
+        Self {
+            tau: Scalar::random(),
+            alpha: Scalar::random(),
+            beta: Scalar::random(),
+            gamma: Scalar::random(),
+            delta: Scalar::random(),
+        }
+    }
+}
+
+pub struct ProvingKey {
+    pub alpha_g1: G1,
+    pub beta_g1: G1,
+    pub beta_g2: G2,
+    pub delta_g1: G1,
+    pub delta_g2: G2,
+    // u_i(τ), v_i(τ) for every variable
+    pub a_query: Vec<G1>,
+    pub b_g1_query: Vec<G1>,
+    pub b_g2_query: Vec<G2>,
+    // τ^i Z(τ) / δ
+    pub h_query: Vec<G1>,
+    // (β u_i(τ) + α v_i(τ) + w_i(τ)) / δ for the witnesses
+    pub l_query: Vec<G1>,
+    pub domain_size: usize,
+}
+
+pub struct VerifyingKey {
+    pub alpha_g1: G1,
+    pub beta_g2: G2,
+    pub gamma_g2: G2,
+    pub delta_g2: G2,
+    // (β u_i(τ) + α v_i(τ) + w_i(τ)) / γ for the constant one and the public inputs
+    pub ic: Vec<G1>,
+}
+
+pub struct Proof {
+    pub a: G1,
+    pub b: G2,
+    pub c: G1,
+}
+
+// The QAP has one row per constraint followed by one row per input with a 1 in A,
+// which keeps the input polynomials linearly independent. Rows are interpolated
+// over the subgroup of the next power of two.
+struct Qap {
+    matrices: [SparseMatrix<Fr>; 3],
+    num_inputs: usize,
+    num_variables: usize,
+    domain: EvaluationDomain<Fr>,
+}
+
+impl Qap {
+    fn new(cs: &ConstraintSystem<Fr>) -> Result<Self, &'static str> {
+        let [mut a, mut b, mut c] = cs.matrices();
+        for i in 0..cs.num_inputs() {
+            a.push(vec![(i, Scalar::one())]);
+            b.push(Vec::new());
+            c.push(Vec::new());
+        }
+        let domain = EvaluationDomain::new(a.len().next_power_of_two())?;
+
+        Ok(Self {
+            matrices: [a, b, c],
+            num_inputs: cs.num_inputs(),
+            num_variables: cs.num_variables(),
+            domain,
+        })
+    }
+
+    // u_i(x), v_i(x), w_i(x) for every variable from the Lagrange basis at x
+    fn evaluate_at(&self, x: &Scalar) -> Result<[Vec<Scalar>; 3], &'static str> {
+        let n = self.domain.size();
+        let elements = self.domain.elements();
+        let differences: Vec<Scalar> = elements.iter().map(|omega| x - omega).collect();
+        if differences.iter().any(|d| d.is_zero()) {
+            return Err("evaluation point lies in the domain");
+        }
+
+        // L_j(x) = Z(x) ω^j / (n (x - ω^j))
+        let scale = &self.domain.evaluate_vanishing_polynomial(x)
+            * &Scalar::from(n as u64).inverse().unwrap();
+        let lagrange: Vec<Scalar> = batch_inverse(&differences)
+            .iter()
+            .zip(elements.iter())
+            .map(|(inverse, omega)| &(inverse * omega) * &scale)
+            .collect();
+
+        Ok(self.matrices.each_ref().map(|matrix| {
+            let mut result = vec![Scalar::zero(); self.num_variables];
+            for (row, l) in matrix.iter().zip(lagrange.iter()) {
+                for (column, coefficient) in row {
+                    result[*column] += &(coefficient * l);
+                }
+            }
+            result
+        }))
+    }
+
+    // coefficients of h = (A B - C) / Z, computed over the coset where Z is the
+    // non-zero constant offset^n - 1
+    fn quotient(&self, assignment: &[Scalar]) -> Vec<Scalar> {
+        let n = self.domain.size();
+        let [a, b, c] = self.matrices.each_ref().map(|matrix| {
+            let mut evaluations: Vec<Scalar> = matrix
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .fold(Scalar::zero(), |acc, (column, coefficient)| {
+                            &acc + &(coefficient * &assignment[*column])
+                        })
+                })
+                .collect();
+            evaluations.resize(n, Scalar::zero());
+            self.domain.coset_fft(&self.domain.ifft(&evaluations))
+        });
+
+        let z_inv = self
+            .domain
+            .evaluate_vanishing_polynomial(self.domain.offset())
+            .inverse()
+            .unwrap();
+        let h: Vec<Scalar> = a
+            .iter()
+            .zip(b.iter())
+            .zip(c.iter())
+            .map(|((a, b), c)| &(&(a * b) - c) * &z_inv)
+            .collect();
+        self.domain.coset_ifft(&h)
+    }
+}
+
+// Σ scalars_i points_i
+fn msm<C: WeierstrassConfig>(points: &[JacobianPoint<C>], scalars: &[Scalar]) -> JacobianPoint<C> {
+    points
+        .iter()
+        .zip(scalars.iter())
+        .filter(|(_, s)| !s.is_zero())
+        .fold(JacobianPoint::identity(), |acc, (point, s)| {
+            &acc + &scalar_mul(point, s)
+        })
+}
+
+// generates the keys for the circuit shape of cs, its assignment is ignored
+pub fn setup(
+    cs: &ConstraintSystem<Fr>,
+    trapdoor: &Trapdoor,
+) -> Result<(ProvingKey, VerifyingKey), &'static str> {
+    let qap = Qap::new(cs)?;
+    let [u, v, w] = qap.evaluate_at(&trapdoor.tau)?;
+    let g1 = G1::generator();
+    let g2 = G2::generator();
+
+    let gamma_inv = trapdoor.gamma.inverse().ok_or("gamma must be non-zero")?;
+    let delta_inv = trapdoor.delta.inverse().ok_or("delta must be non-zero")?;
+    let combined = |i: usize, scale: &Scalar| {
+        let sum = &(&(&trapdoor.beta * &u[i]) + &(&trapdoor.alpha * &v[i])) + &w[i];
+        scalar_mul(&g1, &(&sum * scale))
+    };
+
+    let n = qap.domain.size();
+    let mut h_scalar = &qap.domain.evaluate_vanishing_polynomial(&trapdoor.tau) * &delta_inv;
+    let mut h_query = Vec::with_capacity(n - 1);
+    for _ in 0..n - 1 {
+        h_query.push(scalar_mul(&g1, &h_scalar));
+        h_scalar *= &trapdoor.tau;
+    }
+
+    let pk = ProvingKey {
+        alpha_g1: scalar_mul(&g1, &trapdoor.alpha),
+        beta_g1: scalar_mul(&g1, &trapdoor.beta),
+        beta_g2: scalar_mul(&g2, &trapdoor.beta),
+        delta_g1: scalar_mul(&g1, &trapdoor.delta),
+        delta_g2: scalar_mul(&g2, &trapdoor.delta),
+        a_query: u.iter().map(|s| scalar_mul(&g1, s)).collect(),
+        b_g1_query: v.iter().map(|s| scalar_mul(&g1, s)).collect(),
+        b_g2_query: v.iter().map(|s| scalar_mul(&g2, s)).collect(),
+        h_query,
+        l_query: (qap.num_inputs..qap.num_variables)
+            .map(|i| combined(i, &delta_inv))
+            .collect(),
+        domain_size: n,
+    };
+    let vk = VerifyingKey {
+        alpha_g1: pk.alpha_g1.clone(),
+        beta_g2: pk.beta_g2.clone(),
+        gamma_g2: scalar_mul(&g2, &trapdoor.gamma),
+        delta_g2: pk.delta_g2.clone(),
+        ic: (0..qap.num_inputs)
+            .map(|i| combined(i, &gamma_inv))
+            .collect(),
+    };
+    Ok((pk, vk))
+}
+
+// proves knowledge of the witnesses of cs for its public inputs
+pub fn prove(pk: &ProvingKey, cs: &ConstraintSystem<Fr>) -> Result<Proof, &'static str> {
+    if pk.a_query.len() != cs.num_variables() || pk.l_query.len() != cs.num_witnesses() {
+        return Err("proving key does not match the constraint system");
+    }
+    if !cs.is_satisfied() {
+        return Err("constraint system is not satisfied");
+    }
+    let qap = Qap::new(cs)?;
+    if qap.domain.size() != pk.domain_size {
+        return Err("proving key does not match the constraint system");
+    }
+
+    let assignment = cs.assignment();
+    let witnesses = &assignment[cs.num_inputs()..];
+    let h = qap.quotient(&assignment);
+    let r = Scalar::random();
+    let s = Scalar::random();
+
+    // A = α + Σ a_i u_i(τ) + r δ, B = β + Σ a_i v_i(τ) + s δ
+    let a = &(&pk.alpha_g1 + &msm(&pk.a_query, &assignment)) + &scalar_mul(&pk.delta_g1, &r);
+    let b = &(&pk.beta_g2 + &msm(&pk.b_g2_query, &assignment)) + &scalar_mul(&pk.delta_g2, &s);
+    let b_g1 = &(&pk.beta_g1 + &msm(&pk.b_g1_query, &assignment)) + &scalar_mul(&pk.delta_g1, &s);
+
+    // C = Σ a_i L_i + h(τ) Z(τ) / δ + s A + r B - r s δ
+    let c = &(&(&(&msm(&pk.l_query, witnesses) + &msm(&pk.h_query, &h[..pk.h_query.len()]))
+        + &scalar_mul(&a, &s))
+        + &scalar_mul(&b_g1, &r))
+        - &scalar_mul(&pk.delta_g1, &(&r * &s));
+
+    Ok(Proof { a, b, c })
+}
+
+// e(A, B) = e(α, β) e(Σ x_i IC_i, γ) e(C, δ) with x_0 = 1
+pub fn verify(vk: &VerifyingKey, public_inputs: &[Scalar], proof: &Proof) -> bool {
+    if public_inputs.len() + 1 != vk.ic.len() {
+        return false;
+    }
+    let ic = &vk.ic[0] + &msm(&vk.ic[1..], public_inputs);
+
+    multi_pairing(&[
+        (proof.a.clone(), proof.b.clone()),
+        (-&vk.alpha_g1, vk.beta_g2.clone()),
+        (-&ic, vk.gamma_g2.clone()),
+        (-&proof.c, vk.delta_g2.clone()),
+    ])
+    .is_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetization::r1cs::{LinearCombination, Variable};
+
+    type Lc = LinearCombination<Fr>;
+
+    // x^3 + x + 5 = out with public out
+    fn cubic(x: u64, out: u64) -> ConstraintSystem<Fr> {
+        let mut cs = ConstraintSystem::new();
+        let out = cs.alloc_input(Scalar::from(out));
+        let x = cs.alloc(Scalar::from(x));
+
+        let x_squared = cs.mul(&x.into(), &x.into());
+        let x_cubed = cs.mul(&x_squared.into(), &x.into());
+        let lhs = &(&Lc::from(x_cubed) + &x.into()) + &Lc::constant(Scalar::from(5));
+        cs.enforce_equal(&lhs, &out.into());
+        cs
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let cs = cubic(3, 35);
+        let (pk, vk) = setup(&cs, &Trapdoor::random()).unwrap();
+        let proof = prove(&pk, &cs).unwrap();
+
+        assert!(verify(&vk, &[Scalar::from(35)], &proof));
+        assert!(!verify(&vk, &[Scalar::from(36)], &proof));
+        assert!(!verify(&vk, &[], &proof));
+
+        // proofs are re-randomized, a second one differs and still verifies
+        let other = prove(&pk, &cs).unwrap();
+        assert!(other.a != proof.a);
+        assert!(verify(&vk, &[Scalar::from(35)], &other));
+
+        let forged = Proof {
+            a: proof.a.clone(),
+            b: proof.b.clone(),
+            c: other.c,
+        };
+        assert!(!verify(&vk, &[Scalar::from(35)], &forged));
+    }
+
+    #[test]
+    fn test_rejects_bad_witness() {
+        let cs = cubic(3, 35);
+        let (pk, _) = setup(&cs, &Trapdoor::random()).unwrap();
+
+        let mut bad = cubic(3, 35);
+        bad.set_value(Variable::Witness(0), Scalar::from(4));
+        assert!(prove(&pk, &bad).is_err());
+
+        // a circuit with more variables does not fit the key
+        let mut other = cubic(3, 35);
+        let bit = other.alloc_boolean(true);
+        other.enforce_boolean(bit);
+        assert!(prove(&pk, &other).is_err());
+    }
+
+    #[test]
+    fn test_quotient() {
+        let cs = cubic(3, 35);
+        let qap = Qap::new(&cs).unwrap();
+        let assignment = cs.assignment();
+        let h = qap.quotient(&assignment);
+
+        // A(x) B(x) - C(x) = h(x) Z(x) at a random point
+        let x = Scalar::random();
+        let [u, v, w] = qap.evaluate_at(&x).unwrap();
+        let dot = |polys: &[Scalar]| {
+            polys
+                .iter()
+                .zip(assignment.iter())
+                .fold(Scalar::zero(), |acc, (p, a)| &acc + &(p * a))
+        };
+        let h_x = h
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |acc, c| &(&acc * &x) + c);
+
+        assert_eq!(qap.domain.size(), 8);
+        assert!(h[qap.domain.size() - 1].is_zero());
+        assert!(
+            &(&dot(&u) * &dot(&v)) - &dot(&w)
+                == &h_x * &qap.domain.evaluate_vanishing_polynomial(&x)
+        );
+    }
+}
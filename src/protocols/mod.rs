@@ -0,0 +1,6 @@
+pub mod fri;
+pub mod groth16;
+pub mod plonk;
+pub mod stark;
+pub mod sumcheck;
+pub mod transcript;
@@ -0,0 +1,585 @@
+use crate::arithmetization::plonk::Circuit;
+use crate::commitments::kzg::Kzg;
+use crate::curves::bn254::{Fr, G1};
+use crate::fields::batch::batch_inverse;
+use crate::fields::element::FieldElement;
+use crate::polynomial::dense::Polynomial;
+use crate::polynomial::domain::EvaluationDomain;
+use crate::protocols::transcript::{Duplex, Transcript};
+use cryptography::FftField;
+
+type Scalar = FieldElement<Fr>;
+
+// the three wire columns are told apart by the cosets H, k2 H and k3 H with
+// k2 = g and k3 = g^2 for the multiplicative generator g
+fn coset_shifts() -> [Scalar; 3] {
+    let g = Scalar::new(Fr::multiplicative_generator());
+    [Scalar::one(), g.clone(), g.square()]
+}
+
+// selectors q_l, q_r, q_o, q_m, q_c and the permutation polynomials
+#[derive(Clone)]
+pub struct VerifyingKey {
+    pub n: usize,
+    pub num_public_inputs: usize,
+    pub selector_commitments: [G1; 5],
+    pub sigma_commitments: [G1; 3],
+}
+
+pub struct ProvingKey {
+    pub selectors: [Polynomial<Fr>; 5],
+    pub sigmas: [Polynomial<Fr>; 3],
+    // σ_j(ω^i) over the subgroup
+    pub sigma_values: [Vec<Scalar>; 3],
+    pub verifying_key: VerifyingKey,
+}
+
+// values at ζ, of the wires, permutations, selectors, accumulator and quotient
+// parts. Every polynomial is opened directly instead of through the linearization
+// polynomial of the paper, which costs a few more field elements per proof and
+// keeps the verifier a plain evaluation of the constraint identity.
+pub struct Evaluations {
+    pub wires: [Scalar; 3],
+    pub sigmas: [Scalar; 3],
+    pub selectors: [Scalar; 5],
+    pub z: Scalar,
+    pub z_shifted: Scalar,
+    pub t: [Scalar; 3],
+}
+
+impl Evaluations {
+    // in the order of the batched opening at ζ
+    fn at_zeta(&self) -> Vec<Scalar> {
+        self.wires
+            .iter()
+            .chain(self.sigmas.iter())
+            .chain(self.selectors.iter())
+            .chain([&self.z])
+            .chain(self.t.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+pub struct Proof {
+    pub wire_commitments: [G1; 3],
+    pub z_commitment: G1,
+    pub t_commitments: [G1; 3],
+    pub evaluations: Evaluations,
+    // batched witness at ζ and witness for z at ζω
+    pub opening_proof: G1,
+    pub shifted_opening_proof: G1,
+}
+
+// p(x) + Z_H(x) Σ b_i x^i for random b_i, hides the values over H
+fn blind(coefficients: Vec<Scalar>, n: usize, count: usize) -> Polynomial<Fr> {
+    let mut coefficients = coefficients;
+    coefficients.resize(n + count, Scalar::zero());
+    for i in 0..count {
+        let b = Scalar::random();
+        coefficients[i] -= &b;
+        coefficients[n + i] += &b;
+    }
+    Polynomial::new(coefficients)
+}
+
+fn coefficients(polynomial: &Polynomial<Fr>, length: usize) -> Vec<Scalar> {
+    (0..length).map(|i| polynomial.coefficient(i)).collect()
+}
+
+// -Σ x_i L_i(ζ) with L_i(ζ) = ω^i (ζ^n - 1) / (n (ζ - ω^i))
+fn public_input_evaluation(
+    domain: &EvaluationDomain<Fr>,
+    inputs: &[Scalar],
+    zeta: &Scalar,
+) -> Scalar {
+    let n = domain.size();
+    let scale =
+        &domain.evaluate_vanishing_polynomial(zeta) * &Scalar::from(n as u64).inverse().unwrap();
+    let elements: Vec<Scalar> = (0..inputs.len()).map(|i| domain.element(i)).collect();
+    let differences: Vec<Scalar> = elements.iter().map(|omega| zeta - omega).collect();
+    inputs
+        .iter()
+        .zip(elements.iter().zip(batch_inverse(&differences)))
+        .fold(Scalar::zero(), |acc, (x, (omega, inverse))| {
+            &acc - &(&(x * omega) * &(&inverse * &scale))
+        })
+}
+
+// the statement every challenge depends on
+fn absorb_statement<D: Duplex>(
+    transcript: &mut Transcript<D>,
+    vk: &VerifyingKey,
+    public_inputs: &[Scalar],
+) {
+    transcript.append_u64(b"plonk domain size", vk.n as u64);
+    for commitment in vk.selector_commitments.iter() {
+        transcript.append_group_element(b"plonk selector", commitment);
+    }
+    for commitment in vk.sigma_commitments.iter() {
+        transcript.append_group_element(b"plonk sigma", commitment);
+    }
+    transcript.append_field_elements(b"plonk public inputs", public_inputs);
+}
+
+// interpolates the selectors and the wiring of the circuit padded to a power of two
+pub fn setup(kzg: &Kzg, circuit: &Circuit<Fr>) -> Result<(ProvingKey, VerifyingKey), &'static str> {
+    let n = circuit.num_rows().next_power_of_two().max(4);
+    if kzg.max_degree() < n + 2 {
+        return Err("reference string is too short for the circuit");
+    }
+    let domain = EvaluationDomain::<Fr>::new(n)?;
+    let elements = domain.elements();
+    let rows = circuit.rows();
+
+    let selector_values: [Vec<Scalar>; 5] = std::array::from_fn(|k| {
+        (0..n)
+            .map(|row| match rows.get(row) {
+                Some(gate) => {
+                    let s = &gate.selectors;
+                    [&s.q_l, &s.q_r, &s.q_o, &s.q_m, &s.q_c][k].clone()
+                }
+                None => Scalar::zero(),
+            })
+            .collect()
+    });
+
+    let shifts = coset_shifts();
+    let sigma = circuit.permutation(n)?;
+    let sigma_values: [Vec<Scalar>; 3] = std::array::from_fn(|column| {
+        (0..n)
+            .map(|row| {
+                let target = sigma[column * n + row];
+                &shifts[target / n] * &elements[target % n]
+            })
+            .collect()
+    });
+
+    let selectors = selector_values
+        .each_ref()
+        .map(|values| Polynomial::new(domain.ifft(values)));
+    let sigmas = sigma_values
+        .each_ref()
+        .map(|values| Polynomial::new(domain.ifft(values)));
+
+    let mut selector_commitments = Vec::with_capacity(5);
+    for selector in selectors.iter() {
+        selector_commitments.push(kzg.commit(selector)?);
+    }
+    let mut sigma_commitments = Vec::with_capacity(3);
+    for s in sigmas.iter() {
+        sigma_commitments.push(kzg.commit(s)?);
+    }
+
+    let vk = VerifyingKey {
+        n,
+        num_public_inputs: circuit.num_public_inputs(),
+        selector_commitments: selector_commitments.try_into().unwrap(),
+        sigma_commitments: sigma_commitments.try_into().unwrap(),
+    };
+    let pk = ProvingKey {
+        selectors,
+        sigmas,
+        sigma_values,
+        verifying_key: vk.clone(),
+    };
+    Ok((pk, vk))
+}
+
+pub fn prove<D: Duplex>(
+    kzg: &Kzg,
+    pk: &ProvingKey,
+    circuit: &Circuit<Fr>,
+    transcript: &mut Transcript<D>,
+) -> Result<Proof, &'static str> {
+    let vk = &pk.verifying_key;
+    let n = vk.n;
+    if circuit.num_rows() > n || circuit.num_public_inputs() != vk.num_public_inputs {
+        return Err("proving key does not match the circuit");
+    }
+    if !circuit.is_satisfied() {
+        return Err("circuit is not satisfied");
+    }
+    let domain = EvaluationDomain::<Fr>::new(n)?;
+    let public_inputs = circuit.public_inputs();
+    absorb_statement(transcript, vk, &public_inputs);
+
+    // round 1, blinded wire polynomials
+    let wire_values = circuit.wire_values(n);
+    let wires = wire_values
+        .each_ref()
+        .map(|values| blind(domain.ifft(values), n, 2));
+    let mut wire_commitments = Vec::with_capacity(3);
+    for wire in wires.iter() {
+        let commitment = kzg.commit(wire)?;
+        transcript.append_group_element(b"plonk wire", &commitment);
+        wire_commitments.push(commitment);
+    }
+
+    // round 2, the permutation accumulator z(ω^(i + 1)) = z(ω^i) Π (w + β id + γ) / (w + β σ + γ)
+    let beta: Scalar = transcript.challenge_field_element(b"plonk beta");
+    let gamma: Scalar = transcript.challenge_field_element(b"plonk gamma");
+    let shifts = coset_shifts();
+    let elements = domain.elements();
+    let mut numerators = Vec::with_capacity(n);
+    let mut denominators = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut numerator = Scalar::one();
+        let mut denominator = Scalar::one();
+        for column in 0..3 {
+            let w = &wire_values[column][i];
+            let id = &shifts[column] * &elements[i];
+            numerator *= &(&(w + &(&beta * &id)) + &gamma);
+            denominator *= &(&(w + &(&beta * &pk.sigma_values[column][i])) + &gamma);
+        }
+        numerators.push(numerator);
+        denominators.push(denominator);
+    }
+    let mut z_values = Vec::with_capacity(n);
+    let mut accumulator = Scalar::one();
+    for (numerator, inverse) in numerators.iter().zip(batch_inverse(&denominators)) {
+        z_values.push(accumulator.clone());
+        accumulator = &accumulator * &(numerator * &inverse);
+    }
+    let z = blind(domain.ifft(&z_values), n, 3);
+    let z_commitment = kzg.commit(&z)?;
+    transcript.append_group_element(b"plonk z", &z_commitment);
+
+    // round 3, the quotient over a coset of eight times the size, which holds the
+    // numerator of degree up to 4n + 5
+    let alpha: Scalar = transcript.challenge_field_element(b"plonk alpha");
+    let extended = EvaluationDomain::<Fr>::new(8 * n)?;
+    let m = extended.size();
+    let evaluate = |p: &Polynomial<Fr>| extended.coset_fft(&coefficients(p, m));
+
+    let [a, b, c] = wires.each_ref().map(evaluate);
+    let [q_l, q_r, q_o, q_m, q_c] = pk.selectors.each_ref().map(evaluate);
+    let [s1, s2, s3] = pk.sigmas.each_ref().map(evaluate);
+    let z_coset = evaluate(&z);
+    let omega = domain.generator();
+    let mut power = Scalar::one();
+    let z_shifted = Polynomial::new(
+        z.coefficients
+            .iter()
+            .map(|c| {
+                let scaled = c * &power;
+                power *= omega;
+                scaled
+            })
+            .collect(),
+    );
+    let z_shifted_coset = evaluate(&z_shifted);
+
+    let mut pi_values = vec![Scalar::zero(); n];
+    for (value, x) in pi_values.iter_mut().zip(public_inputs.iter()) {
+        *value = -x;
+    }
+    let pi = extended.coset_fft(&domain.ifft(&pi_values));
+
+    let points = extended.coset_elements();
+    let vanishing: Vec<Scalar> = points
+        .iter()
+        .map(|x| domain.evaluate_vanishing_polynomial(x))
+        .collect();
+    let vanishing_inv = batch_inverse(&vanishing);
+    let first_lagrange_inv = batch_inverse(
+        &points
+            .iter()
+            .map(|x| &(x - &Scalar::one()) * &Scalar::from(n as u64))
+            .collect::<Vec<_>>(),
+    );
+
+    let alpha_squared = alpha.square();
+    let quotient: Vec<Scalar> = (0..m)
+        .map(|i| {
+            let x = &points[i];
+            let gate = &(&(&(&(&(&q_l[i] * &a[i]) + &(&q_r[i] * &b[i])) + &(&q_o[i] * &c[i]))
+                + &(&q_m[i] * &(&a[i] * &b[i])))
+                + &q_c[i])
+                + &pi[i];
+
+            let bx = &beta * x;
+            let identity = &(&(&(&(&a[i] + &bx) + &gamma)
+                * &(&(&b[i] + &(&bx * &shifts[1])) + &gamma))
+                * &(&(&c[i] + &(&bx * &shifts[2])) + &gamma))
+                * &z_coset[i];
+            let permuted = &(&(&(&(&a[i] + &(&beta * &s1[i])) + &gamma)
+                * &(&(&b[i] + &(&beta * &s2[i])) + &gamma))
+                * &(&(&c[i] + &(&beta * &s3[i])) + &gamma))
+                * &z_shifted_coset[i];
+
+            // L_1(x) = (x^n - 1) / (n (x - 1))
+            let first_lagrange = &vanishing[i] * &first_lagrange_inv[i];
+            let boundary = &(&z_coset[i] - &Scalar::one()) * &first_lagrange;
+
+            let numerator =
+                &(&gate + &(&alpha * &(&identity - &permuted))) + &(&alpha_squared * &boundary);
+            &numerator * &vanishing_inv[i]
+        })
+        .collect();
+    let t = extended.coset_ifft(&quotient);
+    if t[3 * (n + 2)..].iter().any(|c| !c.is_zero()) {
+        return Err("quotient degree is too large");
+    }
+    let parts: [Polynomial<Fr>; 3] =
+        std::array::from_fn(|k| Polynomial::new(t[k * (n + 2)..(k + 1) * (n + 2)].to_vec()));
+    let mut t_commitments = Vec::with_capacity(3);
+    for part in parts.iter() {
+        let commitment = kzg.commit(part)?;
+        transcript.append_group_element(b"plonk t", &commitment);
+        t_commitments.push(commitment);
+    }
+
+    // round 4, evaluations at ζ and ζω
+    let zeta: Scalar = transcript.challenge_field_element(b"plonk zeta");
+    let zeta_omega = &zeta * omega;
+    let evaluations = Evaluations {
+        wires: wires.each_ref().map(|p| p.evaluate(&zeta)),
+        sigmas: pk.sigmas.each_ref().map(|p| p.evaluate(&zeta)),
+        selectors: pk.selectors.each_ref().map(|p| p.evaluate(&zeta)),
+        z: z.evaluate(&zeta),
+        z_shifted: z.evaluate(&zeta_omega),
+        t: parts.each_ref().map(|p| p.evaluate(&zeta)),
+    };
+    transcript.append_field_elements(b"plonk evaluations", &evaluations.at_zeta());
+    transcript.append_field_element(b"plonk shifted evaluation", &evaluations.z_shifted);
+
+    // round 5, opening proofs
+    let v: Scalar = transcript.challenge_field_element(b"plonk v");
+    let opened: Vec<&Polynomial<Fr>> = wires
+        .iter()
+        .chain(pk.sigmas.iter())
+        .chain(pk.selectors.iter())
+        .chain([&z])
+        .chain(parts.iter())
+        .collect();
+    let opening_proof = kzg.open_batch(&opened, &zeta, &v)?;
+    let shifted_opening_proof = kzg.open(&z, &zeta_omega)?.1;
+
+    Ok(Proof {
+        wire_commitments: wire_commitments.try_into().unwrap(),
+        z_commitment,
+        t_commitments: t_commitments.try_into().unwrap(),
+        evaluations,
+        opening_proof,
+        shifted_opening_proof,
+    })
+}
+
+pub fn verify<D: Duplex>(
+    kzg: &Kzg,
+    vk: &VerifyingKey,
+    public_inputs: &[Scalar],
+    proof: &Proof,
+    transcript: &mut Transcript<D>,
+) -> Result<(), &'static str> {
+    if public_inputs.len() != vk.num_public_inputs {
+        return Err("wrong number of public inputs");
+    }
+    let n = vk.n;
+    let domain = EvaluationDomain::<Fr>::new(n)?;
+    absorb_statement(transcript, vk, public_inputs);
+
+    for commitment in proof.wire_commitments.iter() {
+        transcript.append_group_element(b"plonk wire", commitment);
+    }
+    let beta: Scalar = transcript.challenge_field_element(b"plonk beta");
+    let gamma: Scalar = transcript.challenge_field_element(b"plonk gamma");
+    transcript.append_group_element(b"plonk z", &proof.z_commitment);
+    let alpha: Scalar = transcript.challenge_field_element(b"plonk alpha");
+    for commitment in proof.t_commitments.iter() {
+        transcript.append_group_element(b"plonk t", commitment);
+    }
+    let zeta: Scalar = transcript.challenge_field_element(b"plonk zeta");
+    let e = &proof.evaluations;
+    transcript.append_field_elements(b"plonk evaluations", &e.at_zeta());
+    transcript.append_field_element(b"plonk shifted evaluation", &e.z_shifted);
+    let v: Scalar = transcript.challenge_field_element(b"plonk v");
+
+    // the constraint identity at ζ
+    let vanishing = domain.evaluate_vanishing_polynomial(&zeta);
+    if vanishing.is_zero() {
+        return Err("evaluation point lies in the domain");
+    }
+    let [a, b, c] = &e.wires;
+    let [q_l, q_r, q_o, q_m, q_c] = &e.selectors;
+    let [s1, s2, s3] = &e.sigmas;
+    let shifts = coset_shifts();
+
+    let gate = &(&(&(&(&(q_l * a) + &(q_r * b)) + &(q_o * c)) + &(q_m * &(a * b))) + q_c)
+        + &public_input_evaluation(&domain, public_inputs, &zeta);
+    let bz = &beta * &zeta;
+    let identity = &(&(&(&(a + &bz) + &gamma) * &(&(b + &(&bz * &shifts[1])) + &gamma))
+        * &(&(c + &(&bz * &shifts[2])) + &gamma))
+        * &e.z;
+    let permuted = &(&(&(&(a + &(&beta * s1)) + &gamma) * &(&(b + &(&beta * s2)) + &gamma))
+        * &(&(c + &(&beta * s3)) + &gamma))
+        * &e.z_shifted;
+    let first_lagrange = &vanishing
+        * &(&(&zeta - &Scalar::one()) * &Scalar::from(n as u64))
+            .inverse()
+            .unwrap();
+    let boundary = &(&e.z - &Scalar::one()) * &first_lagrange;
+    let lhs = &(&gate + &(&alpha * &(&identity - &permuted))) + &(&alpha.square() * &boundary);
+
+    let zeta_shift = zeta.pow_u64(n as u64 + 2);
+    let t = &(&e.t[0] + &(&zeta_shift * &e.t[1])) + &(&zeta_shift.square() * &e.t[2]);
+    if lhs != &t * &vanishing {
+        return Err("constraint identity does not hold at the challenge");
+    }
+
+    // the evaluations match the commitments
+    let commitments: Vec<G1> = proof
+        .wire_commitments
+        .iter()
+        .chain(vk.sigma_commitments.iter())
+        .chain(vk.selector_commitments.iter())
+        .chain([&proof.z_commitment])
+        .chain(proof.t_commitments.iter())
+        .cloned()
+        .collect();
+    if !kzg.verify_batch(&commitments, &zeta, &e.at_zeta(), &v, &proof.opening_proof) {
+        return Err("invalid opening at the challenge");
+    }
+    let zeta_omega = &zeta * domain.generator();
+    if !kzg.verify(
+        &proof.z_commitment,
+        &zeta_omega,
+        &e.z_shifted,
+        &proof.shifted_opening_proof,
+    ) {
+        return Err("invalid opening of the accumulator at the shifted challenge");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetization::plonk::Variable;
+
+    // x^3 + x + 5 = out with public out
+    fn cubic(x: u64, out: u64) -> Circuit<Fr> {
+        let mut circuit = Circuit::new();
+        let out = circuit.alloc_input(Scalar::from(out));
+        let x = circuit.alloc(Scalar::from(x));
+
+        let x_squared = circuit.mul(x, x);
+        let x_cubed = circuit.mul(x_squared, x);
+        let sum = circuit.add(x_cubed, x);
+        let result = circuit.add_constant(sum, Scalar::from(5));
+        circuit.assert_equal(result, out);
+        circuit
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let circuit = cubic(3, 35);
+        let kzg = Kzg::setup(16);
+        let (pk, vk) = setup(&kzg, &circuit).unwrap();
+        let proof = prove(&kzg, &pk, &circuit, &mut Transcript::keccak(b"test")).unwrap();
+
+        let public_inputs = [Scalar::from(35)];
+        assert!(verify(
+            &kzg,
+            &vk,
+            &public_inputs,
+            &proof,
+            &mut Transcript::keccak(b"test")
+        )
+        .is_ok());
+        assert!(verify(
+            &kzg,
+            &vk,
+            &[Scalar::from(36)],
+            &proof,
+            &mut Transcript::keccak(b"test")
+        )
+        .is_err());
+        assert!(verify(&kzg, &vk, &[], &proof, &mut Transcript::keccak(b"test")).is_err());
+        assert!(verify(
+            &kzg,
+            &vk,
+            &public_inputs,
+            &proof,
+            &mut Transcript::keccak(b"other")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_rejects_tampering() {
+        let circuit = cubic(3, 35);
+        let kzg = Kzg::setup(16);
+        let (pk, vk) = setup(&kzg, &circuit).unwrap();
+        let public_inputs = [Scalar::from(35)];
+        let check = |proof: &Proof| {
+            verify(
+                &kzg,
+                &vk,
+                &public_inputs,
+                proof,
+                &mut Transcript::keccak(b"test"),
+            )
+            .is_ok()
+        };
+
+        let mut proof = prove(&kzg, &pk, &circuit, &mut Transcript::keccak(b"test")).unwrap();
+        assert!(check(&proof));
+        proof.evaluations.wires[1] += &Scalar::one();
+        assert!(!check(&proof));
+
+        let mut proof = prove(&kzg, &pk, &circuit, &mut Transcript::keccak(b"test")).unwrap();
+        proof.evaluations.z_shifted += &Scalar::one();
+        assert!(!check(&proof));
+
+        let mut proof = prove(&kzg, &pk, &circuit, &mut Transcript::keccak(b"test")).unwrap();
+        proof.z_commitment = proof.t_commitments[0].clone();
+        assert!(!check(&proof));
+    }
+
+    #[test]
+    fn test_rejects_unsatisfied() {
+        let kzg = Kzg::setup(16);
+        let (pk, _) = setup(&kzg, &cubic(3, 35)).unwrap();
+
+        let mut circuit = cubic(3, 35);
+        circuit.set_value(Variable(2), Scalar::from(4));
+        assert!(prove(&kzg, &pk, &circuit, &mut Transcript::keccak(b"test")).is_err());
+        assert!(prove(&kzg, &pk, &cubic(3, 36), &mut Transcript::keccak(b"test")).is_err());
+    }
+
+    #[test]
+    fn test_larger_circuit() {
+        // out = Σ b_i 2^i over eight boolean variables
+        let mut circuit = Circuit::new();
+        let value = 0b10110101u64;
+        let out = circuit.alloc_input(Scalar::from(value));
+        let mut sum = circuit.zero();
+        let mut power = circuit.constant(Scalar::one());
+        let two = circuit.constant(Scalar::from(2));
+        for i in 0..8 {
+            let bit = circuit.alloc(Scalar::from((value >> i) & 1));
+            circuit.assert_boolean(bit);
+            let term = circuit.mul(bit, power);
+            sum = circuit.add(sum, term);
+            power = circuit.mul(power, two);
+        }
+        circuit.assert_equal(sum, out);
+        assert!(circuit.is_satisfied());
+
+        let kzg = Kzg::setup(66);
+        let (pk, vk) = setup(&kzg, &circuit).unwrap();
+        assert_eq!(vk.n, 64);
+        let proof = prove(&kzg, &pk, &circuit, &mut Transcript::keccak(b"bits")).unwrap();
+        assert!(verify(
+            &kzg,
+            &vk,
+            &[Scalar::from(value)],
+            &proof,
+            &mut Transcript::keccak(b"bits")
+        )
+        .is_ok());
+        assert!(setup(&Kzg::setup(64), &circuit).is_err());
+    }
+}
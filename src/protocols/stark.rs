@@ -0,0 +1,546 @@
+use crate::arithmetization::air::{Air, TraceLde, TraceTable};
+use crate::commitments::merkle::{write_blob, MerkleHasher, MerkleMultiProof, MerkleTree, Reader};
+use crate::fields::batch::batch_inverse;
+use crate::fields::bytes::{elements_from_bytes, elements_to_bytes, FieldBytes};
+use crate::fields::element::FieldElement;
+use crate::protocols::fri::{Fri, FriConfig, FriProof};
+use crate::protocols::transcript::{Duplex, Transcript};
+use cryptography::{FftField, HashFunction};
+use std::marker::PhantomData;
+
+pub struct StarkConfig {
+    // LDE domain size over the trace length
+    pub blowup: usize,
+    pub num_queries: usize,
+    pub folding_factor: usize,
+    pub remainder_degree_bound: usize,
+}
+
+impl StarkConfig {
+    pub fn new(
+        blowup: usize,
+        num_queries: usize,
+        folding_factor: usize,
+        remainder_degree_bound: usize,
+    ) -> Result<Self, &'static str> {
+        // validates the shared parameters
+        FriConfig::new(folding_factor, blowup, num_queries, remainder_degree_bound)?;
+
+        Ok(Self {
+            blowup,
+            num_queries,
+            folding_factor,
+            remainder_degree_bound,
+        })
+    }
+
+    // under the usual proximity conjecture every query costs a cheating prover a
+    // factor of the FRI blowup, which is blowup divided by the composition degree
+    // over the trace length. Challenges come from F itself, so the out-of-domain point
+    // and the combination coefficients only give log2 |F| less log2 of the composition
+    // degree bound times the LDE size, however many queries are made.
+    pub fn conjectured_security_bits<F: FftField>(&self, air: &Air<F>) -> usize {
+        let fri_blowup = self.blowup * air.trace_length() / Self::fri_degree_bound(air);
+        let query_bits = self.num_queries * fri_blowup.trailing_zeros() as usize;
+
+        let lde_size = self.blowup * air.trace_length();
+        let field_bits = F::characteristic().bits() as usize - 1;
+        let loss = (air.composition_degree_bound() * lde_size)
+            .next_power_of_two()
+            .trailing_zeros() as usize;
+        query_bits.min(field_bits.saturating_sub(loss))
+    }
+
+    fn fri_degree_bound<F: FftField>(air: &Air<F>) -> usize {
+        air.composition_degree_bound()
+            .max(air.trace_length())
+            .next_power_of_two()
+    }
+}
+
+pub struct StarkProof<F: FftField> {
+    pub trace_root: Vec<u8>,
+    pub composition_root: Vec<u8>,
+    // trace columns at z and g z, composition at z
+    pub trace_ood: Vec<FieldElement<F>>,
+    pub trace_ood_next: Vec<FieldElement<F>>,
+    pub composition_ood: FieldElement<F>,
+    pub fri: FriProof<F>,
+    // trace rows and composition values at the distinct query positions, in order
+    pub trace_rows: Vec<Vec<FieldElement<F>>>,
+    pub trace_proof: MerkleMultiProof,
+    pub composition_values: Vec<FieldElement<F>>,
+    pub composition_proof: MerkleMultiProof,
+}
+
+// the fields in declaration order, digests, element vectors and nested proofs as blobs
+// (u64 length and bytes), the number of trace rows as a u64
+impl<F> StarkProof<F>
+where
+    F: FftField + FieldBytes,
+{
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_blob(&mut bytes, &self.trace_root);
+        write_blob(&mut bytes, &self.composition_root);
+        write_blob(&mut bytes, &elements_to_bytes(&self.trace_ood));
+        write_blob(&mut bytes, &elements_to_bytes(&self.trace_ood_next));
+        write_blob(
+            &mut bytes,
+            &elements_to_bytes(std::slice::from_ref(&self.composition_ood)),
+        );
+        write_blob(&mut bytes, &self.fri.to_bytes());
+        bytes.extend_from_slice(&(self.trace_rows.len() as u64).to_le_bytes());
+        for row in self.trace_rows.iter() {
+            write_blob(&mut bytes, &elements_to_bytes(row));
+        }
+        write_blob(&mut bytes, &self.trace_proof.to_bytes());
+        write_blob(&mut bytes, &elements_to_bytes(&self.composition_values));
+        write_blob(&mut bytes, &self.composition_proof.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let mut reader = Reader::new(bytes);
+        let trace_root = reader.blob()?.to_vec();
+        let composition_root = reader.blob()?.to_vec();
+        let trace_ood = elements_from_bytes(reader.blob()?)?;
+        let trace_ood_next = elements_from_bytes(reader.blob()?)?;
+        let composition_ood = match &elements_from_bytes::<F>(reader.blob()?)?[..] {
+            [value] => value.clone(),
+            _ => return Err("expected a single composition value"),
+        };
+        let fri = FriProof::from_bytes(reader.blob()?)?;
+        let count = reader.u64()?;
+        let mut trace_rows = Vec::new();
+        for _ in 0..count {
+            trace_rows.push(elements_from_bytes(reader.blob()?)?);
+        }
+        let trace_proof = MerkleMultiProof::from_bytes(reader.blob()?)?;
+        let composition_values = elements_from_bytes(reader.blob()?)?;
+        let composition_proof = MerkleMultiProof::from_bytes(reader.blob()?)?;
+        reader.finish()?;
+
+        Ok(Self {
+            trace_root,
+            composition_root,
+            trace_ood,
+            trace_ood_next,
+            composition_ood,
+            fri,
+            trace_rows,
+            trace_proof,
+            composition_values,
+            composition_proof,
+        })
+    }
+}
+
+// STARK for an AIR: the trace LDE and the composition polynomial are committed with
+// Merkle trees, both are opened at an out-of-domain point z, and FRI shows that the
+// DEEP quotients (T(x) - T(z)) / (x - z), (T(x) - T(g z)) / (x - g z) and
+// (C(x) - C(z)) / (x - z) are polynomials, tying the commitments to the values at z
+pub struct Stark<F: FftField + FieldBytes, H: HashFunction + Default> {
+    config: StarkConfig,
+    field: PhantomData<F>,
+    hasher: PhantomData<H>,
+}
+
+impl<F, H> Stark<F, H>
+where
+    F: FftField + FieldBytes,
+    H: HashFunction + Default,
+{
+    pub fn new(config: StarkConfig) -> Self {
+        Self {
+            config,
+            field: PhantomData,
+            hasher: PhantomData,
+        }
+    }
+
+    fn fri(&self, air: &Air<F>) -> Result<Fri<F, H>, &'static str> {
+        let degree_bound = StarkConfig::fri_degree_bound(air);
+        let lde_size = air.trace_length() * self.config.blowup;
+        if lde_size / degree_bound < 2 {
+            return Err("blowup is too small for the constraint degree");
+        }
+
+        let config = FriConfig::new(
+            self.config.folding_factor,
+            lde_size / degree_bound,
+            self.config.num_queries,
+            self.config.remainder_degree_bound,
+        )?;
+        Fri::new(config, degree_bound)
+    }
+
+    // the public statement: shape of the trace and the boundary values
+    fn append_statement<D: Duplex>(air: &Air<F>, transcript: &mut Transcript<D>) {
+        transcript.append_u64(b"stark width", air.width() as u64);
+        transcript.append_u64(b"stark trace length", air.trace_length() as u64);
+        transcript.append_u64(b"stark transitions", air.transitions().len() as u64);
+        for b in air.boundaries() {
+            transcript.append_u64(b"stark boundary column", b.column as u64);
+            transcript.append_u64(b"stark boundary row", b.row as u64);
+            transcript.append_field_element(b"stark boundary value", &b.value);
+        }
+    }
+
+    // a point outside both the trace domain and the LDE coset
+    fn ood_point<D: Duplex>(
+        air: &Air<F>,
+        lde_size: usize,
+        transcript: &mut Transcript<D>,
+    ) -> FieldElement<F> {
+        let offset_power =
+            FieldElement::<F>::new(F::multiplicative_generator()).pow_u64(lde_size as u64);
+        loop {
+            let z = transcript.challenge_field_element::<F>(b"stark ood point");
+            if !z.pow_u64(air.trace_length() as u64).is_one()
+                && z.pow_u64(lde_size as u64) != offset_power
+            {
+                return z;
+            }
+        }
+    }
+
+    // sum over the columns of γ_c (T_c(x) - T_c(z)) / (x - z) and
+    // γ'_c (T_c(x) - T_c(g z)) / (x - g z), plus γ (C(x) - C(z)) / (x - z)
+    fn deep(
+        row: &[FieldElement<F>],
+        composition: &FieldElement<F>,
+        proof_ood: (&[FieldElement<F>], &[FieldElement<F>], &FieldElement<F>),
+        gammas: &[FieldElement<F>],
+        z_inverse: &FieldElement<F>,
+        gz_inverse: &FieldElement<F>,
+    ) -> FieldElement<F> {
+        let (ood, ood_next, composition_ood) = proof_ood;
+        let width = row.len();
+        let mut at_z = FieldElement::<F>::zero();
+        let mut at_gz = FieldElement::<F>::zero();
+        for c in 0..width {
+            at_z += &(&gammas[c] * &(&row[c] - &ood[c]));
+            at_gz += &(&gammas[width + c] * &(&row[c] - &ood_next[c]));
+        }
+        at_z += &(&gammas[2 * width] * &(composition - composition_ood));
+        &(&at_z * z_inverse) + &(&at_gz * gz_inverse)
+    }
+
+    fn positions(queried: &[usize]) -> Vec<usize> {
+        let mut positions = queried.to_vec();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+
+    pub fn prove<D: Duplex>(
+        &self,
+        air: &Air<F>,
+        trace: &TraceTable<F>,
+        transcript: &mut Transcript<D>,
+    ) -> Result<StarkProof<F>, &'static str> {
+        air.check(trace)?;
+        let fri = self.fri(air)?;
+        Self::append_statement(air, transcript);
+
+        // commit to the trace
+        let lde = TraceLde::new(trace, self.config.blowup)?;
+        let lde_size = lde.domain().size();
+        let rows: Vec<Vec<u8>> = (0..lde_size)
+            .map(|i| elements_to_bytes(&lde.row(i)))
+            .collect();
+        let trace_tree = MerkleTree::new(&rows, 2, H::default())?;
+        transcript.append_message(b"stark trace root", trace_tree.root());
+
+        // commit to the composition
+        let alphas =
+            transcript.challenge_field_elements(b"stark composition", air.num_constraints());
+        let composition = air.composition_evaluations(&lde, &alphas)?;
+        let values: Vec<Vec<u8>> = composition
+            .iter()
+            .map(|c| elements_to_bytes(std::slice::from_ref(c)))
+            .collect();
+        let composition_tree = MerkleTree::new(&values, 2, H::default())?;
+        transcript.append_message(b"stark composition root", composition_tree.root());
+
+        // out-of-domain evaluations
+        let z = Self::ood_point(air, lde_size, transcript);
+        let gz = &air.trace_generator() * &z;
+        let evaluate = |coefficients: &[FieldElement<F>], x: &FieldElement<F>| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(FieldElement::<F>::zero(), |acc, c| &(&acc * x) + c)
+        };
+        let trace_ood: Vec<FieldElement<F>> =
+            lde.polynomials().iter().map(|p| evaluate(p, &z)).collect();
+        let trace_ood_next: Vec<FieldElement<F>> =
+            lde.polynomials().iter().map(|p| evaluate(p, &gz)).collect();
+        let composition_ood = air.evaluate_composition(&z, &trace_ood, &trace_ood_next, &alphas)?;
+        transcript.append_field_elements(b"stark trace ood", &trace_ood);
+        transcript.append_field_elements(b"stark trace ood next", &trace_ood_next);
+        transcript.append_field_element(b"stark composition ood", &composition_ood);
+
+        // DEEP composition over the LDE domain, proven low degree by FRI
+        let gammas = transcript.challenge_field_elements(b"stark deep", 2 * air.width() + 1);
+        let xs = lde.domain().coset_elements();
+        let z_inverses = batch_inverse(&xs.iter().map(|x| x - &z).collect::<Vec<_>>());
+        let gz_inverses = batch_inverse(&xs.iter().map(|x| x - &gz).collect::<Vec<_>>());
+        let deep = (0..lde_size)
+            .map(|i| {
+                Self::deep(
+                    &lde.row(i),
+                    &composition[i],
+                    (&trace_ood, &trace_ood_next, &composition_ood),
+                    &gammas,
+                    &z_inverses[i],
+                    &gz_inverses[i],
+                )
+            })
+            .collect();
+        let (fri_proof, queried) = fri.prove(deep, transcript)?;
+
+        // open the commitments where FRI queried
+        let positions = Self::positions(&queried);
+        Ok(StarkProof {
+            trace_root: trace_tree.root().to_vec(),
+            composition_root: composition_tree.root().to_vec(),
+            trace_ood,
+            trace_ood_next,
+            composition_ood,
+            fri: fri_proof,
+            trace_rows: positions.iter().map(|p| lde.row(*p)).collect(),
+            trace_proof: trace_tree.prove_batch(&positions)?,
+            composition_values: positions.iter().map(|p| composition[*p].clone()).collect(),
+            composition_proof: composition_tree.prove_batch(&positions)?,
+        })
+    }
+
+    pub fn verify<D: Duplex>(
+        &self,
+        air: &Air<F>,
+        proof: &StarkProof<F>,
+        transcript: &mut Transcript<D>,
+    ) -> Result<(), &'static str> {
+        let fri = self.fri(air)?;
+        let width = air.width();
+        if proof.trace_ood.len() != width || proof.trace_ood_next.len() != width {
+            return Err("out-of-domain frame has the wrong width");
+        }
+        Self::append_statement(air, transcript);
+
+        transcript.append_message(b"stark trace root", &proof.trace_root);
+        let alphas =
+            transcript.challenge_field_elements(b"stark composition", air.num_constraints());
+        transcript.append_message(b"stark composition root", &proof.composition_root);
+
+        // the committed composition must match the constraints at z
+        let lde_size = air.trace_length() * self.config.blowup;
+        let z = Self::ood_point(air, lde_size, transcript);
+        let gz = &air.trace_generator() * &z;
+        let expected =
+            air.evaluate_composition(&z, &proof.trace_ood, &proof.trace_ood_next, &alphas)?;
+        if expected != proof.composition_ood {
+            return Err("composition does not match the constraints at the ood point");
+        }
+        transcript.append_field_elements(b"stark trace ood", &proof.trace_ood);
+        transcript.append_field_elements(b"stark trace ood next", &proof.trace_ood_next);
+        transcript.append_field_element(b"stark composition ood", &proof.composition_ood);
+
+        let gammas = transcript.challenge_field_elements(b"stark deep", 2 * width + 1);
+        let queried = fri.verify(&proof.fri, transcript)?;
+
+        // the openings must be consistent with the commitments
+        let positions = Self::positions(&queried.iter().map(|(p, _)| *p).collect::<Vec<_>>());
+        if proof.trace_rows.len() != positions.len()
+            || proof.composition_values.len() != positions.len()
+            || proof.trace_proof.indices != positions
+            || proof.composition_proof.indices != positions
+        {
+            return Err("openings do not match the query positions");
+        }
+        if proof.trace_rows.iter().any(|row| row.len() != width) {
+            return Err("trace row has the wrong width");
+        }
+        let rows: Vec<Vec<u8>> = proof
+            .trace_rows
+            .iter()
+            .map(|row| elements_to_bytes(row))
+            .collect();
+        let values: Vec<Vec<u8>> = proof
+            .composition_values
+            .iter()
+            .map(|c| elements_to_bytes(std::slice::from_ref(c)))
+            .collect();
+        let mut merkle = MerkleHasher::new(H::default(), 2)?;
+        if !merkle.verify_batch(&proof.trace_root, &rows, &proof.trace_proof)
+            || !merkle.verify_batch(&proof.composition_root, &values, &proof.composition_proof)
+        {
+            return Err("opening does not match the commitment");
+        }
+
+        // and with the function FRI tested
+        let domain = fri.domain();
+        for (position, value) in queried.iter() {
+            let i = positions.binary_search(position).unwrap();
+            let x = domain.offset() * &domain.element(*position);
+            let z_inverse = (&x - &z).inverse().unwrap();
+            let gz_inverse = (&x - &gz).inverse().unwrap();
+            let deep = Self::deep(
+                &proof.trace_rows[i],
+                &proof.composition_values[i],
+                (
+                    &proof.trace_ood,
+                    &proof.trace_ood_next,
+                    &proof.composition_ood,
+                ),
+                &gammas,
+                &z_inverse,
+                &gz_inverse,
+            );
+            if deep != *value {
+                return Err("DEEP composition does not match the committed values");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetization::air::BoundaryConstraint;
+    use crate::fields::u64_field::U64Field;
+    use crate::hash::md5::MessageDigestAlgorithm;
+    use crate::hash::sha3::Sha3_256;
+    use crate::polynomial::multivariate::{MonomialOrder, SparsePolynomial};
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn var(i: usize) -> SparsePolynomial<Goldilocks> {
+        SparsePolynomial::variable(i, 4, MonomialOrder::GradedLex)
+    }
+
+    fn fibonacci_trace(n: usize) -> TraceTable<Goldilocks> {
+        let mut rows = vec![vec![Element::one(), Element::one()]];
+        for i in 1..n {
+            let previous = &rows[i - 1];
+            rows.push(vec![previous[1].clone(), &previous[0] + &previous[1]]);
+        }
+        TraceTable::from_rows(rows).unwrap()
+    }
+
+    // (a, b) -> (b, a + b) from (1, 1), claiming the last b
+    fn fibonacci_air(n: usize, result: Element) -> Air<Goldilocks> {
+        let transitions = vec![&var(2) - &var(1), &(&var(3) - &var(0)) - &var(1)];
+        let boundaries = vec![
+            BoundaryConstraint {
+                column: 0,
+                row: 0,
+                value: Element::one(),
+            },
+            BoundaryConstraint {
+                column: 1,
+                row: 0,
+                value: Element::one(),
+            },
+            BoundaryConstraint {
+                column: 1,
+                row: n - 1,
+                value: result,
+            },
+        ];
+        Air::new(2, n, transitions, boundaries).unwrap()
+    }
+
+    fn setup(n: usize) -> (Air<Goldilocks>, TraceTable<Goldilocks>) {
+        let trace = fibonacci_trace(n);
+        let result = trace.get(n - 1, 1).clone();
+        (fibonacci_air(n, result), trace)
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let (air, trace) = setup(64);
+        for config in [
+            StarkConfig::new(4, 16, 2, 4).unwrap(),
+            StarkConfig::new(8, 12, 4, 1).unwrap(),
+        ] {
+            let stark = Stark::<Goldilocks, Sha3_256>::new(config);
+            let proof = stark
+                .prove(&air, &trace, &mut Transcript::keccak(b"fibonacci"))
+                .unwrap();
+            assert!(stark
+                .verify(&air, &proof, &mut Transcript::keccak(b"fibonacci"))
+                .is_ok());
+
+            // a different claimed result
+            let wrong = fibonacci_air(64, Element::from(12345));
+            assert!(stark
+                .verify(&wrong, &proof, &mut Transcript::keccak(b"fibonacci"))
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_security_parameters() {
+        let (air, _) = setup(64);
+        assert_eq!(
+            StarkConfig::new(8, 10, 2, 1)
+                .unwrap()
+                .conjectured_security_bits(&air),
+            30
+        );
+        // 20 queries would give 60 bits, but a 64-bit field with a composition degree
+        // bound of 64 and 512 LDE points leaves 63 - 15
+        assert_eq!(
+            StarkConfig::new(8, 20, 2, 1)
+                .unwrap()
+                .conjectured_security_bits(&air),
+            48
+        );
+        assert!(StarkConfig::new(3, 20, 2, 1).is_err());
+        assert!(StarkConfig::new(8, 0, 2, 1).is_err());
+    }
+
+    #[test]
+    fn test_invalid_trace_is_not_proven() {
+        let (air, mut trace) = setup(32);
+        trace.set(10, 0, Element::from(5));
+        let stark = Stark::<Goldilocks, Sha3_256>::new(StarkConfig::new(4, 8, 2, 1).unwrap());
+        assert!(stark
+            .prove(&air, &trace, &mut Transcript::keccak(b"fibonacci"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_serialization_and_mutation() {
+        let (air, trace) = setup(32);
+        let stark =
+            Stark::<Goldilocks, MessageDigestAlgorithm>::new(StarkConfig::new(4, 8, 2, 2).unwrap());
+        let proof = stark
+            .prove(&air, &trace, &mut Transcript::keccak(b"fibonacci"))
+            .unwrap();
+        let bytes = proof.to_bytes();
+
+        let decoded = StarkProof::<Goldilocks>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(stark
+            .verify(&air, &decoded, &mut Transcript::keccak(b"fibonacci"))
+            .is_ok());
+
+        // flipping any bit is caught, by the parser or by the verifier
+        for i in (0..bytes.len()).step_by(7) {
+            let mut mutated = bytes.clone();
+            mutated[i] ^= 1 << (i % 8);
+            let accepted = StarkProof::<Goldilocks>::from_bytes(&mutated).is_ok_and(|proof| {
+                stark
+                    .verify(&air, &proof, &mut Transcript::keccak(b"fibonacci"))
+                    .is_ok()
+            });
+            assert!(!accepted, "mutation at byte {} was accepted", i);
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::polynomial::lagrange::BarycentricInterpolation;
+use crate::polynomial::multilinear::MultilinearPolynomial;
+use crate::protocols::transcript::{Duplex, Transcript};
+use cryptography::Field;
+use num_bigint::BigUint;
+
+// proves sum over {0, 1}^n of p_1(x) p_2(x) ... p_d(x) for multilinear p_j.
+// In round i the prover sends g_i(X) = sum over the remaining hypercube of the
+// product with the first i - 1 variables fixed to the challenges, as its values
+// at X = 0, 1, ..., d
+pub struct SumcheckProver<F: Field> {
+    tables: Vec<MultilinearPolynomial<F>>,
+    num_variables: usize,
+}
+
+impl<F> SumcheckProver<F>
+where
+    F: Field,
+{
+    pub fn new(polynomials: Vec<MultilinearPolynomial<F>>) -> Result<Self, &'static str> {
+        let num_variables = polynomials
+            .first()
+            .ok_or("at least one polynomial is required")?
+            .num_variables();
+        if polynomials
+            .iter()
+            .any(|p| p.num_variables() != num_variables)
+        {
+            return Err("polynomials must have the same number of variables");
+        }
+        check_degree::<F>(polynomials.len())?;
+
+        Ok(Self {
+            tables: polynomials,
+            num_variables,
+        })
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    // degree of every round polynomial
+    pub fn degree(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn remaining_rounds(&self) -> usize {
+        self.tables[0].num_variables()
+    }
+
+    pub fn claimed_sum(&self) -> FieldElement<F> {
+        let mut sum = FieldElement::<F>::zero();
+        for i in 0..self.tables[0].evaluations().len() {
+            let mut product = FieldElement::<F>::one();
+            for table in self.tables.iter() {
+                product *= &table.evaluations()[i];
+            }
+            sum += &product;
+        }
+        sum
+    }
+
+    // g(0), ..., g(d) in one pass over the current tables, each entry pair
+    // (p(0, x), p(1, x)) is extended to p(t, x) = p(0, x) + t (p(1, x) - p(0, x)),
+    // stepping t through the points of evaluation_points
+    pub fn round_polynomial(&self) -> Vec<FieldElement<F>> {
+        assert!(self.remaining_rounds() > 0, "all rounds are done");
+
+        let degree = self.degree();
+        let half = self.tables[0].evaluations().len() / 2;
+        let mut result = vec![FieldElement::<F>::zero(); degree + 1];
+
+        let mut values = vec![FieldElement::<F>::one(); degree + 1];
+        for i in 0..half {
+            values.fill(FieldElement::<F>::one());
+            for table in self.tables.iter() {
+                let low = &table.evaluations()[i];
+                let step = &table.evaluations()[i + half] - low;
+                let mut current = low.clone();
+                for value in values.iter_mut() {
+                    *value *= &current;
+                    current += &step;
+                }
+            }
+            for (r, v) in result.iter_mut().zip(values.iter()) {
+                *r += v;
+            }
+        }
+        result
+    }
+
+    pub fn receive_challenge(&mut self, r: &FieldElement<F>) {
+        assert!(self.remaining_rounds() > 0, "all rounds are done");
+
+        self.tables = self
+            .tables
+            .iter()
+            .map(|table| table.fix_first_variable(r))
+            .collect();
+    }
+
+    // p_j(r) for every polynomial once all variables are fixed
+    pub fn final_evaluations(&self) -> Vec<FieldElement<F>> {
+        assert_eq!(self.remaining_rounds(), 0, "rounds are still pending");
+
+        self.tables
+            .iter()
+            .map(|table| table.evaluations()[0].clone())
+            .collect()
+    }
+}
+
+// round polynomials are sent as their values at 0, 1, 1 + 1, ..., which are only
+// distinct when there are no more of them than the characteristic
+fn check_degree<F: Field>(degree: usize) -> Result<(), &'static str> {
+    if BigUint::from(degree as u64 + 1) > F::characteristic() {
+        return Err("degree must be less than the field characteristic");
+    }
+    Ok(())
+}
+
+// 0, 1, 1 + 1, ... built by repeated addition as the prover steps through them,
+// from(i) is not the integer i in binary fields
+fn evaluation_points<F: Field>(count: usize) -> Vec<FieldElement<F>> {
+    let mut point = FieldElement::<F>::zero();
+    (0..count)
+        .map(|_| {
+            let current = point.clone();
+            point += &FieldElement::<F>::one();
+            current
+        })
+        .collect()
+}
+
+// g(r) from g(0), ..., g(d)
+fn evaluate_round_polynomial<F: Field>(
+    evaluations: &[FieldElement<F>],
+    r: &FieldElement<F>,
+) -> Result<FieldElement<F>, &'static str> {
+    let xs = evaluation_points(evaluations.len());
+    Ok(BarycentricInterpolation::new(xs, evaluations.to_vec())?.evaluate(r))
+}
+
+pub struct SumcheckVerifier<F: Field> {
+    num_variables: usize,
+    degree: usize,
+    claim: FieldElement<F>,
+    challenges: Vec<FieldElement<F>>,
+}
+
+impl<F> SumcheckVerifier<F>
+where
+    F: Field,
+{
+    pub fn new(
+        num_variables: usize,
+        degree: usize,
+        claimed_sum: FieldElement<F>,
+    ) -> Result<Self, &'static str> {
+        check_degree::<F>(degree)?;
+
+        Ok(Self {
+            num_variables,
+            degree,
+            claim: claimed_sum,
+            challenges: Vec::new(),
+        })
+    }
+
+    pub fn challenges(&self) -> &[FieldElement<F>] {
+        &self.challenges
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.challenges.len() == self.num_variables
+    }
+
+    // checks g(0) + g(1) against the running claim and moves it to g(r)
+    pub fn check_round(
+        &mut self,
+        round_polynomial: &[FieldElement<F>],
+        r: FieldElement<F>,
+    ) -> Result<(), &'static str> {
+        if self.is_done() {
+            return Err("all rounds are done");
+        }
+        if round_polynomial.len() != self.degree + 1 {
+            return Err("round polynomial has the wrong degree");
+        }
+        if &round_polynomial[0] + &round_polynomial[1] != self.claim {
+            return Err("round polynomial does not match the claim");
+        }
+
+        self.claim = evaluate_round_polynomial(round_polynomial, &r)?;
+        self.challenges.push(r);
+        Ok(())
+    }
+
+    // interactive round with a fresh random challenge, which is returned for the prover
+    pub fn receive_round(
+        &mut self,
+        round_polynomial: &[FieldElement<F>],
+    ) -> Result<FieldElement<F>, &'static str> {
+        let r = FieldElement::<F>::random();
+        self.check_round(round_polynomial, r.clone())?;
+        Ok(r)
+    }
+
+    // the evaluations p_j(r) must come from an oracle or commitment the caller trusts
+    pub fn finish(&self, final_evaluations: &[FieldElement<F>]) -> Result<(), &'static str> {
+        if !self.is_done() {
+            return Err("rounds are still pending");
+        }
+        if final_evaluations.len() != self.degree {
+            return Err("one evaluation per polynomial is required");
+        }
+
+        let mut product = FieldElement::<F>::one();
+        for evaluation in final_evaluations {
+            product *= evaluation;
+        }
+        if product != self.claim {
+            return Err("final evaluations do not match the claim");
+        }
+        Ok(())
+    }
+}
+
+// round polynomials and the final p_j(r) of a non-interactive proof
+pub struct SumcheckProof<F: Field> {
+    pub round_polynomials: Vec<Vec<FieldElement<F>>>,
+    pub final_evaluations: Vec<FieldElement<F>>,
+}
+
+// absorbs the statement, shared by prover and verifier
+fn append_statement<F: FieldBytes, D: Duplex>(
+    transcript: &mut Transcript<D>,
+    num_variables: usize,
+    degree: usize,
+    claimed_sum: &FieldElement<F>,
+) {
+    transcript.append_u64(b"sumcheck variables", num_variables as u64);
+    transcript.append_u64(b"sumcheck degree", degree as u64);
+    transcript.append_field_element(b"sumcheck claim", claimed_sum);
+}
+
+// Fiat-Shamir: every challenge is derived from the claim and all earlier messages
+// through the transcript, which may already hold the context of an enclosing protocol.
+// Returns the proof and the random point r.
+pub fn prove<F: FieldBytes, D: Duplex>(
+    polynomials: Vec<MultilinearPolynomial<F>>,
+    transcript: &mut Transcript<D>,
+) -> Result<(SumcheckProof<F>, Vec<FieldElement<F>>), &'static str> {
+    let mut prover = SumcheckProver::new(polynomials)?;
+    append_statement(
+        transcript,
+        prover.num_variables(),
+        prover.degree(),
+        &prover.claimed_sum(),
+    );
+
+    let mut round_polynomials = Vec::with_capacity(prover.num_variables());
+    let mut challenges = Vec::with_capacity(prover.num_variables());
+    for _ in 0..prover.num_variables() {
+        let round_polynomial = prover.round_polynomial();
+        transcript.append_field_elements(b"sumcheck round", &round_polynomial);
+        let r = transcript.challenge_field_element(b"sumcheck challenge");
+
+        prover.receive_challenge(&r);
+        round_polynomials.push(round_polynomial);
+        challenges.push(r);
+    }
+
+    let proof = SumcheckProof {
+        round_polynomials,
+        final_evaluations: prover.final_evaluations(),
+    };
+    Ok((proof, challenges))
+}
+
+// replays the transcript, returns the point r at which the caller must check
+// p_j(r) = proof.final_evaluations[j]
+pub fn verify<F: FieldBytes, D: Duplex>(
+    num_variables: usize,
+    degree: usize,
+    claimed_sum: &FieldElement<F>,
+    proof: &SumcheckProof<F>,
+    transcript: &mut Transcript<D>,
+) -> Result<Vec<FieldElement<F>>, &'static str> {
+    if proof.round_polynomials.len() != num_variables {
+        return Err("proof has the wrong number of rounds");
+    }
+
+    let mut verifier = SumcheckVerifier::new(num_variables, degree, claimed_sum.clone())?;
+    append_statement(transcript, num_variables, degree, claimed_sum);
+
+    for round_polynomial in proof.round_polynomials.iter() {
+        transcript.append_field_elements(b"sumcheck round", round_polynomial);
+        let r = transcript.challenge_field_element(b"sumcheck challenge");
+        verifier.check_round(round_polynomial, r)?;
+    }
+    verifier.finish(&proof.final_evaluations)?;
+
+    Ok(verifier.challenges().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::binary::AesField;
+    use crate::fields::u64_field::U64Field;
+    use crate::hash::md5::MessageDigestAlgorithm;
+    use crate::protocols::transcript::Duplex;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn random_polynomials(count: usize, n: usize) -> Vec<MultilinearPolynomial<Goldilocks>> {
+        (0..count)
+            .map(|_| MultilinearPolynomial::random(n))
+            .collect()
+    }
+
+    #[test]
+    fn test_interactive_sumcheck() {
+        for (count, n) in [(1, 1), (1, 5), (2, 4), (3, 6)] {
+            let polynomials = random_polynomials(count, n);
+            let mut prover = SumcheckProver::new(polynomials.clone()).unwrap();
+            let mut verifier = SumcheckVerifier::new(n, count, prover.claimed_sum()).unwrap();
+
+            for _ in 0..n {
+                let r = verifier.receive_round(&prover.round_polynomial()).unwrap();
+                prover.receive_challenge(&r);
+            }
+
+            // the verifier's oracle check
+            let point = verifier.challenges().to_vec();
+            let evaluations: Vec<Element> = polynomials
+                .iter()
+                .map(|p| p.evaluate(&point).unwrap())
+                .collect();
+            assert!(prover.final_evaluations() == evaluations);
+            assert!(verifier.finish(&evaluations).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_wrong_claim_is_rejected() {
+        let polynomials = random_polynomials(2, 3);
+        let prover = SumcheckProver::new(polynomials).unwrap();
+        let wrong = &prover.claimed_sum() + &Element::one();
+        let mut verifier = SumcheckVerifier::new(3, 2, wrong).unwrap();
+
+        assert!(verifier.receive_round(&prover.round_polynomial()).is_err());
+    }
+
+    #[test]
+    fn test_cheating_round_is_caught_later() {
+        let polynomials = random_polynomials(2, 3);
+        let mut prover = SumcheckProver::new(polynomials.clone()).unwrap();
+        let mut verifier = SumcheckVerifier::new(3, 2, prover.claimed_sum()).unwrap();
+
+        // shift g(0) up and g(1) down so the sum check still passes
+        let mut forged = prover.round_polynomial();
+        forged[0] += &Element::one();
+        forged[1] -= &Element::one();
+        let r = verifier.receive_round(&forged).unwrap();
+        prover.receive_challenge(&r);
+
+        let result = (|| {
+            for _ in 1..3 {
+                let r = verifier.receive_round(&prover.round_polynomial())?;
+                prover.receive_challenge(&r);
+            }
+            verifier.finish(&prover.final_evaluations())
+        })();
+        assert!(result.is_err());
+    }
+
+    fn check_non_interactive<D: Duplex>(transcript: impl Fn() -> Transcript<D>) {
+        let polynomials = random_polynomials(3, 5);
+        let claimed_sum = SumcheckProver::new(polynomials.clone())
+            .unwrap()
+            .claimed_sum();
+
+        let (proof, challenges) = prove(polynomials.clone(), &mut transcript()).unwrap();
+        let point = verify(5, 3, &claimed_sum, &proof, &mut transcript()).unwrap();
+
+        assert!(point == challenges);
+        for (p, evaluation) in polynomials.iter().zip(proof.final_evaluations.iter()) {
+            assert!(p.evaluate(&point).unwrap() == *evaluation);
+        }
+
+        let wrong = &claimed_sum + &Element::one();
+        assert!(verify(5, 3, &wrong, &proof, &mut transcript()).is_err());
+
+        let mut tampered = SumcheckProof {
+            round_polynomials: proof.round_polynomials.clone(),
+            final_evaluations: proof.final_evaluations.clone(),
+        };
+        tampered.round_polynomials[2][3] += &Element::one();
+        assert!(verify(5, 3, &claimed_sum, &tampered, &mut transcript()).is_err());
+    }
+
+    #[test]
+    fn test_non_interactive_sumcheck() {
+        check_non_interactive(|| Transcript::keccak(b"test"));
+        check_non_interactive(|| Transcript::with_hash(MessageDigestAlgorithm::new(), b"test"));
+    }
+
+    // in GF(2^8) the prover's nodes 0, 1 are the verifier's, and a third one would
+    // collide with 0
+    #[test]
+    fn test_binary_field() {
+        let polynomials: Vec<MultilinearPolynomial<AesField>> =
+            (0..1).map(|_| MultilinearPolynomial::random(4)).collect();
+        let (proof, challenges) =
+            prove(polynomials.clone(), &mut Transcript::keccak(b"test")).unwrap();
+        let claimed_sum = SumcheckProver::new(polynomials).unwrap().claimed_sum();
+        let point = verify(4, 1, &claimed_sum, &proof, &mut Transcript::keccak(b"test")).unwrap();
+        assert!(point == challenges);
+
+        let quadratic = vec![MultilinearPolynomial::<AesField>::random(2); 2];
+        assert!(SumcheckProver::new(quadratic).is_err());
+        assert!(SumcheckVerifier::<AesField>::new(2, 2, FieldElement::zero()).is_err());
+    }
+
+    #[test]
+    fn test_degree_below_characteristic() {
+        type F3 = U64Field<3>;
+        let polynomials = vec![MultilinearPolynomial::<F3>::random(3); 3];
+        assert!(SumcheckProver::new(polynomials.clone()).is_err());
+        assert!(SumcheckProver::new(polynomials[..2].to_vec()).is_ok());
+        assert!(SumcheckVerifier::<F3>::new(3, 3, FieldElement::zero()).is_err());
+        assert!(SumcheckVerifier::<F3>::new(3, 2, FieldElement::zero()).is_ok());
+    }
+}
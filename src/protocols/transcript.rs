@@ -0,0 +1,296 @@
+use crate::curves::weierstrass::{JacobianPoint, WeierstrassConfig};
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::hash::sha3::KeccakSponge;
+use cryptography::HashFunction;
+use num_bigint::BigUint;
+
+// stateful object that absorbs bytes and squeezes pseudorandom bytes depending on
+// everything absorbed so far
+pub trait Duplex {
+    fn absorb(&mut self, input: &[u8]);
+    fn squeeze(&mut self, length: usize) -> Vec<u8>;
+}
+
+impl Duplex for KeccakSponge {
+    fn absorb(&mut self, input: &[u8]) {
+        KeccakSponge::absorb(self, input)
+    }
+
+    fn squeeze(&mut self, length: usize) -> Vec<u8> {
+        KeccakSponge::squeeze(self, length)
+    }
+}
+
+// duplex built from any hash function: absorbing replaces the state by
+// H(state || 0 || len || input), squeezing outputs H(state || 1 || counter) blocks
+// and then ratchets the state with H(state || 2 || length)
+pub struct HashChain<H: HashFunction> {
+    hasher: H,
+    state: Vec<u8>,
+}
+
+impl<H> HashChain<H>
+where
+    H: HashFunction,
+{
+    pub fn new(hasher: H) -> Self {
+        Self {
+            hasher,
+            state: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, tag: u8, input: &[u8]) -> Vec<u8> {
+        let mut message = self.state.clone();
+        message.push(tag);
+        message.extend_from_slice(input);
+        self.hasher.hash(&message)
+    }
+}
+
+impl<H> Duplex for HashChain<H>
+where
+    H: HashFunction,
+{
+    fn absorb(&mut self, input: &[u8]) {
+        let mut framed = (input.len() as u64).to_le_bytes().to_vec();
+        framed.extend_from_slice(input);
+        self.state = self.update(0, &framed);
+    }
+
+    fn squeeze(&mut self, length: usize) -> Vec<u8> {
+        let mut output = Vec::with_capacity(length);
+        let mut counter = 0u64;
+        while output.len() < length {
+            output.extend(self.update(1, &counter.to_le_bytes()));
+            counter += 1;
+        }
+        output.truncate(length);
+
+        self.state = self.update(2, &(length as u64).to_le_bytes());
+        output
+    }
+}
+
+// canonical encoding of group elements for absorption
+pub trait GroupEncoding {
+    fn encode(&self) -> Vec<u8>;
+}
+
+// elements of Z_p^*, big-endian without leading zeros
+impl GroupEncoding for BigUint {
+    fn encode(&self) -> Vec<u8> {
+        self.to_bytes_be()
+    }
+}
+
+// curve points as 0 for the identity, otherwise 4 followed by the affine coordinates
+impl<C> GroupEncoding for JacobianPoint<C>
+where
+    C: WeierstrassConfig,
+    C::BaseField: FieldBytes,
+{
+    fn encode(&self) -> Vec<u8> {
+        self.to_affine().to_uncompressed()
+    }
+}
+
+// operation tags, so absorbed data can never be mistaken for a challenge request
+const MESSAGE: u8 = 0;
+const CHALLENGE: u8 = 1;
+
+// Fiat-Shamir transcript. Every operation absorbs a tag, the length-prefixed label and
+// the length-prefixed data, so prover and verifier derive the same challenges exactly
+// when they made the same calls with the same arguments.
+pub struct Transcript<D: Duplex> {
+    duplex: D,
+}
+
+impl Transcript<KeccakSponge> {
+    // SHAKE256 sponge, absorbing again after a squeeze permutes and continues
+    pub fn keccak(protocol: &[u8]) -> Self {
+        Self::new(KeccakSponge::new(136, 0x1f), protocol)
+    }
+}
+
+impl<H> Transcript<HashChain<H>>
+where
+    H: HashFunction,
+{
+    pub fn with_hash(hasher: H, protocol: &[u8]) -> Self {
+        Self::new(HashChain::new(hasher), protocol)
+    }
+}
+
+impl<D> Transcript<D>
+where
+    D: Duplex,
+{
+    pub fn new(duplex: D, protocol: &[u8]) -> Self {
+        let mut transcript = Self { duplex };
+        transcript.append_message(b"protocol", protocol);
+        transcript
+    }
+
+    fn frame(&mut self, tag: u8, label: &[u8], data: &[u8]) {
+        let mut input = vec![tag];
+        input.extend_from_slice(&(label.len() as u64).to_le_bytes());
+        input.extend_from_slice(label);
+        input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        input.extend_from_slice(data);
+        self.duplex.absorb(&input);
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.frame(MESSAGE, label, message);
+    }
+
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &value.to_le_bytes());
+    }
+
+    pub fn append_field_element<F: FieldBytes>(&mut self, label: &[u8], element: &FieldElement<F>) {
+        self.append_message(label, &F::to_bytes(&element.value));
+    }
+
+    pub fn append_field_elements<F: FieldBytes>(
+        &mut self,
+        label: &[u8],
+        elements: &[FieldElement<F>],
+    ) {
+        let mut bytes = Vec::with_capacity(elements.len() * F::ELEMENT_BYTES);
+        for element in elements {
+            bytes.extend(F::to_bytes(&element.value));
+        }
+        self.append_message(label, &bytes);
+    }
+
+    pub fn append_group_element<G: GroupEncoding>(&mut self, label: &[u8], element: &G) {
+        self.append_message(label, &element.encode());
+    }
+
+    pub fn challenge_bytes(&mut self, label: &[u8], length: usize) -> Vec<u8> {
+        self.frame(CHALLENGE, label, &(length as u64).to_le_bytes());
+        self.duplex.squeeze(length)
+    }
+
+    // ELEMENT_BYTES + 16 bytes read as a big-endian integer and reduced modulo p, the 128
+    // extra bits keep the result within 2^-128 of uniform. In binary fields the bytes are
+    // a polynomial over GF(2) reduced modulo the field polynomial, which is exactly uniform.
+    pub fn challenge_field_element<F: FieldBytes>(&mut self, label: &[u8]) -> FieldElement<F> {
+        let bytes = self.challenge_bytes(label, F::ELEMENT_BYTES + 16);
+        let radix = FieldElement::<F>::from(256);
+        let mut element = FieldElement::<F>::zero();
+        for byte in bytes {
+            element = &(&element * &radix) + &FieldElement::from(byte as u64);
+        }
+        element
+    }
+
+    pub fn challenge_field_elements<F: FieldBytes>(
+        &mut self,
+        label: &[u8],
+        count: usize,
+    ) -> Vec<FieldElement<F>> {
+        (0..count)
+            .map(|_| self.challenge_field_element(label))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::binary::BinaryField;
+    use crate::fields::u64_field::U64Field;
+    use crate::hash::md5::MessageDigestAlgorithm;
+    use crate::hash::sha3::Sha3_256;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+    type Element = FieldElement<Goldilocks>;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // a fixed sequence of operations exercising every kind of absorb and squeeze
+    fn run<D: Duplex>(transcript: &mut Transcript<D>) -> (Vec<u8>, Vec<Element>) {
+        transcript.append_message(b"statement", b"hello");
+        transcript.append_u64(b"rounds", 3);
+        transcript.append_field_elements(b"evaluations", &[Element::from(1), Element::from(2)]);
+        transcript.append_group_element(b"commitment", &BigUint::from(0xdeadbeefu64));
+        let bytes = transcript.challenge_bytes(b"bytes", 16);
+        let elements = transcript.challenge_field_elements(b"elements", 2);
+        (bytes, elements)
+    }
+
+    // pinned so that proofs stay reproducible across versions
+    #[test]
+    fn test_vectors() {
+        let (bytes, elements) = run(&mut Transcript::keccak(b"test"));
+        assert_eq!(hex(&bytes), "1925240cb3a3c2a07fecf7adbee393df");
+        assert_eq!(elements[0].value, 2545492432858636086);
+        assert_eq!(elements[1].value, 5308430760088459811);
+
+        let (bytes, elements) = run(&mut Transcript::with_hash(Sha3_256::new(), b"test"));
+        assert_eq!(hex(&bytes), "d8bce766b419bf45f27813c8bd956585");
+        assert_eq!(elements[0].value, 8660460819411460508);
+        assert_eq!(elements[1].value, 14951312210225603585);
+
+        let (bytes, elements) = run(&mut Transcript::with_hash(
+            MessageDigestAlgorithm::new(),
+            b"test",
+        ));
+        assert_eq!(hex(&bytes), "b69834e3039ef45750c557796c2b2799");
+        assert_eq!(elements[0].value, 4679165794369250701);
+        assert_eq!(elements[1].value, 13422937031471812543);
+    }
+
+    #[test]
+    fn test_deterministic_and_binding() {
+        let (bytes, elements) = run(&mut Transcript::keccak(b"test"));
+        assert_eq!(
+            run(&mut Transcript::keccak(b"test")),
+            (bytes.clone(), elements)
+        );
+
+        // different protocol label
+        assert_ne!(run(&mut Transcript::keccak(b"other")).0, bytes);
+
+        // same bytes split differently between label and message
+        let mut a = Transcript::keccak(b"test");
+        a.append_message(b"ab", b"c");
+        let mut b = Transcript::keccak(b"test");
+        b.append_message(b"a", b"bc");
+        assert_ne!(a.challenge_bytes(b"x", 32), b.challenge_bytes(b"x", 32));
+
+        // successive challenges differ
+        let mut transcript = Transcript::with_hash(Sha3_256::new(), b"test");
+        let first = transcript.challenge_bytes(b"x", 32);
+        assert_ne!(first, transcript.challenge_bytes(b"x", 32));
+    }
+
+    #[test]
+    fn test_field_challenges_reduce_wide_integers() {
+        let mut a = Transcript::keccak(b"test");
+        let mut b = Transcript::keccak(b"test");
+        let element: Element = a.challenge_field_element(b"x");
+        let wide = BigUint::from_bytes_be(&b.challenge_bytes(b"x", 24));
+
+        assert_eq!(
+            BigUint::from(element.value),
+            wide % BigUint::from(18446744069414584321u64)
+        );
+    }
+
+    #[test]
+    fn test_binary_field_challenges() {
+        let mut transcript = Transcript::keccak(b"test");
+        let elements =
+            transcript.challenge_field_elements::<BinaryField<4, 0x3>>(b"small field", 64);
+
+        assert!(elements.iter().all(|e| e.value < 16));
+        assert!(elements.iter().any(|e| e.value != elements[0].value));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod shamir;
+pub mod vss;
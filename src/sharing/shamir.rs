@@ -0,0 +1,333 @@
+use crate::fields::binary::BinaryField;
+use crate::fields::bytes::FieldBytes;
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+use crate::fields::u64_field::U64Field;
+use crate::polynomial::dense::Polynomial;
+use cryptography::Field;
+use std::marker::PhantomData;
+
+// fields that byte secrets can be packed into and shares serialized from
+pub trait SecretField: FieldBytes {
+    // bytes of secret packed into one element, every such value must be a distinct element
+    const SECRET_BYTES: usize;
+}
+
+impl<const MODULUS: u64> SecretField for U64Field<MODULUS> {
+    const SECRET_BYTES: usize = ((63 - MODULUS.leading_zeros()) / 8) as usize;
+}
+
+impl<const N: u32, const POLYNOMIAL: u128> SecretField for BinaryField<N, POLYNOMIAL> {
+    const SECRET_BYTES: usize = (N / 8) as usize;
+}
+
+impl<C, const N: usize> SecretField for MontgomeryField<C, N>
+where
+    C: MontgomeryConfig<N>,
+{
+    const SECRET_BYTES: usize =
+        ((64 * N as u32 - C::MODULUS[N - 1].leading_zeros() - 1) / 8) as usize;
+}
+
+// evaluations of the sharing polynomials at x, one value per secret element
+pub struct Share<F: Field> {
+    pub x: FieldElement<F>,
+    pub values: Vec<FieldElement<F>>,
+}
+
+impl<F> Clone for Share<F>
+where
+    F: Field,
+{
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<F> Share<F>
+where
+    F: SecretField,
+{
+    // x followed by the values, each as a big-endian element
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = F::to_bytes(&self.x.value);
+        for value in self.values.iter() {
+            bytes.extend(F::to_bytes(&value.value));
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.is_empty() || !bytes.len().is_multiple_of(F::ELEMENT_BYTES) {
+            return Err("share length is not a multiple of the element size");
+        }
+
+        let mut elements = bytes.chunks(F::ELEMENT_BYTES).map(|chunk| {
+            F::from_bytes(chunk)
+                .map(FieldElement::<F>::new)
+                .ok_or("share contains a non-canonical field element")
+        });
+        let x = elements.next().unwrap()?;
+        let values = elements.collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { x, values })
+    }
+}
+
+// t-of-n sharing, any threshold shares recover the secret and fewer reveal nothing about it
+pub struct Shamir<F: Field> {
+    threshold: usize,
+    shares: usize,
+    field: PhantomData<F>,
+}
+
+impl<F> Shamir<F>
+where
+    F: SecretField,
+{
+    pub fn new(threshold: usize, shares: usize) -> Result<Self, &'static str> {
+        if threshold == 0 {
+            return Err("threshold must be positive");
+        }
+        if threshold > shares {
+            return Err("threshold must not exceed the number of shares");
+        }
+        // share indices 1..=shares must be distinct non-zero elements
+        let max_shares = 1u128
+            .checked_shl(8 * F::SECRET_BYTES as u32)
+            .unwrap_or(u128::MAX);
+        if shares as u128 >= max_shares {
+            return Err("too many shares for the field");
+        }
+
+        Ok(Self {
+            threshold,
+            shares,
+            field: PhantomData,
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn shares(&self) -> usize {
+        self.shares
+    }
+
+    // random polynomial of degree threshold - 1 with the secret as constant term
+    pub fn random_polynomial(&self, secret: &FieldElement<F>) -> Polynomial<F> {
+        let mut coefficients = vec![secret.clone()];
+        coefficients.extend((1..self.threshold).map(|_| FieldElement::<F>::random()));
+        Polynomial::new(coefficients)
+    }
+
+    // share i holds every polynomial evaluated at x = i
+    pub fn evaluate_shares(&self, polynomials: &[Polynomial<F>]) -> Vec<Share<F>> {
+        (1..=self.shares as u64)
+            .map(|i| {
+                let x = FieldElement::<F>::from(i);
+                let values = polynomials.iter().map(|p| p.evaluate(&x)).collect();
+                Share { x, values }
+            })
+            .collect()
+    }
+
+    pub fn split(&self, secret: &[FieldElement<F>]) -> Vec<Share<F>> {
+        let polynomials: Vec<Polynomial<F>> =
+            secret.iter().map(|s| self.random_polynomial(s)).collect();
+        self.evaluate_shares(&polynomials)
+    }
+
+    // the secret is padded with 0x80 and zeros, then packed SECRET_BYTES at a time
+    pub fn split_bytes(&self, secret: &[u8]) -> Result<Vec<Share<F>>, &'static str> {
+        if F::SECRET_BYTES == 0 {
+            return Err("field is too small to hold a byte");
+        }
+
+        let mut padded = secret.to_vec();
+        padded.push(0x80);
+        padded.resize(padded.len().div_ceil(F::SECRET_BYTES) * F::SECRET_BYTES, 0);
+
+        let elements: Vec<FieldElement<F>> = padded
+            .chunks(F::SECRET_BYTES)
+            .map(|chunk| {
+                let mut bytes = vec![0; F::ELEMENT_BYTES - F::SECRET_BYTES];
+                bytes.extend_from_slice(chunk);
+                FieldElement::<F>::new(F::from_bytes(&bytes).unwrap())
+            })
+            .collect();
+
+        Ok(self.split(&elements))
+    }
+
+    // Lagrange interpolation at zero over all given shares
+    pub fn reconstruct(&self, shares: &[Share<F>]) -> Result<Vec<FieldElement<F>>, &'static str> {
+        if shares.len() < self.threshold {
+            return Err("not enough shares");
+        }
+        let length = shares[0].values.len();
+        if shares.iter().any(|share| share.values.len() != length) {
+            return Err("shares have different lengths");
+        }
+
+        let coefficients = lagrange_coefficients_at_zero(shares)?;
+        Ok((0..length)
+            .map(|k| {
+                let mut secret = FieldElement::<F>::zero();
+                for (share, coefficient) in shares.iter().zip(coefficients.iter()) {
+                    secret += &(&share.values[k] * coefficient);
+                }
+                secret
+            })
+            .collect())
+    }
+
+    pub fn reconstruct_bytes(&self, shares: &[Share<F>]) -> Result<Vec<u8>, &'static str> {
+        let elements = self.reconstruct(shares)?;
+
+        let mut padded = Vec::with_capacity(elements.len() * F::SECRET_BYTES);
+        for element in elements.iter() {
+            let bytes = F::to_bytes(&element.value);
+            let (high, low) = bytes.split_at(F::ELEMENT_BYTES - F::SECRET_BYTES);
+            if high.iter().any(|byte| *byte != 0) {
+                return Err("shares do not reconstruct a byte secret");
+            }
+            padded.extend_from_slice(low);
+        }
+
+        while padded.last() == Some(&0) {
+            padded.pop();
+        }
+        if padded.pop() != Some(0x80) {
+            return Err("shares do not reconstruct a byte secret");
+        }
+        Ok(padded)
+    }
+}
+
+// lambda_i = prod_{j != i} x_j / (x_j - x_i)
+fn lagrange_coefficients_at_zero<F: Field>(
+    shares: &[Share<F>],
+) -> Result<Vec<FieldElement<F>>, &'static str> {
+    shares
+        .iter()
+        .enumerate()
+        .map(|(i, share)| {
+            let mut numerator = FieldElement::<F>::one();
+            let mut denominator = FieldElement::<F>::one();
+            for (j, other) in shares.iter().enumerate() {
+                if i != j {
+                    numerator *= &other.x;
+                    denominator *= &(&other.x - &share.x);
+                }
+            }
+            let inverse = denominator.inverse().ok_or("duplicate share indices")?;
+            Ok(&numerator * &inverse)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::binary::{AesField, GhashField};
+
+    type Goldilocks = U64Field<18446744069414584321>;
+
+    #[test]
+    fn test_split_and_reconstruct_elements() {
+        let shamir = Shamir::<Goldilocks>::new(3, 5).unwrap();
+        let secret: Vec<FieldElement<Goldilocks>> =
+            (0..4).map(|_| FieldElement::random()).collect();
+
+        let shares = shamir.split(&secret);
+
+        assert_eq!(shares.len(), 5);
+        assert!(shamir.reconstruct(&shares[..3]).unwrap() == secret);
+        assert!(shamir.reconstruct(&shares[2..]).unwrap() == secret);
+        assert!(shamir.reconstruct(&shares).unwrap() == secret);
+        let subset = [shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert!(shamir.reconstruct(&subset).unwrap() == secret);
+        assert_eq!(shamir.reconstruct(&shares[..2]), Err("not enough shares"));
+    }
+
+    #[test]
+    fn test_byte_secrets_over_prime_field() {
+        let shamir = Shamir::<Goldilocks>::new(2, 3).unwrap();
+
+        for secret in [
+            b"".to_vec(),
+            b"seven!!".to_vec(),
+            b"correct horse battery staple".to_vec(),
+            vec![0; 9],
+        ] {
+            let shares = shamir.split_bytes(&secret).unwrap();
+            assert_eq!(shamir.reconstruct_bytes(&shares[1..]).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn test_byte_secrets_over_gf256() {
+        let shamir = Shamir::<AesField>::new(4, 255).unwrap();
+        let secret = b"byte-wise shares".to_vec();
+
+        let shares = shamir.split_bytes(&secret).unwrap();
+
+        // one share byte per secret byte plus the padding byte
+        assert_eq!(shares[0].values.len(), secret.len() + 1);
+        assert_eq!(shamir.reconstruct_bytes(&shares[100..104]).unwrap(), secret);
+        assert!(Shamir::<AesField>::new(2, 256).is_err());
+    }
+
+    #[test]
+    fn test_share_serialization() {
+        let shamir = Shamir::<Goldilocks>::new(2, 4).unwrap();
+        let shares = shamir.split_bytes(b"serialize me").unwrap();
+
+        let decoded: Vec<Share<Goldilocks>> = shares
+            .iter()
+            .map(|share| Share::from_bytes(&share.to_bytes()).unwrap())
+            .collect();
+
+        assert_eq!(shares[0].to_bytes().len(), 8 * (1 + 2));
+        assert_eq!(
+            shamir.reconstruct_bytes(&decoded[2..]).unwrap(),
+            b"serialize me"
+        );
+        assert!(Share::<Goldilocks>::from_bytes(&[1, 2, 3]).is_err());
+        assert!(Share::<Goldilocks>::from_bytes(&[0xff; 16]).is_err());
+
+        let gcm = Shamir::<GhashField>::new(2, 2).unwrap();
+        let share = gcm.split_bytes(&[7; 20]).unwrap().remove(0);
+        assert_eq!(share.to_bytes().len(), 16 * 3);
+        assert!(
+            Share::<GhashField>::from_bytes(&share.to_bytes())
+                .unwrap()
+                .values
+                == share.values
+        );
+    }
+
+    #[test]
+    fn test_invalid_parameters_and_shares() {
+        assert!(Shamir::<Goldilocks>::new(0, 3).is_err());
+        assert!(Shamir::<Goldilocks>::new(4, 3).is_err());
+
+        let shamir = Shamir::<Goldilocks>::new(2, 3).unwrap();
+        let shares = shamir.split_bytes(b"secret").unwrap();
+        let duplicate = [shares[0].clone(), shares[0].clone()];
+
+        assert_eq!(
+            shamir.reconstruct(&duplicate),
+            Err("duplicate share indices")
+        );
+
+        let mut truncated = shares[1].clone();
+        truncated.values.pop();
+        assert!(shamir.reconstruct(&[shares[0].clone(), truncated]).is_err());
+    }
+}
@@ -0,0 +1,311 @@
+use crate::fields::element::FieldElement;
+use crate::fields::montgomery::{MontgomeryConfig, MontgomeryField};
+use crate::polynomial::dense::Polynomial;
+use crate::sharing::shamir::{SecretField, Shamir, Share};
+use cryptography::Field;
+use num_bigint::BigUint;
+use num_traits::One;
+
+// discrete logarithms modulo p and in the order q subgroup must both be out of reach,
+// the commitments g^(a_j) otherwise give away the secret
+const MIN_MODULUS_BITS: u64 = 2048;
+const MIN_ORDER_BITS: u64 = 256;
+
+// the 256-bit prime q = 2^256 - 432420386565659656852420866394968145599, also the order of
+// the secp256k1 group
+#[derive(Debug)]
+pub struct ScalarConfig;
+
+impl MontgomeryConfig<4> for ScalarConfig {
+    const MODULUS: [u64; 4] = [
+        0xbfd25e8cd0364141,
+        0xbaaedce6af48a03b,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+    ];
+    const GENERATOR: u64 = 7;
+}
+
+// exponents of the modp_2048 group
+pub type Scalar = MontgomeryField<ScalarConfig, 4>;
+
+// order q subgroup of Z_p^* where p = kq + 1, with generators g and h whose
+// relative discrete logarithm nobody knows
+pub struct SchnorrGroup {
+    pub p: BigUint,
+    pub q: BigUint,
+    pub g: BigUint,
+    pub h: BigUint,
+}
+
+impl SchnorrGroup {
+    pub fn new(p: BigUint, q: BigUint, g: BigUint, h: BigUint) -> Result<Self, &'static str> {
+        let one = BigUint::one();
+        if p <= one || q <= one || (&p - &one) % &q != BigUint::from(0u64) {
+            return Err("q must divide p - 1");
+        }
+        if p.bits() < MIN_MODULUS_BITS || q.bits() < MIN_ORDER_BITS {
+            return Err("group is too small to hide discrete logarithms");
+        }
+        for generator in [&g, &h] {
+            if *generator <= one || *generator >= p || generator.modpow(&q, &p) != one {
+                return Err("generators must have order q");
+            }
+        }
+        if g == h {
+            return Err("generators must be distinct");
+        }
+
+        Ok(Self { p, q, g, h })
+    }
+
+    // 2048-bit p = (2^1792 + 3134) q + 1 for the 256-bit q of Scalar, the smallest such k,
+    // with g = 2^k and h = 3^k
+    pub fn modp_2048() -> Self {
+        let q = Scalar::characteristic();
+        let k = (BigUint::one() << 1792) + BigUint::from(3134u64);
+        let p = &k * &q + BigUint::one();
+        let g = BigUint::from(2u64).modpow(&k, &p);
+        let h = BigUint::from(3u64).modpow(&k, &p);
+
+        Self { p, q, g, h }
+    }
+
+    // the secret field must be the integers modulo q, and q large enough
+    fn check_field<F: SecretField>(&self) -> Result<(), &'static str> {
+        if F::characteristic() != self.q {
+            return Err("group order must equal the field characteristic");
+        }
+        if self.q.bits() < MIN_ORDER_BITS {
+            return Err("group is too small to hide discrete logarithms");
+        }
+        Ok(())
+    }
+
+    fn exponent<F: SecretField>(a: &FieldElement<F>) -> BigUint {
+        BigUint::from_bytes_be(&F::to_bytes(&a.value))
+    }
+
+    // prod C_j ^ (x^j), the commitment to the polynomial evaluated at x
+    fn evaluate_commitments<F: SecretField>(
+        &self,
+        commitments: &[BigUint],
+        x: &FieldElement<F>,
+    ) -> BigUint {
+        let mut result = BigUint::one();
+        let mut power = FieldElement::<F>::one();
+        for commitment in commitments {
+            result = result * commitment.modpow(&Self::exponent(&power), &self.p) % &self.p;
+            power *= x;
+        }
+        result
+    }
+}
+
+// Shamir sharing of a single field element with public commitments g^(a_j) to the coefficients
+pub struct FeldmanVss<F: SecretField> {
+    group: SchnorrGroup,
+    shamir: Shamir<F>,
+}
+
+impl<F> FeldmanVss<F>
+where
+    F: SecretField,
+{
+    pub fn new(group: SchnorrGroup, threshold: usize, shares: usize) -> Result<Self, &'static str> {
+        group.check_field::<F>()?;
+        let shamir = Shamir::new(threshold, shares)?;
+
+        Ok(Self { group, shamir })
+    }
+
+    pub fn split(&self, secret: &FieldElement<F>) -> (Vec<Share<F>>, Vec<BigUint>) {
+        let polynomial = self.shamir.random_polynomial(secret);
+        let commitments = (0..self.shamir.threshold())
+            .map(|j| {
+                let exponent = SchnorrGroup::exponent(&polynomial.coefficient(j));
+                self.group.g.modpow(&exponent, &self.group.p)
+            })
+            .collect();
+
+        (self.shamir.evaluate_shares(&[polynomial]), commitments)
+    }
+
+    // g^y == prod C_j ^ (x^j)
+    pub fn verify(&self, share: &Share<F>, commitments: &[BigUint]) -> bool {
+        if share.values.len() != 1 || commitments.len() != self.shamir.threshold() {
+            return false;
+        }
+
+        let expected = self.group.evaluate_commitments(commitments, &share.x);
+        let exponent = SchnorrGroup::exponent(&share.values[0]);
+        self.group.g.modpow(&exponent, &self.group.p) == expected
+    }
+
+    pub fn reconstruct(&self, shares: &[Share<F>]) -> Result<FieldElement<F>, &'static str> {
+        let mut secret = self.shamir.reconstruct(shares)?;
+        secret.pop().ok_or("shares hold no secret")
+    }
+}
+
+// share of the secret together with the share of the blinding polynomial
+pub struct PedersenShare<F: SecretField> {
+    pub share: Share<F>,
+    pub blinding: FieldElement<F>,
+}
+
+impl<F> Clone for PedersenShare<F>
+where
+    F: SecretField,
+{
+    fn clone(&self) -> Self {
+        Self {
+            share: self.share.clone(),
+            blinding: self.blinding.clone(),
+        }
+    }
+}
+
+// like Feldman but the commitments g^(a_j) h^(b_j) hide the secret unconditionally
+pub struct PedersenVss<F: SecretField> {
+    group: SchnorrGroup,
+    shamir: Shamir<F>,
+}
+
+impl<F> PedersenVss<F>
+where
+    F: SecretField,
+{
+    pub fn new(group: SchnorrGroup, threshold: usize, shares: usize) -> Result<Self, &'static str> {
+        group.check_field::<F>()?;
+        let shamir = Shamir::new(threshold, shares)?;
+
+        Ok(Self { group, shamir })
+    }
+
+    pub fn split(&self, secret: &FieldElement<F>) -> (Vec<PedersenShare<F>>, Vec<BigUint>) {
+        let polynomial = self.shamir.random_polynomial(secret);
+        let blinding: Polynomial<F> = self.shamir.random_polynomial(&FieldElement::random());
+
+        let p = &self.group.p;
+        let commitments = (0..self.shamir.threshold())
+            .map(|j| {
+                let a = SchnorrGroup::exponent(&polynomial.coefficient(j));
+                let b = SchnorrGroup::exponent(&blinding.coefficient(j));
+                self.group.g.modpow(&a, p) * self.group.h.modpow(&b, p) % p
+            })
+            .collect();
+
+        let shares = self
+            .shamir
+            .evaluate_shares(&[polynomial, blinding])
+            .into_iter()
+            .map(|mut share| {
+                let blinding = share.values.pop().unwrap();
+                PedersenShare { share, blinding }
+            })
+            .collect();
+
+        (shares, commitments)
+    }
+
+    // g^y h^y' == prod C_j ^ (x^j)
+    pub fn verify(&self, share: &PedersenShare<F>, commitments: &[BigUint]) -> bool {
+        if share.share.values.len() != 1 || commitments.len() != self.shamir.threshold() {
+            return false;
+        }
+
+        let p = &self.group.p;
+        let expected = self.group.evaluate_commitments(commitments, &share.share.x);
+        let a = SchnorrGroup::exponent(&share.share.values[0]);
+        let b = SchnorrGroup::exponent(&share.blinding);
+        self.group.g.modpow(&a, p) * self.group.h.modpow(&b, p) % p == expected
+    }
+
+    pub fn reconstruct(
+        &self,
+        shares: &[PedersenShare<F>],
+    ) -> Result<FieldElement<F>, &'static str> {
+        let shares: Vec<Share<F>> = shares.iter().map(|share| share.share.clone()).collect();
+        let mut secret = self.shamir.reconstruct(&shares)?;
+        secret.pop().ok_or("shares hold no secret")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::binary::AesField;
+    use crate::fields::u64_field::U64Field;
+    use crate::primes::prime::Prime;
+
+    type Goldilocks = U64Field<18446744069414584321>;
+
+    #[test]
+    fn test_modp_2048_group() {
+        let group = SchnorrGroup::modp_2048();
+
+        assert_eq!(group.p.bits(), 2048);
+        assert_eq!(group.q.bits(), 256);
+        assert!(group.p.is_prime(8));
+        assert!(SchnorrGroup::new(group.p, group.q, group.g, group.h).is_ok());
+    }
+
+    #[test]
+    fn test_feldman() {
+        let vss = FeldmanVss::<Scalar>::new(SchnorrGroup::modp_2048(), 3, 5).unwrap();
+        let secret = FieldElement::<Scalar>::random();
+
+        let (shares, commitments) = vss.split(&secret);
+
+        assert!(shares.iter().all(|share| vss.verify(share, &commitments)));
+        assert!(vss.reconstruct(&shares[1..4]).unwrap() == secret);
+
+        let mut forged = shares[0].clone();
+        forged.values[0] += &FieldElement::one();
+        assert!(!vss.verify(&forged, &commitments));
+        assert!(!vss.verify(&shares[0], &commitments[1..]));
+    }
+
+    #[test]
+    fn test_pedersen() {
+        let vss = PedersenVss::<Scalar>::new(SchnorrGroup::modp_2048(), 2, 4).unwrap();
+        let secret = FieldElement::<Scalar>::random();
+
+        let (shares, commitments) = vss.split(&secret);
+
+        assert!(shares.iter().all(|share| vss.verify(share, &commitments)));
+        assert!(vss.reconstruct(&shares[2..]).unwrap() == secret);
+
+        let mut forged = shares[1].clone();
+        forged.blinding += &FieldElement::one();
+        assert!(!vss.verify(&forged, &commitments));
+    }
+
+    #[test]
+    fn test_field_must_match_group() {
+        assert!(FeldmanVss::<AesField>::new(SchnorrGroup::modp_2048(), 2, 3).is_err());
+        assert!(PedersenVss::<AesField>::new(SchnorrGroup::modp_2048(), 2, 3).is_err());
+
+        let group = SchnorrGroup::modp_2048();
+        assert!(
+            SchnorrGroup::new(group.p.clone(), group.q.clone(), group.g.clone(), group.g).is_err()
+        );
+        assert!(SchnorrGroup::new(group.p, group.q, BigUint::from(2u64), group.h).is_err());
+    }
+
+    // a 64-bit q lets anyone solve for the coefficients behind g^(a_j)
+    #[test]
+    fn test_small_group_is_rejected() {
+        let q = Goldilocks::characteristic();
+        let k = (BigUint::one() << 1984) + BigUint::from(962u64);
+        let p = &k * &q + BigUint::one();
+        let g = BigUint::from(2u64).modpow(&k, &p);
+        let h = BigUint::from(3u64).modpow(&k, &p);
+        assert!(p.is_prime(8));
+        assert!(SchnorrGroup::new(p.clone(), q.clone(), g.clone(), h.clone()).is_err());
+
+        let group = SchnorrGroup { p, q, g, h };
+        assert!(FeldmanVss::<Goldilocks>::new(group, 2, 3).is_err());
+    }
+}